@@ -0,0 +1,69 @@
+use syn::{Attribute, Lit, Meta, NestedMeta};
+
+/// Where a `#[dict(...)]` attribute is placed
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub enum Position {
+    Container,
+    Variant,
+    Field,
+}
+
+/// The options set with `#[dict(...)]` attributes
+#[derive(Default)]
+pub struct Attrs {
+    /// the name of the struct or of the variant in the `_type` key, the name of a field's key
+    /// or the string a unit variant is converted to
+    pub rename: Option<String>,
+
+    /// the key holding the type name of the dicts, `_type` by default
+    pub tag: Option<String>,
+
+    /// skip a field, which is filled with its default value when extracted from a dict, or
+    /// a variant when extracting an enum; variants can't be skipped when converting an enum
+    pub skip: bool,
+}
+
+fn string(lit: &Lit) -> syn::Result<String> {
+    match lit {
+        Lit::Str(s) => Ok(s.value()),
+        other => Err(syn::Error::new_spanned(other, "expected a string literal")),
+    }
+}
+
+pub fn parse_attrs(attrs: &[Attribute], position: Position) -> syn::Result<Attrs> {
+    let mut result = Attrs::default();
+
+    for attr in attrs.iter().filter(|a| a.path.is_ident("dict")) {
+        let list = match attr.parse_meta()? {
+            Meta::List(list) => list,
+            other => return Err(syn::Error::new_spanned(other, "expected #[dict(...)]")),
+        };
+
+        for nested in list.nested.iter() {
+            match nested {
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("rename") => {
+                    result.rename = Some(string(&nv.lit)?);
+                }
+                NestedMeta::Meta(Meta::NameValue(nv))
+                    if nv.path.is_ident("tag") && position == Position::Container =>
+                {
+                    result.tag = Some(string(&nv.lit)?);
+                }
+                NestedMeta::Meta(Meta::Path(path))
+                    if path.is_ident("skip") && position != Position::Container =>
+                {
+                    result.skip = true;
+                }
+                other => {
+                    return Err(syn::Error::new_spanned(
+                        other,
+                        "unsupported dict attribute, expected `rename = \"...\"`, \
+                         `tag = \"...\"` on containers or `skip` on variants and fields",
+                    ))
+                }
+            }
+        }
+    }
+
+    Ok(result)
+}
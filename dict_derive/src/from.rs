@@ -0,0 +1,443 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+
+use syn::{Data, DataEnum, DataStruct, DeriveInput, Field, Fields, Ident, Type};
+
+use crate::attrs::{parse_attrs, Attrs, Position};
+use crate::utils::generic_argument;
+
+/// Generate an expression extracting a value of type `ty` from the Python object `value`,
+/// evaluating to a `Result` with the error and its path. Lists are extracted item by item so
+/// that the path of the errors can name the index of the offending item.
+fn extract_value(ty: &Type, value: &Ident, depth: usize) -> syn::Result<TokenStream> {
+    if let Some(inner) = generic_argument(ty, "Option")? {
+        let extract = extract_value(inner, value, depth)?;
+
+        return Ok(quote! {
+            if #value.is_none() {
+                Ok(None)
+            } else {
+                (#extract).map(Some)
+            }
+        });
+    }
+
+    if let Some(inner) = generic_argument(ty, "Box")? {
+        let extract = extract_value(inner, value, depth)?;
+
+        return Ok(quote! { (#extract).map(Box::new) });
+    }
+
+    if let Some(inner) = generic_argument(ty, "Vec")? {
+        let item = Ident::new(&format!("item{}", depth), Span::call_site());
+        let extract = extract_value(inner, &item, depth + 1)?;
+
+        return Ok(quote! {
+            match <::pyo3::types::PyList as PyTryFrom>::try_from(#value) {
+                Ok(list) => list
+                    .iter()
+                    .enumerate()
+                    .map(|(i, #item)| {
+                        (#extract).map_err(|(err, mut path)| {
+                            path.insert(0, format!("[{}]", i));
+                            (err, path)
+                        })
+                    })
+                    .collect::<Result<Vec<_>, (PyErr, Vec<String>)>>(),
+                Err(_) => Err((
+                    PyErr::new::<TypeError, _>(format!(
+                        "expected a list, got {}",
+                        #value.get_type().name()
+                    )),
+                    Vec::new(),
+                )),
+            }
+        });
+    }
+
+    Ok(quote_spanned! {ty.span()=>
+        <#ty>::extract_with_path(#value)
+    })
+}
+
+/// Generate an expression extracting the value of `field` from the item `key` of `dict`,
+/// returning early on errors. Skipped fields are filled with their default value.
+fn extract_field(field: &Field, key: &str, skip: bool) -> syn::Result<TokenStream> {
+    if skip {
+        return Ok(quote_spanned! {field.ty.span()=> Default::default() });
+    }
+
+    let value = Ident::new("value", Span::call_site());
+    let extract = extract_value(&field.ty, &value, 0)?;
+
+    let missing = match generic_argument(&field.ty, "Option")? {
+        Some(_) => quote!(Ok(None)),
+        None => quote! {
+            Err((PyErr::new::<ValueError, _>("missing key"), Vec::new()))
+        },
+    };
+
+    Ok(quote_spanned! {field.ty.span()=>
+        match dict.get_item(#key) {
+            Some(#value) => #extract,
+            None => #missing,
+        }
+        .map_err(|(err, mut path)| {
+            path.insert(0, #key.to_string());
+            (err, path)
+        })?
+    })
+}
+
+/// Generate the `name: value` pairs initialising the named `fields` from `dict`
+fn extract_named_fields(fields: &Fields) -> syn::Result<Vec<TokenStream>> {
+    let mut values = Vec::new();
+
+    for field in fields.iter() {
+        let ident = match &field.ident {
+            Some(i) => i,
+            None => {
+                return Err(syn::Error::new(
+                    field.span(),
+                    "Unnamed fields are not supported",
+                ))
+            }
+        };
+
+        let field_attrs = parse_attrs(&field.attrs, Position::Field)?;
+        let key = field_attrs.rename.unwrap_or_else(|| ident.to_string());
+        let value = extract_field(field, &key, field_attrs.skip)?;
+
+        values.push(quote!(#ident: #value));
+    }
+
+    Ok(values)
+}
+
+/// The bodies of the functions generated for a type
+struct Body {
+    /// extracting a value from `obj`
+    extract: TokenStream,
+
+    /// telling if `obj` has the type name, or the name of a unit variant, of the type
+    matches: TokenStream,
+}
+
+fn struct_body(name: &Ident, data: DataStruct, attrs: Attrs) -> syn::Result<Body> {
+    let values = extract_named_fields(&data.fields)?;
+    let tag = attrs.tag.unwrap_or_else(|| "_type".to_string());
+    let type_name = attrs.rename.unwrap_or_else(|| name.to_string());
+
+    let matches = quote! {
+        <PyDict as PyTryFrom>::try_from(obj)
+            .ok()
+            .and_then(|dict| dict.get_item(#tag))
+            .and_then(|value| value.extract::<&str>().ok())
+            == Some(#type_name)
+    };
+
+    let extract = quote! {
+        let dict = <PyDict as PyTryFrom>::try_from(obj).map_err(|_| {
+            let err = PyErr::new::<TypeError, _>(format!(
+                "expected a dict, got {}",
+                obj.get_type().name()
+            ));
+
+            (err, Vec::new())
+        })?;
+
+        let err = match dict.get_item(#tag) {
+            Some(value) if value.extract::<&str>().ok() == Some(#type_name) => None,
+            Some(value) => Some(match value.repr() {
+                Ok(repr) => PyErr::new::<ValueError, _>(format!(
+                    "expected {} {}, got {}",
+                    #tag,
+                    #type_name,
+                    repr
+                )),
+                Err(err) => err,
+            }),
+            None => Some(PyErr::new::<ValueError, _>(format!(
+                "missing key {}, expected {}",
+                #tag,
+                #type_name
+            ))),
+        };
+
+        if let Some(err) = err {
+            return Err((err, Vec::new()));
+        }
+
+        Ok(#name {
+            #(#values),*
+        })
+    };
+
+    Ok(Body { extract, matches })
+}
+
+/// Unit variants are extracted from their name, or from their type name in the dict if the
+/// enum has a tag, as struct variants and newtype variants with a renamed field. The other
+/// newtype variants are extracted as their value, by the first one whose type matches it.
+fn enum_body(name: &Ident, data: DataEnum, attrs: Attrs) -> syn::Result<Body> {
+    let has_tag = attrs.tag.is_some();
+    let tag = attrs.tag.unwrap_or_else(|| "_type".to_string());
+    let enum_name = name.to_string();
+
+    let mut string_names = Vec::new();
+    let mut string_arms = Vec::new();
+    let mut tagged_names = Vec::new();
+    let mut tagged_arms = Vec::new();
+    let mut fallback_types = Vec::new();
+    let mut fallbacks = Vec::new();
+
+    for variant in data.variants {
+        let ident = &variant.ident;
+        let variant_attrs = parse_attrs(&variant.attrs, Position::Variant)?;
+        let type_name = variant_attrs.rename.unwrap_or_else(|| ident.to_string());
+
+        if variant_attrs.skip {
+            continue;
+        }
+
+        match &variant.fields {
+            Fields::Unit if has_tag => {
+                tagged_arms.push(quote!(#type_name => return Ok(#name::#ident)));
+                tagged_names.push(type_name);
+            }
+            Fields::Unit => {
+                string_arms.push(quote!(#type_name => return Ok(#name::#ident)));
+                string_names.push(type_name);
+            }
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                let field = &fields.unnamed[0];
+                let field_attrs = parse_attrs(&field.attrs, Position::Field)?;
+
+                match field_attrs.rename {
+                    Some(key) => {
+                        let value = extract_field(field, &key, false)?;
+                        tagged_arms.push(quote!(#type_name => return Ok(#name::#ident(#value))));
+                        tagged_names.push(type_name);
+                    }
+                    None => {
+                        let ty = &field.ty;
+
+                        fallbacks.push(quote! {
+                            if <#ty>::matches_tag(obj) {
+                                return <#ty>::extract_with_path(obj).map(#name::#ident);
+                            }
+                        });
+                        fallback_types.push(ty.clone());
+                    }
+                }
+            }
+            Fields::Unnamed(fields) => {
+                return Err(syn::Error::new(
+                    fields.span(),
+                    "Tuple variants with more than one field are not supported",
+                ))
+            }
+            Fields::Named(_) => {
+                let values = extract_named_fields(&variant.fields)?;
+                tagged_arms.push(quote! {
+                    #type_name => return Ok(#name::#ident { #(#values),* })
+                });
+                tagged_names.push(type_name);
+            }
+        }
+    }
+
+    let (string_match, string_matches) = match string_arms.is_empty() {
+        true => (quote!(), quote!()),
+        false => (
+            quote! {
+                if let Ok(value) = obj.extract::<&str>() {
+                    match value {
+                        #(#string_arms,)*
+                        _ => (),
+                    }
+                }
+            },
+            quote! {
+                if let Ok(value) = obj.extract::<&str>() {
+                    if [#(#string_names),*].contains(&value) {
+                        return true;
+                    }
+                }
+            },
+        ),
+    };
+
+    let type_name = quote! {
+        let dict = <PyDict as PyTryFrom>::try_from(obj).ok();
+        let type_name = dict
+            .and_then(|dict| dict.get_item(#tag))
+            .and_then(|value| value.extract::<&str>().ok());
+    };
+
+    let extract = quote! {
+        #string_match
+
+        #type_name
+
+        match (dict, type_name) {
+            #[allow(unused_variables)]
+            (Some(dict), Some(type_name)) => match type_name {
+                #(#tagged_arms,)*
+                _ => (),
+            },
+            _ => (),
+        }
+
+        #(#fallbacks)*
+
+        let err = match (type_name, obj.repr()) {
+            (Some(type_name), _) => PyErr::new::<ValueError, _>(format!(
+                "unexpected {} {} for {}",
+                #tag,
+                type_name,
+                #enum_name
+            )),
+            (None, Ok(repr)) => PyErr::new::<ValueError, _>(format!(
+                "unexpected value {} for {}",
+                repr,
+                #enum_name
+            )),
+            (None, Err(err)) => err,
+        };
+
+        Err((err, Vec::new()))
+    };
+
+    let matches = quote! {
+        #string_matches
+
+        #type_name
+
+        let tagged_names: &[&str] = &[#(#tagged_names),*];
+
+        matches!(type_name, Some(type_name) if tagged_names.contains(&type_name))
+            #(|| <#fallback_types>::matches_tag(obj))*
+    };
+
+    Ok(Body { extract, matches })
+}
+
+pub fn from_impl(ast: DeriveInput) -> TokenStream {
+    let DeriveInput {
+        ident: name,
+        attrs,
+        generics,
+        data,
+        ..
+    } = ast;
+
+    let body = parse_attrs(&attrs, Position::Container).and_then(|attrs| match data {
+        Data::Struct(s) => struct_body(&name, s, attrs),
+        Data::Enum(e) => enum_body(&name, e, attrs),
+        Data::Union(u) => Err(syn::Error::new(
+            u.union_token.span,
+            "Deriving unions is not supported",
+        )),
+    });
+
+    let body = match body {
+        Ok(body) => body,
+        Err(e) => return e.to_compile_error(),
+    };
+
+    let mut source_generics = generics.clone();
+    source_generics.params.insert(0, syn::parse_quote!('source));
+
+    let (impl_generics, _, _) = source_generics.split_for_impl();
+    let (_, ty_generics, where_clause) = generics.split_for_impl();
+
+    let Body { extract, matches } = body;
+
+    // The derived types have their own `matches_tag()` and `extract_with_path()`, which take
+    // precedence over the ones of this trait, so that they are matched from their type name and
+    // the path of their errors is kept
+    let extract_trait = quote! {
+        #[allow(dead_code)]
+        trait Extract<'s>: Sized {
+            fn matches_tag(obj: &'s PyAny) -> bool;
+            fn extract_with_path(obj: &'s PyAny) -> Result<Self, (::pyo3::PyErr, Vec<String>)>;
+        }
+
+        impl<'s, T: FromPyObject<'s>> Extract<'s> for T {
+            fn matches_tag(obj: &'s PyAny) -> bool {
+                obj.extract::<T>().is_ok()
+            }
+
+            fn extract_with_path(obj: &'s PyAny) -> Result<Self, (::pyo3::PyErr, Vec<String>)> {
+                obj.extract().map_err(|err| (err, Vec::new()))
+            }
+        }
+    };
+
+    quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Tell if a Python object has the type name of this type, or the name of one of its
+            /// unit variants, without checking its fields
+            #[allow(dead_code)]
+            pub fn matches_tag(obj: &::pyo3::types::PyAny) -> bool {
+                use ::pyo3::{FromPyObject, PyTryFrom};
+                use ::pyo3::types::{PyAny, PyDict};
+
+                #extract_trait
+
+                #matches
+            }
+
+            /// Extract from a Python object, returning the errors with the path of the offending
+            /// value: the keys and the `[index]` of the list items, from the outermost one
+            #[allow(dead_code)]
+            pub fn extract_with_path(
+                obj: &'source ::pyo3::types::PyAny,
+            ) -> Result<Self, (::pyo3::PyErr, Vec<String>)> {
+                use ::pyo3::{FromPyObject, ObjectProtocol, PyErr, PyTryFrom};
+                #[allow(unused_imports)]
+                use ::pyo3::exceptions::{ValueError, TypeError};
+                use ::pyo3::types::{PyAny, PyDict};
+
+                #extract_trait
+
+                #extract
+            }
+        }
+
+        impl #impl_generics ::pyo3::FromPyObject<'source> for #name #ty_generics #where_clause {
+            fn extract(obj: &'source ::pyo3::types::PyAny) -> ::pyo3::PyResult<Self> {
+                use ::pyo3::PyErr;
+                use ::pyo3::exceptions::{ValueError, TypeError};
+
+                let (err, path) = match Self::extract_with_path(obj) {
+                    Ok(value) => return Ok(value),
+                    Err((err, path)) if path.is_empty() => return Err(err),
+                    Err(error) => error,
+                };
+
+                // Prefix the path of the offending value to the message of the error
+                let mut location = String::new();
+
+                for segment in path {
+                    if !location.is_empty() && !segment.starts_with('[') {
+                        location.push('.');
+                    }
+
+                    location.push_str(&segment);
+                }
+
+                let py = obj.py();
+                let is_type_error = err.is_instance::<TypeError>(py);
+                let message = format!("`{}`: {}", location, err.instance(py));
+
+                match is_type_error {
+                    true => Err(PyErr::new::<TypeError, _>(message)),
+                    false => Err(PyErr::new::<ValueError, _>(message)),
+                }
+            }
+        }
+    }
+}
@@ -1,79 +1,255 @@
-use proc_macro2::TokenStream;
+use proc_macro2::{Span, TokenStream};
 use quote::{quote, quote_spanned};
 use syn::spanned::Spanned;
 
-use syn::{Data, DeriveInput, Field};
+use syn::{Data, DataEnum, DataStruct, DeriveInput, Fields, Ident, Type};
 
-use crate::utils::{which_field_type, MappedFieldType};
+use crate::attrs::{parse_attrs, Attrs, Position};
+use crate::utils::generic_argument;
 
-fn map_fields(field: Field) -> TokenStream {
-    let ident = match &field.ident {
-        Some(i) => i,
-        None => {
-            return syn::Error::new(field.span(), "Unnamed fields are not supported")
-                .to_compile_error()
-        }
-    };
+/// Generate an expression converting `value`, of type `ty`, into a `PyObject` and evaluating
+/// to a `PyResult`. Boxes are unwrapped, also within options and lists, and the values with a
+/// `try_into_py()` method, like the derived types, are converted with it.
+fn into_value(ty: &Type, value: TokenStream, depth: usize) -> syn::Result<TokenStream> {
+    let item = Ident::new(&format!("item{}", depth), Span::call_site());
 
-    let name = ident.to_string();
+    if let Some(inner) = generic_argument(ty, "Box")? {
+        let convert = into_value(inner, quote!(#item), depth + 1)?;
 
-    // TODO: find a way to do this without the .expect
-    match which_field_type(&field.ty) {
-        MappedFieldType::IsBox => {
-            quote_spanned! {field.ty.span()=>
-                dict.set_item(#name, IntoPy::<PyObject>::into_py(*self.#ident, py))
-                    .expect("Failed to set_item on dict");
-            }
-        }
-        MappedFieldType::IsOptionBox => {
-            quote_spanned! {field.ty.span()=>
-                dict.set_item(#name, IntoPy::<PyObject>::into_py(self.#ident.map(|v| *v), py))
-                    .expect("Failed to set_item on dict");
+        return Ok(quote! {{
+            let #item = *#value;
+            #convert
+        }});
+    }
+
+    if let Some(inner) = generic_argument(ty, "Option")? {
+        let convert = into_value(inner, quote!(#item), depth + 1)?;
+
+        return Ok(quote! {
+            match #value {
+                Some(#item) => #convert,
+                None => Ok(py.None()),
             }
-        }
-        _ => {
-            quote_spanned! {field.ty.span()=>
-                dict.set_item(#name, IntoPy::<PyObject>::into_py(self.#ident, py))
-                    .expect("Failed to set_item on dict");
+        });
+    }
+
+    if let Some(inner) = generic_argument(ty, "Vec")? {
+        let convert = into_value(inner, quote!(#item), depth + 1)?;
+
+        return Ok(quote! {
+            #value
+                .into_iter()
+                .map(|#item| #convert)
+                .collect::<PyResult<Vec<PyObject>>>()
+                .map(|items| IntoPy::<PyObject>::into_py(items, py))
+        });
+    }
+
+    Ok(quote_spanned! {ty.span()=>
+        (#value).try_into_py(py)
+    })
+}
+
+fn set_item(key: &str, value: TokenStream) -> TokenStream {
+    quote! {
+        dict.set_item(#key, #value?)?;
+    }
+}
+
+/// Generate a dict with the type name in the `tag` key and the given items
+fn into_dict(tag: &str, type_name: &str, setters: Vec<TokenStream>) -> TokenStream {
+    quote! {{
+        let dict = PyDict::new(py);
+        dict.set_item(#tag, #type_name)?;
+        #(#setters)*
+        Ok(dict.into())
+    }}
+}
+
+fn struct_body(name: &Ident, data: DataStruct, attrs: Attrs) -> syn::Result<TokenStream> {
+    let mut setters = Vec::new();
+
+    for field in data.fields {
+        let ident = match &field.ident {
+            Some(i) => i,
+            None => {
+                return Err(syn::Error::new(
+                    field.span(),
+                    "Unnamed fields are not supported",
+                ))
             }
+        };
+
+        let field_attrs = parse_attrs(&field.attrs, Position::Field)?;
+
+        if field_attrs.skip {
+            continue;
         }
+
+        let key = field_attrs.rename.unwrap_or_else(|| ident.to_string());
+        let value = into_value(&field.ty, quote!(self.#ident), 0)?;
+        setters.push(set_item(&key, value));
     }
+
+    let tag = attrs.tag.unwrap_or_else(|| "_type".to_string());
+    let type_name = attrs.rename.unwrap_or_else(|| name.to_string());
+
+    Ok(into_dict(&tag, &type_name, setters))
 }
 
-pub fn into_impl(ast: DeriveInput) -> TokenStream {
-    let struct_data = match ast.data {
-        Data::Struct(s) => s,
-        Data::Enum(e) => {
-            return syn::Error::new(e.enum_token.span, "Deriving enums is not supported")
-                .to_compile_error();
+/// Unit variants are converted to their name, or to a dict with just the type name if the enum
+/// has a tag. Newtype variants are converted as their value, unless their field is renamed: in
+/// that case they are converted to a dict with the type name, `_type` unless the enum has a tag,
+/// and the value in the renamed key, like struct variants.
+fn enum_body(name: &Ident, data: DataEnum, attrs: Attrs) -> syn::Result<TokenStream> {
+    let tag = attrs.tag.as_deref();
+    let mut arms = Vec::new();
+
+    for variant in data.variants {
+        let ident = &variant.ident;
+        let variant_attrs = parse_attrs(&variant.attrs, Position::Variant)?;
+        let type_name = variant_attrs.rename.unwrap_or_else(|| ident.to_string());
+
+        // A skipped variant is not extracted, but its values still have to be converted
+        if variant_attrs.skip {
+            return Err(syn::Error::new(
+                ident.span(),
+                "`skip` on variants is only supported by FromPyObject",
+            ));
         }
-        Data::Union(u) => {
-            return syn::Error::new(u.union_token.span, "Deriving unions is not supported")
-                .to_compile_error();
+
+        let arm = match variant.fields {
+            Fields::Unit => match tag {
+                Some(tag) => {
+                    let value = into_dict(tag, &type_name, Vec::new());
+                    quote!(#name::#ident => #value)
+                }
+                None => quote!(#name::#ident => Ok(IntoPy::<PyObject>::into_py(#type_name, py))),
+            },
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                let field = &fields.unnamed[0];
+                let field_attrs = parse_attrs(&field.attrs, Position::Field)?;
+                let value = into_value(&field.ty, quote!(value), 0)?;
+
+                match field_attrs.rename {
+                    Some(key) => {
+                        let value = into_dict(
+                            tag.unwrap_or("_type"),
+                            &type_name,
+                            vec![set_item(&key, value)],
+                        );
+                        quote!(#name::#ident(value) => #value)
+                    }
+                    None => quote!(#name::#ident(value) => #value),
+                }
+            }
+            Fields::Unnamed(fields) => {
+                return Err(syn::Error::new(
+                    fields.span(),
+                    "Tuple variants with more than one field are not supported",
+                ))
+            }
+            Fields::Named(fields) => {
+                let mut bindings = Vec::new();
+                let mut setters = Vec::new();
+
+                for field in fields.named {
+                    let field_ident = field.ident.as_ref().unwrap();
+                    let field_attrs = parse_attrs(&field.attrs, Position::Field)?;
+
+                    if field_attrs.skip {
+                        continue;
+                    }
+
+                    let binding = Ident::new(&format!("field_{}", field_ident), Span::call_site());
+                    let key = field_attrs
+                        .rename
+                        .unwrap_or_else(|| field_ident.to_string());
+
+                    let value = into_value(&field.ty, quote!(#binding), 0)?;
+                    setters.push(set_item(&key, value));
+                    bindings.push(quote!(#field_ident: #binding));
+                }
+
+                let value = into_dict(tag.unwrap_or("_type"), &type_name, setters);
+                quote!(#name::#ident { #(#bindings,)* .. } => #value)
+            }
+        };
+
+        arms.push(arm);
+    }
+
+    Ok(quote! {
+        match self {
+            #(#arms,)*
         }
-    };
+    })
+}
 
-    let field_setters = struct_data.fields.into_iter().map(map_fields);
+pub fn into_impl(ast: DeriveInput) -> TokenStream {
+    let DeriveInput {
+        ident: name,
+        attrs,
+        generics,
+        data,
+        ..
+    } = ast;
+
+    let body = parse_attrs(&attrs, Position::Container).and_then(|attrs| match data {
+        Data::Struct(s) => struct_body(&name, s, attrs),
+        Data::Enum(e) => enum_body(&name, e, attrs),
+        Data::Union(u) => Err(syn::Error::new(
+            u.union_token.span,
+            "Deriving unions is not supported",
+        )),
+    });
+
+    let body = match body {
+        Ok(body) => body,
+        Err(e) => return e.to_compile_error(),
+    };
 
-    let name = ast.ident;
-    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
-    let struct_name = name.to_string();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let type_name = name.to_string();
 
     quote! {
-        impl #impl_generics ::pyo3::IntoPy<::pyo3::PyObject> for #name #ty_generics #where_clause {
-            fn into_py(self, py: ::pyo3::Python) -> ::pyo3::PyObject {
-                use ::pyo3::{IntoPy, PyObject, PyErr, PyResult};
-                use ::pyo3::exceptions::{ValueError, TypeError};
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Convert into a Python object, returning the errors raised by Python
+            #[allow(dead_code)]
+            pub fn try_into_py(self, py: ::pyo3::Python) -> ::pyo3::PyResult<::pyo3::PyObject> {
+                use ::pyo3::{IntoPy, PyObject, PyResult, Python};
                 use ::pyo3::types::PyDict;
-                let dict = PyDict::new(py);
 
-                dict.set_item("_type", IntoPy::<PyObject>::into_py(#struct_name, py))
-                    .expect("Failed to set_item on dict");
-                #(#field_setters);*
+                // The derived types have their own `try_into_py()`, which takes precedence over
+                // the one of this trait, so that their errors are returned
+                #[allow(dead_code)]
+                trait TryIntoPy {
+                    fn try_into_py(self, py: Python) -> PyResult<PyObject>;
+                }
 
-                dict.into()
+                impl<T: IntoPy<PyObject>> TryIntoPy for T {
+                    fn try_into_py(self, py: Python) -> PyResult<PyObject> {
+                        Ok(self.into_py(py))
+                    }
+                }
+
+                #body
             }
         }
 
+        impl #impl_generics ::pyo3::IntoPy<::pyo3::PyObject> for #name #ty_generics #where_clause {
+            /// Panics if Python raises an error, like the conversions of PyO3's collections:
+            /// `try_into_py()` returns it instead
+            fn into_py(self, py: ::pyo3::Python) -> ::pyo3::PyObject {
+                match self.try_into_py(py) {
+                    Ok(obj) => obj,
+                    Err(err) => panic!(
+                        "failed to convert {} into a Python object: {:?}",
+                        #type_name,
+                        err
+                    ),
+                }
+            }
+        }
     }
 }
@@ -1,14 +1,23 @@
 extern crate proc_macro;
 extern crate syn;
 
+mod attrs;
+mod from;
 mod into;
 mod utils;
 
+use from::from_impl;
 use into::into_impl;
 use proc_macro::TokenStream;
 use syn::DeriveInput;
 
-#[proc_macro_derive(IntoPyObject)]
+#[proc_macro_derive(FromPyObject, attributes(dict))]
+pub fn derive_from_py_object(input: TokenStream) -> TokenStream {
+    let ast = syn::parse_macro_input!(input as DeriveInput);
+    TokenStream::from(from_impl(ast))
+}
+
+#[proc_macro_derive(IntoPyObject, attributes(dict))]
 pub fn derive_into_py_object(input: TokenStream) -> TokenStream {
     let ast = syn::parse_macro_input!(input as DeriveInput);
     TokenStream::from(into_impl(ast))
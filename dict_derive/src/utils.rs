@@ -1,53 +1,35 @@
 use syn::GenericArgument;
 use syn::PathArguments;
 
-#[derive(PartialEq, Eq, Debug)]
-pub enum MappedFieldType {
-    IsBox,
-    IsOption,
-    IsOptionBox,
-    IsAny,
-}
-
-pub fn which_field_type(ty: &syn::Type) -> MappedFieldType {
+/// The type argument of `ty` if it's the generic type `name`, like `Option<T>` or
+/// `std::vec::Vec<T>`. Anything but a single type argument, like `Option<A, B>`, is an error.
+pub fn generic_argument<'a>(ty: &'a syn::Type, name: &str) -> syn::Result<Option<&'a syn::Type>> {
     let path = match *ty {
         syn::Type::Path(ref p) if p.qself.is_none() => &p.path,
-        _ => return MappedFieldType::IsAny,
+        _ => return Ok(None),
     };
 
-    let mut segments_iter = path
-        .segments
-        .iter()
-        .map(|segment| segment.ident.to_string());
-
-    if segments_iter.clone().any(|x| x.as_str() == "Box") {
-        MappedFieldType::IsBox
-    } else if segments_iter.any(|x| x.as_str() == "Option") {
-        match path.segments[0].arguments {
-            PathArguments::AngleBracketed(ref angle_bracketed) => {
-                if angle_bracketed.args.len() != 1 {
-                    panic!("Didn't expected more than one AngleBracketed");
-                }
-
-                let argument = &angle_bracketed.args[0];
-
-                let field_type = match argument {
-                    GenericArgument::Type(ref t) => which_field_type(&t),
-                    _ => panic!("Unexpected GenericArgument {:?}", argument),
-                };
+    let segment = match path.segments.last() {
+        Some(segment) if segment.ident == name => segment,
+        _ => return Ok(None),
+    };
 
-                match field_type {
-                    MappedFieldType::IsBox => MappedFieldType::IsOptionBox,
-                    _ => MappedFieldType::IsOption,
-                }
+    match segment.arguments {
+        PathArguments::AngleBracketed(ref angle_bracketed) if angle_bracketed.args.len() == 1 => {
+            match angle_bracketed.args[0] {
+                GenericArgument::Type(ref t) => Ok(Some(t)),
+                ref other => Err(syn::Error::new_spanned(
+                    other,
+                    format!("expected a type argument for `{}`", name),
+                )),
             }
-            PathArguments::None => MappedFieldType::IsAny,
-            _ => panic!(
-                "Unexpected segment {:?}",
-                path.segments[0].arguments
-            ),
         }
-    } else {
-        MappedFieldType::IsAny
+        ref other => Err(syn::Error::new_spanned(
+            ty,
+            match other {
+                PathArguments::None => format!("missing the type argument of `{}`", name),
+                _ => format!("expected a single type argument for `{}`", name),
+            },
+        )),
     }
 }
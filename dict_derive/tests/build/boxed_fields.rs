@@ -0,0 +1,11 @@
+use dict_derive::{FromPyObject, IntoPyObject};
+
+#[derive(FromPyObject, IntoPyObject)]
+struct Tree {
+    value: u32,
+    children: Vec<Box<Tree>>,
+    parent: Option<Box<Tree>>,
+    leaves: Option<Vec<Box<Tree>>>,
+}
+
+fn main() {}
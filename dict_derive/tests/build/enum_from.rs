@@ -0,0 +1,20 @@
+use dict_derive::FromPyObject;
+
+#[derive(FromPyObject)]
+pub enum Nothing {
+    None,
+}
+
+#[derive(FromPyObject)]
+#[dict(tag = "kind")]
+pub enum Shape {
+    #[dict(rename = "Point")]
+    Empty,
+    Circle(#[dict(rename = "radius")] u32),
+    Rectangle {
+        width: u32,
+        height: u32,
+    },
+}
+
+fn main() {}
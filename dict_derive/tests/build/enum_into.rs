@@ -5,4 +5,16 @@ pub enum Nothing {
     None,
 }
 
+#[derive(IntoPyObject)]
+#[dict(tag = "kind")]
+pub enum Shape {
+    #[dict(rename = "Point")]
+    Empty,
+    Circle(#[dict(rename = "radius")] u32),
+    Rectangle {
+        width: u32,
+        height: u32,
+    },
+}
+
 fn main() {}
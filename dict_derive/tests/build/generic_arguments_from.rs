@@ -0,0 +1,12 @@
+use dict_derive::FromPyObject;
+
+mod pair {
+    pub type Vec<A, B> = (A, B);
+}
+
+#[derive(FromPyObject)]
+struct Point {
+    coords: pair::Vec<u32, u32>,
+}
+
+fn main() {}
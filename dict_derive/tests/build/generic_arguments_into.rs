@@ -0,0 +1,12 @@
+use dict_derive::IntoPyObject;
+
+mod pair {
+    pub type Option<A, B> = (A, B);
+}
+
+#[derive(IntoPyObject)]
+struct Point {
+    coords: pair::Option<u32, u32>,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use dict_derive::IntoPyObject;
+
+#[derive(IntoPyObject)]
+enum Color {
+    Red,
+    #[dict(skip)]
+    Green,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use dict_derive::FromPyObject;
+
+#[derive(FromPyObject)]
+enum Shape {
+    Point,
+    Segment(u32, u32),
+}
+
+fn main() {}
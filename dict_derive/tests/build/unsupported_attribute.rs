@@ -0,0 +1,9 @@
+use dict_derive::IntoPyObject;
+
+#[derive(IntoPyObject)]
+struct User {
+    #[dict(default)]
+    name: String,
+}
+
+fn main() {}
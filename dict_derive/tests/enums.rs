@@ -0,0 +1,172 @@
+extern crate pyo3;
+use dict_derive::{FromPyObject, IntoPyObject};
+use pyo3::exceptions::ValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+#[derive(FromPyObject, IntoPyObject, Debug, PartialEq)]
+enum Color {
+    Red,
+    #[dict(rename = "green")]
+    Green,
+}
+
+#[derive(FromPyObject, IntoPyObject, Debug, PartialEq)]
+#[dict(tag = "kind")]
+enum Shape {
+    #[dict(rename = "Point")]
+    Empty,
+    Circle(#[dict(rename = "radius")] u32),
+    Rectangle {
+        width: u32,
+        height: u32,
+    },
+}
+
+#[derive(FromPyObject, IntoPyObject, Debug, PartialEq)]
+struct Canvas {
+    #[dict(rename = "fill")]
+    color: Color,
+    shapes: Vec<Shape>,
+    #[dict(skip)]
+    dirty: bool,
+}
+
+#[derive(FromPyObject, IntoPyObject, Debug, PartialEq)]
+enum Item {
+    Canvas(Canvas),
+    Color(Color),
+}
+
+#[derive(FromPyObject, Debug, PartialEq)]
+enum Paint {
+    #[allow(dead_code)]
+    #[dict(skip)]
+    Hidden(Color),
+    Color(Color),
+}
+
+fn error_message(py: Python, err: PyErr) -> String {
+    err.instance(py).to_string()
+}
+
+#[test]
+fn test_unit_variants() -> PyResult<()> {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let obj: PyObject = Color::Green.into_py(py);
+    assert_eq!(obj.extract::<&str>(py)?, "green");
+    assert_eq!(obj.extract::<Color>(py)?, Color::Green);
+
+    let obj: PyObject = Shape::Empty.into_py(py);
+    let dict: &PyDict = obj.cast_as(py)?;
+    assert_eq!(dict.len(), 1);
+    assert_eq!(dict.get_item("kind").unwrap().extract::<&str>()?, "Point");
+    assert_eq!(obj.extract::<Shape>(py)?, Shape::Empty);
+
+    Ok(())
+}
+
+#[test]
+fn test_tagged_variants() -> PyResult<()> {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let obj: PyObject = Shape::Circle(3).into_py(py);
+    let dict: &PyDict = obj.cast_as(py)?;
+    assert_eq!(dict.get_item("kind").unwrap().extract::<&str>()?, "Circle");
+    assert_eq!(dict.get_item("radius").unwrap().extract::<u32>()?, 3);
+    assert_eq!(obj.extract::<Shape>(py)?, Shape::Circle(3));
+
+    let shape = Shape::Rectangle {
+        width: 2,
+        height: 4,
+    };
+    let obj: PyObject = shape.into_py(py);
+    let dict: &PyDict = obj.cast_as(py)?;
+    assert_eq!(dict.get_item("width").unwrap().extract::<u32>()?, 2);
+    assert_eq!(dict.get_item("height").unwrap().extract::<u32>()?, 4);
+    assert_eq!(
+        obj.extract::<Shape>(py)?,
+        Shape::Rectangle {
+            width: 2,
+            height: 4
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_renamed_and_skipped_fields() -> PyResult<()> {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let canvas = Canvas {
+        color: Color::Red,
+        shapes: vec![Shape::Empty, Shape::Circle(1)],
+        dirty: true,
+    };
+    let obj: PyObject = canvas.into_py(py);
+    let dict: &PyDict = obj.cast_as(py)?;
+    assert_eq!(dict.get_item("fill").unwrap().extract::<&str>()?, "Red");
+    assert!(dict.get_item("color").is_none());
+    assert!(dict.get_item("dirty").is_none());
+
+    let canvas: Canvas = obj.extract(py)?;
+    assert_eq!(canvas.shapes, vec![Shape::Empty, Shape::Circle(1)]);
+    assert!(!canvas.dirty);
+
+    Ok(())
+}
+
+#[test]
+fn test_newtype_variants() -> PyResult<()> {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let obj: PyObject = Item::Color(Color::Red).into_py(py);
+    assert_eq!(obj.extract::<&str>(py)?, "Red");
+    assert_eq!(obj.extract::<Item>(py)?, Item::Color(Color::Red));
+
+    let obj: PyObject = Color::Green.into_py(py);
+    assert_eq!(obj.extract::<Paint>(py)?, Paint::Color(Color::Green));
+
+    Ok(())
+}
+
+#[test]
+fn test_unexpected_variant() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let err = "Blue".into_py(py).extract::<Color>(py).unwrap_err();
+
+    assert!(err.is_instance::<ValueError>(py));
+    assert_eq!(error_message(py, err), "unexpected value 'Blue' for Color");
+
+    let dict = PyDict::new(py);
+    dict.set_item("kind", "Triangle").unwrap();
+
+    let err = dict.extract::<Shape>().unwrap_err();
+
+    assert!(err.is_instance::<ValueError>(py));
+    assert_eq!(error_message(py, err), "unexpected kind Triangle for Shape");
+
+    dict.del_item("kind").unwrap();
+    dict.set_item("_type", "Canvas").unwrap();
+    dict.set_item("fill", "Red").unwrap();
+
+    let err = dict.extract::<Item>().unwrap_err();
+
+    assert!(err.is_instance::<ValueError>(py));
+    assert_eq!(error_message(py, err), "`shapes`: missing key");
+
+    dict.set_item("_type", "Brush").unwrap();
+
+    let err = dict.extract::<Item>().unwrap_err();
+
+    assert!(err.is_instance::<ValueError>(py));
+    assert_eq!(error_message(py, err), "unexpected _type Brush for Item");
+}
@@ -0,0 +1,151 @@
+extern crate pyo3;
+use dict_derive::{FromPyObject, IntoPyObject};
+use pyo3::exceptions::{TypeError, ValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+#[derive(FromPyObject, IntoPyObject, Debug, PartialEq)]
+struct User {
+    name: Option<String>,
+    email: String,
+    age: u16,
+}
+
+#[derive(FromPyObject, IntoPyObject, Debug, PartialEq)]
+struct Group {
+    owner: Box<User>,
+    members: Vec<User>,
+}
+
+#[derive(Debug, PartialEq)]
+struct Code(String);
+
+impl<'source> FromPyObject<'source> for Code {
+    fn extract(obj: &'source PyAny) -> PyResult<Self> {
+        let code: String = obj.extract()?;
+
+        match code.starts_with('#') {
+            true => Ok(Code(code)),
+            false => Err(PyErr::new::<ValueError, _>(format!(
+                "`{}` is not a code",
+                code
+            ))),
+        }
+    }
+}
+
+#[derive(FromPyObject, Debug, PartialEq)]
+struct Label {
+    codes: Vec<Code>,
+}
+
+fn user(name: Option<&str>) -> User {
+    User {
+        name: name.map(|n| n.to_owned()),
+        email: "tester@tests.com".to_owned(),
+        age: 27,
+    }
+}
+
+fn error_message(py: Python, err: PyErr) -> String {
+    err.instance(py).to_string()
+}
+
+#[test]
+fn test_conversion() -> PyResult<()> {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let group = Group {
+        owner: Box::new(user(Some("Owner"))),
+        members: vec![user(None), user(Some("Test"))],
+    };
+    let obj: PyObject = group.into_py(py);
+    let group: Group = obj.extract(py)?;
+
+    assert_eq!(group.owner, Box::new(user(Some("Owner"))));
+    assert_eq!(group.members, vec![user(None), user(Some("Test"))]);
+
+    Ok(())
+}
+
+#[test]
+fn test_missing_optional_key() -> PyResult<()> {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let dict = PyDict::new(py);
+    dict.set_item("_type", "User")?;
+    dict.set_item("email", "tester@tests.com")?;
+    dict.set_item("age", 27)?;
+
+    let user: User = dict.extract()?;
+
+    assert_eq!(user.name, None);
+
+    Ok(())
+}
+
+#[test]
+fn test_invalid_type() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let obj: PyObject = user(None).into_py(py);
+    let dict: &PyDict = obj.cast_as(py).unwrap();
+    dict.set_item("_type", "Group").unwrap();
+
+    let err = dict.extract::<User>().unwrap_err();
+
+    assert!(err.is_instance::<ValueError>(py));
+    assert_eq!(error_message(py, err), "expected _type User, got 'Group'");
+
+    let err = py.None().extract::<User>(py).unwrap_err();
+
+    assert!(err.is_instance::<TypeError>(py));
+    assert_eq!(error_message(py, err), "expected a dict, got NoneType");
+}
+
+#[test]
+fn test_error_path() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let obj: PyObject = Group {
+        owner: Box::new(user(None)),
+        members: vec![user(None), user(None)],
+    }
+    .into_py(py);
+
+    let dict: &PyDict = obj.cast_as(py).unwrap();
+    let members: &PyAny = dict.get_item("members").unwrap();
+    let member: &PyDict = members.get_item(1).unwrap().cast_as().unwrap();
+    member.del_item("email").unwrap();
+
+    let err = dict.extract::<Group>().unwrap_err();
+
+    assert!(err.is_instance::<ValueError>(py));
+    assert_eq!(error_message(py, err), "`members[1].email`: missing key");
+
+    member.set_item("email", 42).unwrap();
+
+    let err = dict.extract::<Group>().unwrap_err();
+
+    assert!(err.is_instance::<TypeError>(py));
+    assert!(error_message(py, err).starts_with("`members[1].email`: "));
+}
+
+#[test]
+fn test_error_path_of_message_with_backticks() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let dict = PyDict::new(py);
+    dict.set_item("_type", "Label").unwrap();
+    dict.set_item("codes", vec!["#a", "b"]).unwrap();
+
+    let err = dict.extract::<Label>().unwrap_err();
+
+    assert!(err.is_instance::<ValueError>(py));
+    assert_eq!(error_message(py, err), "`codes[1]`: `b` is not a code");
+}
@@ -1,5 +1,6 @@
 extern crate pyo3;
 use dict_derive::IntoPyObject;
+use pyo3::exceptions::ValueError;
 use pyo3::prelude::*;
 use pyo3::types::PyDict;
 
@@ -10,6 +11,27 @@ struct User {
     age: u16,
 }
 
+/// A value whose conversion fails, like the one of a derived type when Python raises an error
+struct Broken;
+
+impl Broken {
+    fn try_into_py(self, _py: Python) -> PyResult<PyObject> {
+        Err(PyErr::new::<ValueError, _>("broken"))
+    }
+}
+
+#[derive(IntoPyObject)]
+struct Part {
+    name: String,
+    value: Broken,
+}
+
+#[derive(IntoPyObject)]
+struct Machine {
+    name: String,
+    parts: Vec<Option<Box<Part>>>,
+}
+
 #[test]
 fn test_conversion() -> PyResult<()> {
     let gil = Python::acquire_gil();
@@ -58,3 +80,43 @@ fn test_conversion() -> PyResult<()> {
     }
     Ok(())
 }
+
+#[test]
+fn test_try_conversion() -> PyResult<()> {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let user = User {
+        name: Some("Test".to_owned()),
+        email: "tester@tests.com".to_owned(),
+        age: 27,
+    };
+    let obj: PyObject = user.try_into_py(py)?;
+    let dict: &PyDict = obj.cast_as(py)?;
+
+    let name: Option<&str> = dict.get_item("name").unwrap().extract()?;
+    assert_eq!(name, Some("Test"));
+
+    Ok(())
+}
+
+#[test]
+fn test_nested_error() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let machine = Machine {
+        name: "Machine".to_owned(),
+        parts: vec![
+            None,
+            Some(Box::new(Part {
+                name: "Part".to_owned(),
+                value: Broken,
+            })),
+        ],
+    };
+    let err = machine.try_into_py(py).unwrap_err();
+
+    assert!(err.is_instance::<ValueError>(py));
+    assert_eq!(err.instance(py).to_string(), "broken");
+}
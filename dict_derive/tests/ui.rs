@@ -2,6 +2,14 @@
 fn tests() {
     let t = trybuild::TestCases::new();
     t.pass("tests/build/parse_into.rs");
-    t.compile_fail("tests/build/enum_into.rs");
+    t.pass("tests/build/enum_into.rs");
+    t.pass("tests/build/boxed_fields.rs");
     t.compile_fail("tests/build/unsupported_into.rs");
+    t.compile_fail("tests/build/unsupported_attribute.rs");
+    t.compile_fail("tests/build/generic_arguments_into.rs");
+    t.compile_fail("tests/build/skipped_variant_into.rs");
+    t.pass("tests/build/parse_from.rs");
+    t.pass("tests/build/enum_from.rs");
+    t.compile_fail("tests/build/generic_arguments_from.rs");
+    t.compile_fail("tests/build/tuple_variant_from.rs");
 }
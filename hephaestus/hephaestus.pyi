@@ -0,0 +1,549 @@
+# Generated by `hephaestus::tree::stub()`, do not edit.
+
+from typing import Any, Dict, List, Optional, Tuple, Union
+
+def parse(sql_str: str) -> Dict[str, Any]: ...
+def parse_lenient(sql_str: str) -> Dict[str, Any]: ...
+def parse_many(sql_strs: List[str], threads: Optional[int] = None) -> List[Dict[str, Any]]: ...
+def parse_tree(sql_str: str) -> Statement: ...
+def to_sql(statement: Dict[str, Any]) -> str: ...
+def format_sql(sql_str: str, **options: Any) -> str: ...
+def fingerprint(sql_str: str) -> Tuple[str, str]: ...
+def diff(sql_a: str, sql_b: str) -> List[Dict[str, Any]]: ...
+def tables(sql_str: str) -> List[str]: ...
+def ctes(sql_str: str) -> List[str]: ...
+def columns(sql_str: str) -> List[str]: ...
+def functions(sql_str: str) -> List[str]: ...
+def literals(sql_str: str) -> List[str]: ...
+def lint(sql_str: str, disable: Optional[List[str]] = None) -> List[Dict[str, Any]]: ...
+def parameters(sql_str: str) -> List[Dict[str, Any]]: ...
+def substitute(sql_str: str, *values: Any, **named: Any) -> str: ...
+def simplify(sql_str: str) -> str: ...
+def predicates(sql_str: str) -> List[Dict[str, Any]]: ...
+def join_graphs(sql_str: str) -> List[Dict[str, Any]]: ...
+def join_graphs_dot(sql_str: str) -> str: ...
+def complexity(sql_str: str) -> Dict[str, Any]: ...
+def classify(sql_str: str) -> Dict[str, Any]: ...
+def inline_cte(sql_str: str, name: str) -> str: ...
+def extract_cte(sql_str: str, name: str) -> str: ...
+
+Statement = Union[
+    SelectStatement,
+    InsertStatement,
+    DeleteStatement,
+    UpdateStatement,
+    AttachStatement,
+    DescribeStatement,
+    ErrorNode,
+]
+
+SelectMode = Union[
+    SelectAll,
+    SelectDistinct,
+]
+
+SetExpression = Union[
+    ValuesSetExpression,
+    QuerySetExpression,
+    OpSetExpression,
+]
+
+TableExpression = Union[
+    NamedTableExpression,
+    SelectTableExpression,
+    JoinTableExpression,
+    ErrorNode,
+]
+
+JoinOperator = Union[
+    RegularJoin,
+    NaturalJoin,
+    CrossJoin,
+]
+
+JoinConstraint = Union[
+    Expression,
+    ColumnsJoinConstraint,
+]
+
+ResultColumns = Union[
+    AllResultColumn,
+    ListResultColumn,
+]
+
+ResultColumn = Union[
+    str,
+    ExprResultColumn,
+]
+
+SetSpecification = Union[
+    SelectStatement,
+    ListSetSpecification,
+    List[str],
+]
+
+Literal = Union[
+    StringLiteral,
+    NumericLiteral,
+    NullLiteral,
+    BooleanLiteral,
+    CurrentTimeLiteral,
+    CurrentDateLiteral,
+    CurrentTimestampLiteral,
+    PlaceholderLiteral,
+    TimeLiteral,
+    DateLiteral,
+    TimestampLiteral,
+]
+
+Parameter = Union[
+    PositionalParameter,
+    NamedParameter,
+    VariableParameter,
+]
+
+Expression = Union[
+    Literal,
+    QualifiedIdentifierExpression,
+    MakeTupleExpression,
+    SelectStatement,
+    UnaryExpression,
+    BinaryExpression,
+    ComparisonExpression,
+    InExpression,
+    BetweenExpression,
+    CaseExpression,
+    CoalesceExpression,
+    ReplaceExpression,
+    SubstringExpression,
+    ToDateExpression,
+    PowerExpression,
+    ConcatExpression,
+    SumExpression,
+    MaxExpression,
+    MinExpression,
+    CastExpression,
+    RightExpression,
+    CountExpression,
+    UnknownExpression,
+    Parameter,
+    ErrorNode,
+]
+
+DataType = Union[
+    BooleanDataType,
+    CharDataType,
+    DateDataType,
+    DecimalDataType,
+    DoublePrecisionDataType,
+    TimestampDataType,
+    LocalTimestampDataType,
+    VarcharDataType,
+]
+
+Node = Union[
+    SelectStatement,
+    InsertStatement,
+    DeleteStatement,
+    UpdateStatement,
+    AttachStatement,
+    DescribeStatement,
+    CommonTableExpression,
+    Assignment,
+    SelectAll,
+    SelectDistinct,
+    ValuesSetExpression,
+    QuerySetExpression,
+    OpSetExpression,
+    NamedTableExpression,
+    SelectTableExpression,
+    JoinTableExpression,
+    RegularJoin,
+    NaturalJoin,
+    CrossJoin,
+    ColumnsJoinConstraint,
+    AllResultColumn,
+    ListResultColumn,
+    ExprResultColumn,
+    GroupBy,
+    QualifiedIdentifierExpression,
+    MakeTupleExpression,
+    UnaryExpression,
+    BinaryExpression,
+    ComparisonExpression,
+    InExpression,
+    ListSetSpecification,
+    BetweenExpression,
+    CaseExpression,
+    WhenClause,
+    CoalesceExpression,
+    ReplaceExpression,
+    SubstringExpression,
+    ToDateExpression,
+    PowerExpression,
+    ConcatExpression,
+    MaxExpression,
+    MinExpression,
+    SumExpression,
+    CastExpression,
+    RightExpression,
+    CountExpression,
+    UnknownExpression,
+    ErrorNode,
+    StringLiteral,
+    NumericLiteral,
+    NullLiteral,
+    BooleanLiteral,
+    CurrentTimeLiteral,
+    CurrentDateLiteral,
+    CurrentTimestampLiteral,
+    PlaceholderLiteral,
+    TimeLiteral,
+    DateLiteral,
+    TimestampLiteral,
+    PositionalParameter,
+    NamedParameter,
+    VariableParameter,
+    Ordering,
+    Limit,
+    BooleanDataType,
+    CharDataType,
+    DateDataType,
+    DecimalDataType,
+    DoublePrecisionDataType,
+    TimestampDataType,
+    LocalTimestampDataType,
+    VarcharDataType,
+]
+
+class SelectStatement:
+    common: List[CommonTableExpression]
+    recursive: bool
+    expr: SetExpression
+    order_by: List[Ordering]
+    limit: Optional[Limit]
+    def children(self) -> List[Node]: ...
+
+class InsertStatement:
+    table_name: List[str]
+    columns: Optional[List[str]]
+    source: SetExpression
+    def children(self) -> List[Node]: ...
+
+class DeleteStatement:
+    table_name: List[str]
+    where_expr: Optional[Expression]
+    def children(self) -> List[Node]: ...
+
+class UpdateStatement:
+    table_name: List[str]
+    assignments: List[Assignment]
+    where_expr: Optional[Expression]
+    def children(self) -> List[Node]: ...
+
+class AttachStatement:
+    qualified_name: List[str]
+    path: str
+    def children(self) -> List[Node]: ...
+
+class DescribeStatement:
+    qualified_name: List[str]
+    def children(self) -> List[Node]: ...
+
+class CommonTableExpression:
+    identifier: str
+    column_names: Optional[List[str]]
+    query: SelectStatement
+    def children(self) -> List[Node]: ...
+
+class Assignment:
+    columns: List[str]
+    expr: Expression
+    def children(self) -> List[Node]: ...
+
+class SelectAll:
+    def children(self) -> List[Node]: ...
+
+class SelectDistinct:
+    def children(self) -> List[Node]: ...
+
+class ValuesSetExpression:
+    values: List[List[Expression]]
+    def children(self) -> List[Node]: ...
+
+class QuerySetExpression:
+    mode: SelectMode
+    columns: ResultColumns
+    from_: List[TableExpression]
+    where_expr: Optional[Expression]
+    group_by: Optional[GroupBy]
+    def children(self) -> List[Node]: ...
+
+class OpSetExpression:
+    op: str
+    left: SetExpression
+    right: SetExpression
+    def children(self) -> List[Node]: ...
+
+class NamedTableExpression:
+    name: List[str]
+    alias: Optional[str]
+    def children(self) -> List[Node]: ...
+
+class SelectTableExpression:
+    select: SelectStatement
+    alias: Optional[str]
+    def children(self) -> List[Node]: ...
+
+class JoinTableExpression:
+    left: TableExpression
+    right: TableExpression
+    op: JoinOperator
+    constraint: JoinConstraint
+    def children(self) -> List[Node]: ...
+
+class RegularJoin:
+    join: str
+    def children(self) -> List[Node]: ...
+
+class NaturalJoin:
+    join: str
+    def children(self) -> List[Node]: ...
+
+class CrossJoin:
+    def children(self) -> List[Node]: ...
+
+class ColumnsJoinConstraint:
+    columns: List[str]
+    def children(self) -> List[Node]: ...
+
+class AllResultColumn:
+    def children(self) -> List[Node]: ...
+
+class ListResultColumn:
+    values: List[ResultColumn]
+    def children(self) -> List[Node]: ...
+
+class ExprResultColumn:
+    expr: Expression
+    rename: Optional[str]
+    def children(self) -> List[Node]: ...
+
+class GroupBy:
+    groupings: List[Expression]
+    having: Optional[Expression]
+    def children(self) -> List[Node]: ...
+
+class QualifiedIdentifierExpression:
+    identifiers: List[str]
+    def children(self) -> List[Node]: ...
+
+class MakeTupleExpression:
+    exprs: List[Expression]
+    def children(self) -> List[Node]: ...
+
+class UnaryExpression:
+    op: str
+    expr: Expression
+    def children(self) -> List[Node]: ...
+
+class BinaryExpression:
+    op: str
+    left: Expression
+    right: Expression
+    def children(self) -> List[Node]: ...
+
+class ComparisonExpression:
+    op: str
+    left: Expression
+    right: Expression
+    def children(self) -> List[Node]: ...
+
+class InExpression:
+    expr: Expression
+    set: SetSpecification
+    def children(self) -> List[Node]: ...
+
+class ListSetSpecification:
+    exprs: List[Expression]
+    def children(self) -> List[Node]: ...
+
+class BetweenExpression:
+    expr: Expression
+    lower: Expression
+    upper: Expression
+    def children(self) -> List[Node]: ...
+
+class CaseExpression:
+    expr: Optional[Expression]
+    when_part: List[WhenClause]
+    else_part: Optional[Expression]
+    def children(self) -> List[Node]: ...
+
+class WhenClause:
+    guard: Expression
+    body: Expression
+    def children(self) -> List[Node]: ...
+
+class CoalesceExpression:
+    exprs: List[Expression]
+    def children(self) -> List[Node]: ...
+
+class ReplaceExpression:
+    string: Expression
+    search_string: Expression
+    replace_string: Optional[Expression]
+    def children(self) -> List[Node]: ...
+
+class SubstringExpression:
+    string: Expression
+    position: Expression
+    length: Optional[Expression]
+    def children(self) -> List[Node]: ...
+
+class ToDateExpression:
+    string: Expression
+    format: Optional[Expression]
+    def children(self) -> List[Node]: ...
+
+class PowerExpression:
+    base: Expression
+    exponent: Expression
+    def children(self) -> List[Node]: ...
+
+class ConcatExpression:
+    exprs: List[Expression]
+    def children(self) -> List[Node]: ...
+
+class MaxExpression:
+    mode: SelectMode
+    expr: Expression
+    def children(self) -> List[Node]: ...
+
+class MinExpression:
+    mode: SelectMode
+    expr: Expression
+    def children(self) -> List[Node]: ...
+
+class SumExpression:
+    mode: SelectMode
+    expr: Expression
+    def children(self) -> List[Node]: ...
+
+class CastExpression:
+    expr: Expression
+    data_type: DataType
+    def children(self) -> List[Node]: ...
+
+class RightExpression:
+    string: Expression
+    length: Expression
+    def children(self) -> List[Node]: ...
+
+class CountExpression:
+    columns: ResultColumns
+    mode: SelectMode
+    def children(self) -> List[Node]: ...
+
+class UnknownExpression:
+    name: List[str]
+    exprs: List[Expression]
+    def children(self) -> List[Node]: ...
+
+class ErrorNode:
+    message: str
+    token: Optional[str]
+    location: int
+    text: str
+    span: Tuple[int, int]
+    def children(self) -> List[Node]: ...
+
+class StringLiteral:
+    str: str
+    def children(self) -> List[Node]: ...
+
+class NumericLiteral:
+    num: str
+    def children(self) -> List[Node]: ...
+
+class NullLiteral:
+    def children(self) -> List[Node]: ...
+
+class BooleanLiteral:
+    value: bool
+    def children(self) -> List[Node]: ...
+
+class CurrentTimeLiteral:
+    def children(self) -> List[Node]: ...
+
+class CurrentDateLiteral:
+    def children(self) -> List[Node]: ...
+
+class CurrentTimestampLiteral:
+    def children(self) -> List[Node]: ...
+
+class PlaceholderLiteral:
+    def children(self) -> List[Node]: ...
+
+class TimeLiteral:
+    time: str
+    def children(self) -> List[Node]: ...
+
+class DateLiteral:
+    date: str
+    def children(self) -> List[Node]: ...
+
+class TimestampLiteral:
+    ts: str
+    def children(self) -> List[Node]: ...
+
+class PositionalParameter:
+    index: int
+    def children(self) -> List[Node]: ...
+
+class NamedParameter:
+    name: str
+    def children(self) -> List[Node]: ...
+
+class VariableParameter:
+    name: str
+    def children(self) -> List[Node]: ...
+
+class Ordering:
+    expr: Expression
+    collation: Optional[str]
+    direction: str
+    def children(self) -> List[Node]: ...
+
+class Limit:
+    number_rows: Expression
+    offset_value: Optional[Expression]
+    def children(self) -> List[Node]: ...
+
+class BooleanDataType:
+    def children(self) -> List[Node]: ...
+
+class CharDataType:
+    s: Literal
+    def children(self) -> List[Node]: ...
+
+class DateDataType:
+    def children(self) -> List[Node]: ...
+
+class DecimalDataType:
+    p: Literal
+    s: Literal
+    def children(self) -> List[Node]: ...
+
+class DoublePrecisionDataType:
+    def children(self) -> List[Node]: ...
+
+class TimestampDataType:
+    def children(self) -> List[Node]: ...
+
+class LocalTimestampDataType:
+    def children(self) -> List[Node]: ...
+
+class VarcharDataType:
+    s: Literal
+    def children(self) -> List[Node]: ...
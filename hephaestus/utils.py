@@ -1,5 +1,8 @@
+import warnings
 from typing import Dict, Iterator
 
+from hephaestus import ctes, tables, to_sql
+
 
 def iter_node_attributes(root_node: object) -> Iterator[object]:
     nodes = [root_node]
@@ -15,13 +18,20 @@ def iter_node_attributes(root_node: object) -> Iterator[object]:
 
 
 def iter_tables(sql_ast: Dict) -> Iterator[str]:
-    for node in iter_node_attributes(sql_ast):
-        if node["_type"] == "NamedTableExpression":
-            for name in node["name"]:
-                yield name
+    warnings.warn(
+        "iter_tables() is deprecated, use hephaestus.tables() instead",
+        DeprecationWarning,
+        stacklevel=2,
+    )
+
+    yield from tables(to_sql(sql_ast))
 
 
 def iter_ctes(sql_ast: Dict) -> Iterator[str]:
-    for node in iter_node_attributes(sql_ast):
-        if node["_type"] == "CommonTableExpression":
-            yield node["identifier"]
+    warnings.warn(
+        "iter_ctes() is deprecated, use hephaestus.ctes() instead",
+        DeprecationWarning,
+        stacklevel=2,
+    )
+
+    yield from ctes(to_sql(sql_ast))
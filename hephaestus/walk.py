@@ -1,12 +1,15 @@
+from typing import Dict, Union
+
 from hephaestus import parse
 
 
 class Visitor:
-    def __init__(self, sql: str):
+    def __init__(self, sql: Union[str, Dict]):
         self.sql = sql
 
     def walk(self):
-        root_node = parse(self.sql)
+        # Reuse an already parsed AST instead of parsing the SQL again
+        root_node = self.sql if isinstance(self.sql, dict) else parse(self.sql)
         nodes = [(None, root_node)]
 
         for (attr, value) in nodes:
@@ -9,6 +9,7 @@ setup(
     version="0.1.3",
     rust_extensions=[RustExtension("hephaestus.hephaestus", binding=Binding.PyO3)],
     packages=["hephaestus"],
+    package_data={"hephaestus": ["py.typed", "*.pyi"]},
     # rust extensions are not zip safe, just like C-extensions.
     zip_safe=False,
     long_description=Path("README.md").read_text(),
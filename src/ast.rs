@@ -21,7 +21,7 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-use dict_derive::IntoPyObject;
+use dict_derive::{FromPyObject, IntoPyObject};
 
 use super::symbols;
 
@@ -29,12 +29,13 @@ use super::symbols;
 pub type Error = super::error::Error;
 
 /// SQL statements that are supported by this implementation
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub enum SqlStatement {
     /// A regular (DML) statement
     Statement(Statement),
 
-    /// Query plan analysis
+    /// Query plan analysis; its dictionary is the one of the statement, which is extracted back
+    /// as a `Statement`
     ExplainQueryPlan(Statement),
 
     /// Attach an external file as source for query processing
@@ -42,9 +43,12 @@ pub enum SqlStatement {
 
     /// Describe a particular schema object
     Describe(DescribeStatement),
+
+    /// a statement which cannot be parsed
+    Error(ErrorNode),
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub enum Statement {
     Select(SelectStatement),
     Insert(InsertStatement),
@@ -53,7 +57,7 @@ pub enum Statement {
 }
 
 /// Representation of an insert statement
-#[derive(IntoPyObject, Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub struct InsertStatement {
     /// the name of the table into which we want to insert new values
     pub table_name: Vec<symbols::Name>,
@@ -68,7 +72,7 @@ pub struct InsertStatement {
 
 /// Representation of a common table expression, which provides a short-hand notation for
 /// queries within the context of a single statement.
-#[derive(IntoPyObject, Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub struct CommonTableExpression {
     /// the name under which we will refer to these query results in the remainder of the query
     /// that is using this common table expression
@@ -82,11 +86,15 @@ pub struct CommonTableExpression {
 }
 
 /// Representation of a select statement.
-#[derive(IntoPyObject, Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub struct SelectStatement {
     /// 0 or more comon table expressions, that can be referenced by the main query expression
     pub common: Vec<CommonTableExpression>,
 
+    /// whether the common table expressions are defined by `WITH RECURSIVE`, so that they can
+    /// reference themselves
+    pub recursive: bool,
+
     /// the query expression
     pub expr: Box<SetExpression>,
 
@@ -99,7 +107,7 @@ pub struct SelectStatement {
 }
 
 /// Represenatation of a delete statement
-#[derive(IntoPyObject, Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub struct DeleteStatement {
     /// the name of the table from which rows should be deleted
     pub table_name: Vec<symbols::Name>,
@@ -109,7 +117,7 @@ pub struct DeleteStatement {
 }
 
 /// Representation of an update statement
-#[derive(IntoPyObject, Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub struct UpdateStatement {
     /// the qualified table name
     pub table_name: Vec<symbols::Name>,
@@ -122,7 +130,7 @@ pub struct UpdateStatement {
 }
 
 /// Rerpresentation of an attach statement
-#[derive(IntoPyObject, Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub struct AttachStatement {
     /// the table name within the previous (or default) schema
     pub qualified_name: Vec<symbols::Name>,
@@ -165,7 +173,7 @@ impl AttachStatement {
 }
 
 /// Representation of a describe statememnt
-#[derive(IntoPyObject, Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub struct DescribeStatement {
     /// the name of the object to describe
     pub qualified_name: Vec<symbols::Name>,
@@ -199,24 +207,27 @@ impl DescribeStatement {
 
 /// Assignment used as part of an Update statement. One or more columns are updated with
 /// the provided expression value.
-#[derive(IntoPyObject, Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub struct Assignment {
     pub columns: Vec<symbols::Name>,
     pub expr: Expression,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
+#[dict(tag = "_type")]
 pub enum SelectMode {
+    #[dict(rename = "SelectAll")]
     All,
+    #[dict(rename = "SelectDistinct")]
     Distinct,
 }
 
-#[derive(IntoPyObject, Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub struct ValuesSetExpression {
     pub values: Vec<Vec<Expression>>,
 }
 
-#[derive(IntoPyObject, Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub struct QuerySetExpression {
     pub mode: SelectMode,
     pub columns: ResultColumns,
@@ -225,7 +236,7 @@ pub struct QuerySetExpression {
     pub group_by: Option<GroupBy>,
 }
 
-#[derive(IntoPyObject, Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub struct OpSetExpression {
     pub op: SetOperator,
     pub left: Box<SetExpression>,
@@ -233,7 +244,7 @@ pub struct OpSetExpression {
 }
 
 /// Representation of a SetExpression, a collection of rows, each having one or more columns.
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub enum SetExpression {
     /// Literal row values
     Values(ValuesSetExpression),
@@ -245,7 +256,7 @@ pub enum SetExpression {
     Op(OpSetExpression),
 }
 
-#[derive(IntoPyObject, Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub struct NamedTableExpression {
     /// the qualified table name
     pub name: Vec<symbols::Name>,
@@ -254,7 +265,7 @@ pub struct NamedTableExpression {
     pub alias: Option<symbols::Name>,
 }
 
-#[derive(IntoPyObject, Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub struct SelectTableExpression {
     /// a nested select statement
     pub select: SelectStatement,
@@ -263,7 +274,7 @@ pub struct SelectTableExpression {
     pub alias: Option<symbols::Name>,
 }
 
-#[derive(IntoPyObject, Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub struct JoinTableExpression {
     /// the left table expression to join
     pub left: Box<TableExpression>,
@@ -279,7 +290,7 @@ pub struct JoinTableExpression {
 }
 
 /// Representations of base queries
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub enum TableExpression {
     /// The row set of a given table; possibly providing an alias
     Named(NamedTableExpression),
@@ -289,15 +300,18 @@ pub enum TableExpression {
 
     /// The Join of two `TableExpression` values
     Join(JoinTableExpression),
+
+    /// a table expression which cannot be parsed
+    Error(ErrorNode),
 }
 
-#[derive(IntoPyObject, Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub struct ColumnsJoinConstraint {
     pub columns: Vec<symbols::Name>,
 }
 
 /// Representation of a join constraint
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub enum JoinConstraint {
     /// an expression describing the contraint
     Expr(Expression),
@@ -307,45 +321,56 @@ pub enum JoinConstraint {
 }
 
 /// Join operators
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
+#[dict(tag = "_type")]
 pub enum JoinOperator {
     /// Regular join
-    Join(JoinType),
+    #[dict(rename = "RegularJoin")]
+    Join(#[dict(rename = "join")] JoinType),
 
     /// Natural join
-    Natural(JoinType),
+    #[dict(rename = "NaturalJoin")]
+    Natural(#[dict(rename = "join")] JoinType),
 
     /// Cross join
+    #[dict(rename = "CrossJoin")]
     Cross,
 }
 
 /// Join types
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub enum JoinType {
     /// Inner join
+    #[dict(rename = "inner")]
     Inner,
 
     /// Left (outer) join
+    #[dict(rename = "left")]
     Left,
 
     /// Right (outer) join
+    #[dict(rename = "right")]
     Right,
 
     /// Full (outer) join
+    #[dict(rename = "full")]
     Full,
 }
 
 /// Representation of result columns in a select statement
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
+#[dict(tag = "_type")]
 pub enum ResultColumns {
     /// All columns ('*')
+    #[dict(rename = "AllResultColumn")]
     All,
 
     /// Result column specification
-    List(Vec<ResultColumn>),
+    #[dict(rename = "ListResultColumn")]
+    List(#[dict(rename = "values")] Vec<ResultColumn>),
 }
 
-#[derive(IntoPyObject, Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub struct ExprResultColumn {
     /// the expression to evaluate
     pub expr: Expression,
@@ -355,7 +380,7 @@ pub struct ExprResultColumn {
 }
 
 /// Representation of a single result column specification
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub enum ResultColumn {
     /// All columns from a given named schema object
     AllFrom(symbols::Name),
@@ -365,7 +390,7 @@ pub enum ResultColumn {
 }
 
 /// Representation of grouping of result sets
-#[derive(IntoPyObject, Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub struct GroupBy {
     /// One or more expressions that define the buckets for grouping
     pub groupings: Vec<Expression>,
@@ -375,214 +400,235 @@ pub struct GroupBy {
 }
 
 /// Possible binary operators on row sets
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub enum SetOperator {
     /// Intersection operation
+    #[dict(rename = "intersect")]
     Intersect,
 
     /// Set minus operation
+    #[dict(rename = "except")]
     Except,
 
     /// Union of distinct values
+    #[dict(rename = "union")]
     Union,
 
     /// Union including possible duplicates occuring on both sides
+    #[dict(rename = "union_all")]
     UnionAll,
 }
 
 /// Possible unary operators for simple expressions
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub enum UnaryOperator {
     /// Numeric negation
+    #[dict(rename = "negate")]
     Negate,
 
     /// Logical inversion
+    #[dict(rename = "not")]
     Not,
 
     /// Null check
+    #[dict(rename = "isnull")]
     IsNull,
 }
 
 /// Binary operators for simple expressions
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub enum BinaryOperator {
     /// Numeric multiplication
+    #[dict(rename = "multiply")]
     Multiply,
 
     /// Numeric division
+    #[dict(rename = "divide")]
     Divide,
 
     /// Numeric addition
+    #[dict(rename = "add")]
     Add,
 
     /// Numeric subtraction
+    #[dict(rename = "subtract")]
     Subtract,
 
     /// Concatenation of character sequences
+    #[dict(rename = "concat")]
     Concat,
 
     /// Logical and
+    #[dict(rename = "and")]
     And,
 
     /// Logical or
+    #[dict(rename = "or")]
     Or,
 }
 
 /// Comparison operators
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub enum ComparisonOperator {
     /// Equality
+    #[dict(rename = "equal")]
     Equal,
 
     /// Inquality
+    #[dict(rename = "not_equal")]
     NotEqual,
 
     /// Less than
+    #[dict(rename = "less_than")]
     LessThan,
 
     /// Less than or equal to
+    #[dict(rename = "less_equal")]
     LessEqual,
 
     /// Greater than
+    #[dict(rename = "greater_than")]
     GreaterThan,
 
     /// Greater than or equal to
+    #[dict(rename = "greater_equal")]
     GreaterEqual,
 
     /// Like operator (string matching)
+    #[dict(rename = "like")]
     Like,
 }
 
-#[derive(IntoPyObject, Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub struct QualifiedIdentifierExpression {
     pub identifiers: Vec<symbols::Name>,
 }
 
-#[derive(IntoPyObject, Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub struct MakeTupleExpression {
     pub exprs: Vec<Expression>,
 }
 
-#[derive(IntoPyObject, Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub struct UnaryExpression {
     pub op: UnaryOperator,
     pub expr: Box<Expression>,
 }
 
-#[derive(IntoPyObject, Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub struct BinaryExpression {
     pub op: BinaryOperator,
     pub left: Box<Expression>,
     pub right: Box<Expression>,
 }
 
-#[derive(IntoPyObject, Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub struct ComparisonExpression {
     pub op: ComparisonOperator,
     pub left: Box<Expression>,
     pub right: Box<Expression>,
 }
 
-#[derive(IntoPyObject, Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub struct InExpression {
     pub expr: Box<Expression>,
     pub set: SetSpecification,
 }
 
-#[derive(IntoPyObject, Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub struct BetweenExpression {
     pub expr: Box<Expression>,
     pub lower: Box<Expression>,
     pub upper: Box<Expression>,
 }
 
-#[derive(IntoPyObject, Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub struct CaseExpression {
     pub expr: Option<Box<Expression>>,
     pub when_part: Vec<WhenClause>,
     pub else_part: Option<Box<Expression>>,
 }
 
-#[derive(IntoPyObject, Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub struct CoalesceExpression {
     pub exprs: Vec<Expression>,
 }
 
-#[derive(IntoPyObject, Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub struct ReplaceExpression {
     pub string: Box<Expression>,
     pub search_string: Box<Expression>,
     pub replace_string: Option<Box<Expression>>,
 }
 
-#[derive(IntoPyObject, Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub struct SubstringExpression {
     pub string: Box<Expression>,
     pub position: Box<Expression>,
     pub length: Option<Box<Expression>>,
 }
 
-#[derive(IntoPyObject, Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub struct ToDateExpression {
     pub string: Box<Expression>,
     pub format: Option<Box<Expression>>,
 }
 
-#[derive(IntoPyObject, Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub struct PowerExpression {
     pub base: Box<Expression>,
     pub exponent: Box<Expression>,
 }
 
-#[derive(IntoPyObject, Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub struct ConcatExpression {
     pub exprs: Vec<Expression>,
 }
 
-#[derive(IntoPyObject, Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub struct MaxExpression {
     pub mode: SelectMode,
     pub expr: Box<Expression>,
 }
 
-#[derive(IntoPyObject, Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub struct MinExpression {
     pub mode: SelectMode,
     pub expr: Box<Expression>,
 }
 
-#[derive(IntoPyObject, Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub struct SumExpression {
     pub mode: SelectMode,
     pub expr: Box<Expression>,
 }
 
-#[derive(IntoPyObject, Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub struct CastExpression {
     pub expr: Box<Expression>,
     pub data_type: DataType,
 }
 
-#[derive(IntoPyObject, Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub struct RightExpression {
     pub string: Box<Expression>,
     pub length: Box<Expression>,
 }
 
-#[derive(IntoPyObject, Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub struct CountExpression {
     pub columns: ResultColumns,
     pub mode: SelectMode,
 }
 
-#[derive(IntoPyObject, Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub struct UnknownExpression {
     pub name: Vec<symbols::Name>,
     pub exprs: Vec<Expression>,
 }
 
 /// Scalar expressions
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub enum Expression {
     /// a literal value
     Literal(Literal),
@@ -650,23 +696,31 @@ pub enum Expression {
 
     /// Unknown Expression
     Unknown(UnknownExpression),
+
+    /// a bind parameter
+    Parameter(Parameter),
+
+    /// an expression which cannot be parsed
+    Error(ErrorNode),
 }
 
 /// Specification of the containing set within a set membership expression
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
+#[dict(tag = "_type")]
 pub enum SetSpecification {
     /// Rows returned by a select statement
     Select(SelectStatement),
 
     /// List of expressions
-    List(Vec<Expression>),
+    #[dict(rename = "ListSetSpecification")]
+    List(#[dict(rename = "exprs")] Vec<Expression>),
 
     /// a qualified name specifying a collection
     Name(Vec<symbols::Name>),
 }
 
 /// Representation of a when clause used inside a case expression
-#[derive(IntoPyObject, Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub struct WhenClause {
     /// guard statement determining when this claause applies
     pub guard: Expression,
@@ -676,38 +730,92 @@ pub struct WhenClause {
 }
 
 /// Literal values
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
+#[dict(tag = "_type")]
 pub enum Literal {
     /// String literal
-    String(String),
+    #[dict(rename = "StringLiteral")]
+    String(#[dict(rename = "str")] String),
 
     /// Numeric literal
-    Numeric(String),
+    #[dict(rename = "NumericLiteral")]
+    Numeric(#[dict(rename = "num")] String),
 
     /// the NULL value
+    #[dict(rename = "NullLiteral")]
     Null,
 
+    /// TRUE or FALSE
+    #[dict(rename = "BooleanLiteral")]
+    Boolean(#[dict(rename = "value")] bool),
+
     /// the current time
+    #[dict(rename = "CurrentTimeLiteral")]
     CurrentTime,
 
     /// the current date
+    #[dict(rename = "CurrentDateLiteral")]
     CurrentDate,
 
     /// the current timestamp
+    #[dict(rename = "CurrentTimestampLiteral")]
     CurrentTimestamp,
 
     /// DATE literal
-    Date(String),
+    #[dict(rename = "DateLiteral")]
+    Date(#[dict(rename = "date")] String),
 
     /// TIME literal
-    Time(String),
+    #[dict(rename = "TimeLiteral")]
+    Time(#[dict(rename = "time")] String),
 
     /// TIMESTAMP literal
-    Timestamp(String),
+    #[dict(rename = "TimestampLiteral")]
+    Timestamp(#[dict(rename = "ts")] String),
+
+    /// a placeholder standing for any literal value, as left by the query normaliser
+    #[dict(rename = "PlaceholderLiteral")]
+    Placeholder,
+}
+
+/// Bind parameters, standing for values given when the statement is run
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
+#[dict(tag = "_type")]
+pub enum Parameter {
+    /// `?`, numbered from 1 in order of appearance
+    #[dict(rename = "PositionalParameter")]
+    Positional(#[dict(rename = "index")] usize),
+
+    /// `:name`
+    #[dict(rename = "NamedParameter")]
+    Named(#[dict(rename = "name")] symbols::Name),
+
+    /// `${name}`, as substituted in Exasol scripts
+    #[dict(rename = "VariableParameter")]
+    Variable(#[dict(rename = "name")] symbols::Name),
+}
+
+/// Placeholder for the SQL skipped by the parser to recover from a syntax error
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
+pub struct ErrorNode {
+    /// the message of the parse error
+    pub message: String,
+
+    /// the unexpected token, `None` if the statement ended early
+    pub token: Option<String>,
+
+    /// the byte offset where the parser found the error
+    pub location: usize,
+
+    /// the SQL skipped, printed back as it is
+    pub text: String,
+
+    /// where the SQL skipped is in the source
+    pub span: symbols::Span,
 }
 
 /// Sort ordering direction
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub enum OrderingDirection {
     /// Sort in ascending order
     Ascending,
@@ -717,7 +825,7 @@ pub enum OrderingDirection {
 }
 
 /// Specification of a sort order
-#[derive(IntoPyObject, Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub struct Ordering {
     /// an expression evaluating to the sort key
     pub expr: Expression,
@@ -730,7 +838,7 @@ pub struct Ordering {
 }
 
 /// Limits for a limit clause
-#[derive(IntoPyObject, Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
 pub struct Limit {
     /// number of rows to return
     pub number_rows: Expression,
@@ -747,29 +855,38 @@ pub fn append<T>(list: Vec<T>, item: T) -> Vec<T> {
 }
 
 /// Supported data types
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(IntoPyObject, FromPyObject, Serialize, Debug, PartialEq, Eq, Clone)]
+#[dict(tag = "_type")]
 pub enum DataType {
     /// boolean data type
+    #[dict(rename = "BooleanDataType")]
     Boolean,
 
     /// char
-    Char(Literal),
+    #[dict(rename = "CharDataType")]
+    Char(#[dict(rename = "s")] Literal),
 
     /// date
+    #[dict(rename = "DateDataType")]
     Date,
 
     /// decimal
+    #[dict(rename = "DecimalDataType")]
     Decimal { p: Literal, s: Literal },
 
     /// double precision
+    #[dict(rename = "DoublePrecisionDataType")]
     DoublePrecision,
 
     /// timestamp
+    #[dict(rename = "TimestampDataType")]
     Timestamp,
 
     /// local timestamp
+    #[dict(rename = "LocalTimestampDataType")]
     LocalTimestamp,
 
     /// varchar
-    Varchar(Literal),
+    #[dict(rename = "VarcharDataType")]
+    Varchar(#[dict(rename = "s")] Literal),
 }
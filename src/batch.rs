@@ -0,0 +1,91 @@
+// MIT License
+//
+// Copyright (c) 2019 Daniele Esposti
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::mem;
+use std::panic;
+use std::thread;
+
+use dict_derive::IntoPyObject;
+
+use super::ast;
+use super::recovery::parse_strict;
+
+/// The outcome of parsing one of the statements of a batch: either the statement or the
+/// message of the parse error
+#[derive(IntoPyObject, Debug, PartialEq, Eq, Clone)]
+pub struct ParseResult {
+    pub statement: Option<ast::SqlStatement>,
+    pub error: Option<String>,
+}
+
+impl From<Result<ast::SqlStatement, String>> for ParseResult {
+    fn from(result: Result<ast::SqlStatement, String>) -> ParseResult {
+        match result {
+            Ok(statement) => ParseResult {
+                statement: Some(statement),
+                error: None,
+            },
+            Err(error) => ParseResult {
+                statement: None,
+                error: Some(error),
+            },
+        }
+    }
+}
+
+/// Parse the statements on up to `threads` threads, each one parsing a contiguous chunk of
+/// them, and return the results in the same order as the statements
+pub fn parse_many(sql_strs: Vec<String>, threads: usize) -> Vec<Result<ast::SqlStatement, String>> {
+    let threads = threads.min(sql_strs.len());
+
+    if threads <= 1 {
+        return sql_strs
+            .iter()
+            .map(|sql_str| parse_strict(sql_str))
+            .collect();
+    }
+
+    let chunk_size = sql_strs.len().div_ceil(threads);
+    let mut rest = sql_strs;
+    let mut handles = Vec::with_capacity(threads);
+
+    while !rest.is_empty() {
+        let tail = rest.split_off(chunk_size.min(rest.len()));
+        let chunk = mem::replace(&mut rest, tail);
+
+        handles.push(thread::spawn(move || {
+            chunk
+                .iter()
+                .map(|sql_str| parse_strict(sql_str))
+                .collect::<Vec<_>>()
+        }));
+    }
+
+    handles
+        .into_iter()
+        .flat_map(|handle| {
+            handle
+                .join()
+                .unwrap_or_else(|err| panic::resume_unwind(err))
+        })
+        .collect()
+}
@@ -0,0 +1,502 @@
+// MIT License
+//
+// Copyright (c) 2019 Daniele Esposti
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::HashSet;
+use std::env;
+use std::fmt::Display;
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+use std::process;
+
+use lalrpop_util::ParseError;
+
+use hephaestus::ast::SqlStatement;
+use hephaestus::catalog::MemoryCatalog;
+use hephaestus::complexity;
+use hephaestus::format::{self, FormatOptions};
+use hephaestus::lint::{Linter, Severity};
+use hephaestus::{binder, inspect, recovery, split_statements, sql, strip_comments, typecheck};
+
+const USAGE: &str = "\
+Usage: hephaestus <command> [options] [FILE]...
+
+Read the statements, separated by `;`, from the given files or from the standard input.
+
+Commands:
+    parse [--format json|debug]    print the AST of the statements, as JSON by default
+    complexity [--format text|json]
+                                   print the complexity metrics of the statements, as text by
+                                   default
+    fmt [--check] [--config FILE]  reformat the files in place, or print the formatted standard
+                                   input; with `--check` just list the files to reformat. The
+                                   style is read from the TOML file, `hephaestus.toml` by default
+    lint [--catalog FILE] [--disable RULE,...]
+                                   report the type errors, the problems found by the lint rules
+                                   but the disabled ones and, given a JSON catalog, the unknown
+                                   tables and columns
+    tables                         list the tables referenced by the statements
+    validate                       report the statements which cannot be parsed
+
+The exit code is 1 when a statement cannot be parsed or there are problems to report, other than
+lint infos, 2 for invalid arguments or unreadable files.
+";
+
+/// The command line options; each command accepts just some of them
+#[derive(Default)]
+struct Options {
+    check: bool,
+    format: Option<String>,
+    catalog: Option<String>,
+    config: Option<String>,
+    disable: Vec<String>,
+    files: Vec<String>,
+}
+
+/// An input script, either a file or the standard input
+struct Source {
+    path: Option<String>,
+    sql: String,
+}
+
+impl Source {
+    fn name(&self) -> &str {
+        self.path.as_ref().map_or("<stdin>", String::as_str)
+    }
+
+    /// The 1-based line and column of a byte offset
+    fn location(&self, offset: usize) -> (usize, usize) {
+        let before = &self.sql[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+
+        (
+            before.matches('\n').count() + 1,
+            before[line_start..].chars().count() + 1,
+        )
+    }
+
+    fn report(&self, offset: usize, message: impl Display) {
+        let (line, column) = self.location(offset);
+        eprintln!("{}:{}:{}: {}", self.name(), line, column, message);
+    }
+}
+
+/// A statement of a source, with its byte offset in the source
+struct Parsed {
+    offset: usize,
+    statement: SqlStatement,
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let code = match run(&args) {
+        Ok(true) => 0,
+        Ok(false) => 1,
+        Err(message) => {
+            eprintln!("hephaestus: {}", message);
+            2
+        }
+    };
+
+    process::exit(code);
+}
+
+/// Run a command, returning whether it succeeded without problems to report
+fn run(args: &[String]) -> Result<bool, String> {
+    let (command, args) = match args.split_first() {
+        Some((command, args)) => (command.as_str(), args),
+        None => return Err(format!("missing command\n\n{}", USAGE)),
+    };
+
+    if command == "-h" || command == "--help" || command == "help" {
+        print!("{}", USAGE);
+        return Ok(true);
+    }
+
+    let options = parse_options(command, args)?;
+    let sources = read_sources(&options.files)?;
+
+    match command {
+        "parse" => parse(&sources, options.format.as_deref().unwrap_or("json")),
+        "complexity" => complexity(&sources, options.format.as_deref().unwrap_or("text")),
+        "fmt" => fmt(&sources, options.check, options.config.as_deref()),
+        "lint" => lint(&sources, options.catalog.as_deref(), &options.disable),
+        "tables" => tables(&sources),
+        "validate" => {
+            // parse every source, to report the errors of all of them
+            let mut valid = true;
+
+            for source in &sources {
+                valid &= parse_source(source).is_some();
+            }
+
+            Ok(valid)
+        }
+        _ => unreachable!(),
+    }
+}
+
+fn parse_options(command: &str, args: &[String]) -> Result<Options, String> {
+    let allowed: &[&str] = match command {
+        "parse" | "complexity" => &["--format"],
+        "fmt" => &["--check", "--config"],
+        "lint" => &["--catalog", "--disable"],
+        "tables" | "validate" => &[],
+        _ => return Err(format!("unknown command `{}`\n\n{}", command, USAGE)),
+    };
+
+    let mut options = Options::default();
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        if !arg.starts_with("--") {
+            options.files.push(arg.clone());
+            continue;
+        }
+
+        let (name, value) = match arg.find('=') {
+            Some(i) => (&arg[..i], Some(arg[i + 1..].to_string())),
+            None => (arg.as_str(), None),
+        };
+
+        if !allowed.contains(&name) {
+            return Err(format!("unknown option `{}` for `{}`", name, command));
+        }
+
+        if name == "--check" {
+            options.check = true;
+            continue;
+        }
+
+        let value = match value.or_else(|| args.next().cloned()) {
+            Some(value) => value,
+            None => return Err(format!("missing value for `{}`", name)),
+        };
+
+        match name {
+            "--format" if value == "json" => options.format = Some(value),
+            "--format" if value == "debug" && command == "parse" => options.format = Some(value),
+            "--format" if value == "text" && command == "complexity" => {
+                options.format = Some(value)
+            }
+            "--format" => return Err(format!("unknown format `{}`", value)),
+            "--catalog" => options.catalog = Some(value),
+            "--disable" => options
+                .disable
+                .extend(value.split(',').map(|rule| rule.trim().to_string())),
+            _ => options.config = Some(value),
+        }
+    }
+
+    Ok(options)
+}
+
+fn read_sources(files: &[String]) -> Result<Vec<Source>, String> {
+    if files.is_empty() {
+        let mut sql = String::new();
+
+        io::stdin()
+            .read_to_string(&mut sql)
+            .map_err(|e| format!("cannot read the standard input: {}", e))?;
+
+        return Ok(vec![Source { path: None, sql }]);
+    }
+
+    files
+        .iter()
+        .map(|path| {
+            fs::read_to_string(path)
+                .map(|sql| Source {
+                    path: Some(path.clone()),
+                    sql,
+                })
+                .map_err(|e| format!("cannot read {}: {}", path, e))
+        })
+        .collect()
+}
+
+/// Parse the statements of a source, reporting the parse errors; `None` if any statement
+/// cannot be parsed
+fn parse_source(source: &Source) -> Option<Vec<Parsed>> {
+    let mut statements = Vec::new();
+    let mut valid = true;
+
+    for (offset, sql_str) in split_statements(&source.sql) {
+        let stripped_sql = strip_comments(sql_str);
+
+        match sql::SqlStatementParser::new().parse(&stripped_sql) {
+            Ok(statement) => match recovery::errors(&statement).first() {
+                Some(error) => {
+                    let message = match &error.token {
+                        Some(token) => format!("unexpected token `{}`", token),
+                        None => "unexpected end of statement".to_string(),
+                    };
+                    source.report(offset + error.location, message);
+                    valid = false;
+                }
+                None => statements.push(Parsed { offset, statement }),
+            },
+            Err(error) => {
+                let (location, message) = describe_error(&error);
+                source.report(offset + location.unwrap_or(0), message);
+                valid = false;
+            }
+        }
+    }
+
+    if valid {
+        Some(statements)
+    } else {
+        None
+    }
+}
+
+/// The offset, within the statement, and the message of a parse error
+fn describe_error<T: Display, E: Display>(
+    error: &ParseError<usize, T, E>,
+) -> (Option<usize>, String) {
+    match error {
+        ParseError::InvalidToken { location } => (Some(*location), "invalid token".to_string()),
+        ParseError::UnrecognizedEOF { location, .. } => {
+            (Some(*location), "unexpected end of statement".to_string())
+        }
+        ParseError::UnrecognizedToken {
+            token: (start, token, _),
+            ..
+        } => (Some(*start), format!("unexpected token `{}`", token)),
+        ParseError::ExtraToken {
+            token: (start, token, _),
+        } => (Some(*start), format!("extra token `{}`", token)),
+        ParseError::User { error } => (None, error.to_string()),
+    }
+}
+
+/// The offset of the first token of a statement
+fn statement_start(source: &Source, parsed: &Parsed) -> usize {
+    let stripped_sql = strip_comments(&source.sql[parsed.offset..]);
+    let blank = stripped_sql.len() - stripped_sql.trim_start().len();
+
+    parsed.offset + blank
+}
+
+fn parse(sources: &[Source], format: &str) -> Result<bool, String> {
+    let mut valid = true;
+
+    for source in sources {
+        let statements = match parse_source(source) {
+            Some(statements) => statements,
+            None => {
+                valid = false;
+                continue;
+            }
+        };
+
+        for parsed in statements {
+            if format == "debug" {
+                println!("{:#?}", parsed.statement);
+            } else {
+                let json = serde_json::to_string_pretty(&parsed.statement)
+                    .map_err(|e| format!("cannot serialize the AST: {}", e))?;
+                println!("{}", json);
+            }
+        }
+    }
+
+    Ok(valid)
+}
+
+fn complexity(sources: &[Source], format: &str) -> Result<bool, String> {
+    let mut valid = true;
+
+    for source in sources {
+        let statements = match parse_source(source) {
+            Some(statements) => statements,
+            None => {
+                valid = false;
+                continue;
+            }
+        };
+
+        for parsed in &statements {
+            let metrics = complexity::complexity(&parsed.statement);
+
+            if format == "json" {
+                let json = serde_json::to_string_pretty(&metrics)
+                    .map_err(|e| format!("cannot serialize the metrics: {}", e))?;
+                println!("{}", json);
+            } else {
+                let (line, column) = source.location(statement_start(source, parsed));
+                println!("{}:{}:{}", source.name(), line, column);
+
+                for metric in metrics.to_string().lines() {
+                    println!("    {}", metric);
+                }
+            }
+        }
+    }
+
+    Ok(valid)
+}
+
+/// The default file of the format options, used if it exists
+const FORMAT_CONFIG: &str = "hephaestus.toml";
+
+fn format_options(config: Option<&str>) -> Result<FormatOptions, String> {
+    let path = match config {
+        Some(path) => path,
+        None if Path::new(FORMAT_CONFIG).exists() => FORMAT_CONFIG,
+        None => return Ok(FormatOptions::default()),
+    };
+
+    let toml = fs::read_to_string(path).map_err(|e| format!("cannot read {}: {}", path, e))?;
+
+    FormatOptions::from_toml(&toml).map_err(|e| format!("{}: {}", path, e))
+}
+
+fn fmt(sources: &[Source], check: bool, config: Option<&str>) -> Result<bool, String> {
+    let options = format_options(config)?;
+    let mut formatted_all = true;
+
+    for source in sources {
+        // parse first to report the errors with their location
+        if parse_source(source).is_none() {
+            formatted_all = false;
+            continue;
+        }
+
+        let mut formatted = format::format_sql(&source.sql, &options)?;
+
+        if !formatted.is_empty() {
+            formatted.push('\n');
+        }
+
+        match &source.path {
+            None if check => formatted_all &= formatted == source.sql,
+            None => print!("{}", formatted),
+            Some(_) if formatted == source.sql => {}
+            Some(path) if check => {
+                println!("{}", path);
+                formatted_all = false;
+            }
+            Some(path) => {
+                fs::write(path, formatted).map_err(|e| format!("cannot write {}: {}", path, e))?
+            }
+        }
+    }
+
+    Ok(formatted_all)
+}
+
+fn lint(sources: &[Source], catalog: Option<&str>, disable: &[String]) -> Result<bool, String> {
+    let mut linter = Linter::new();
+
+    for rule in disable {
+        linter = linter.without_rule(rule)?;
+    }
+
+    let catalog = match catalog {
+        Some(path) => {
+            let json =
+                fs::read_to_string(path).map_err(|e| format!("cannot read {}: {}", path, e))?;
+            let catalog = MemoryCatalog::from_json(&json)
+                .map_err(|e| format!("invalid catalog {}: {}", path, e))?;
+
+            Some(catalog)
+        }
+        None => None,
+    };
+
+    let mut clean = true;
+
+    for source in sources {
+        let statements = match parse_source(source) {
+            Some(statements) => statements,
+            None => {
+                clean = false;
+                continue;
+            }
+        };
+
+        for parsed in &statements {
+            // type errors don't carry a location, they are reported at the statement
+            for error in typecheck::check(&parsed.statement) {
+                source.report(statement_start(source, parsed), error);
+                clean = false;
+            }
+
+            for diagnostic in linter.lint(&parsed.statement) {
+                let offset = diagnostic.span.map_or_else(
+                    || statement_start(source, parsed),
+                    |span| parsed.offset + span.start,
+                );
+
+                source.report(offset, &diagnostic);
+                clean &= diagnostic.severity == Severity::Info;
+            }
+
+            if let Some(catalog) = &catalog {
+                for error in binder::bind(catalog, &parsed.statement).errors {
+                    let offset = error.span.map_or_else(
+                        || statement_start(source, parsed),
+                        |span| parsed.offset + span.start,
+                    );
+
+                    // the span is relative to the statement, don't print it
+                    let error = binder::BindError {
+                        span: None,
+                        ..error
+                    };
+                    source.report(offset, error);
+                    clean = false;
+                }
+            }
+        }
+    }
+
+    Ok(clean)
+}
+
+fn tables(sources: &[Source]) -> Result<bool, String> {
+    let mut valid = true;
+    let mut seen = HashSet::new();
+
+    for source in sources {
+        let statements = match parse_source(source) {
+            Some(statements) => statements,
+            None => {
+                valid = false;
+                continue;
+            }
+        };
+
+        for parsed in &statements {
+            for table in inspect::tables(&parsed.statement) {
+                if seen.insert(table.to_lowercase()) {
+                    println!("{}", table);
+                }
+            }
+        }
+    }
+
+    Ok(valid)
+}
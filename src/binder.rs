@@ -0,0 +1,950 @@
+// MIT License
+//
+// Copyright (c) 2019 Daniele Esposti
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use super::ast;
+use super::catalog::Catalog;
+use super::symbols::{Name, Span};
+use super::typecheck::{self, TypeChecker};
+use super::types::DataType;
+use super::visit::{self, Visitor};
+
+/// The kinds of problems found while binding names
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum BindErrorKind {
+    /// the table isn't in the catalog, or the qualifier doesn't match a table of the query
+    UnknownTable,
+
+    /// the column isn't defined by any table in scope
+    UnknownColumn,
+
+    /// more than one table in scope defines the column
+    AmbiguousColumn,
+}
+
+/// A name that cannot be bound
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct BindError {
+    pub kind: BindErrorKind,
+
+    /// the name as written in the query
+    pub name: Vec<Name>,
+
+    /// where the name appears in the query
+    pub span: Option<Span>,
+}
+
+impl BindError {
+    fn new(kind: BindErrorKind, name: &[Name]) -> BindError {
+        BindError {
+            kind,
+            name: name.to_vec(),
+            span: span_of(name),
+        }
+    }
+}
+
+impl fmt::Display for BindError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        let kind = match self.kind {
+            BindErrorKind::UnknownTable => "unknown table",
+            BindErrorKind::UnknownColumn => "unknown column",
+            BindErrorKind::AmbiguousColumn => "ambiguous column",
+        };
+
+        write!(f, "{} {}", kind, join(&self.name))?;
+
+        if let Some(span) = self.span {
+            write!(f, " at {}..{}", span.start, span.end)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A column reference of the query resolved to the column it refers to
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct BoundColumn {
+    /// the column reference as written in the query
+    pub identifiers: Vec<Name>,
+
+    /// the qualified name of the catalog table defining the column; `None` for the columns of
+    /// nested queries and common table expressions
+    pub table: Option<Vec<Name>>,
+
+    /// the referred column
+    pub column: Name,
+
+    pub data_type: DataType,
+}
+
+/// The outcome of binding a statement
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Binding {
+    /// the column references that have been resolved; the FROM clause of a query is bound before
+    /// its select list and the other clauses
+    pub columns: Vec<BoundColumn>,
+
+    pub errors: Vec<BindError>,
+}
+
+/// A column of a relation in scope
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct RelationColumn {
+    /// the name of the column; `None` for expressions without an alias
+    name: Option<Name>,
+
+    data_type: DataType,
+}
+
+/// A row set of the FROM clause
+#[derive(Debug, Clone)]
+struct Relation {
+    /// the name the relation can be referred to with
+    alias: Option<Name>,
+
+    /// the qualified name of the table, for relations coming from the catalog
+    table: Option<Vec<Name>>,
+
+    /// the columns of the relation; `None` when they are unknown, e.g. for tables missing from
+    /// the catalog
+    columns: Option<Vec<RelationColumn>>,
+}
+
+impl Relation {
+    fn column(&self, name: &Name) -> Option<&RelationColumn> {
+        self.columns
+            .as_ref()
+            .and_then(|columns| columns.iter().find(|c| c.name.as_ref() == Some(name)))
+    }
+
+    fn may_have_column(&self, name: &Name) -> bool {
+        self.columns.is_none() || self.column(name).is_some()
+    }
+}
+
+#[derive(Debug, Default)]
+struct Scope {
+    relations: Vec<Relation>,
+
+    /// the columns merged by `JOIN ... USING` and `NATURAL JOIN`, with the relations they come
+    /// from
+    coalesced: Vec<(Name, Vec<usize>)>,
+
+    /// the columns of the select list, which other clauses can refer to by their name
+    aliases: Vec<RelationColumn>,
+}
+
+impl Scope {
+    fn find_relation(&self, name: &Name) -> Option<&Relation> {
+        self.relations
+            .iter()
+            .rev()
+            .find(|relation| relation.alias.as_ref() == Some(name))
+    }
+
+    /// The columns selected by `*`: the coalesced columns first, then the remaining ones of each
+    /// relation in order. Each column comes with the index of the relation it must be qualified
+    /// with, `None` for the coalesced ones.
+    fn star_columns(&self) -> Option<Vec<(Option<usize>, RelationColumn)>> {
+        let mut columns = Vec::new();
+
+        for (name, relations) in &self.coalesced {
+            let data_type = relations
+                .first()
+                .and_then(|i| self.relations[*i].column(name))
+                .map_or(DataType::Generic, |column| column.data_type);
+
+            columns.push((
+                None,
+                RelationColumn {
+                    name: Some(name.clone()),
+                    data_type,
+                },
+            ));
+        }
+
+        for (i, relation) in self.relations.iter().enumerate() {
+            for column in relation.columns.as_ref()? {
+                let coalesced = self.coalesced.iter().any(|(name, relations)| {
+                    column.name.as_ref() == Some(name) && relations.contains(&i)
+                });
+
+                if !coalesced {
+                    columns.push((Some(i), column.clone()));
+                }
+            }
+        }
+
+        Some(columns)
+    }
+}
+
+enum Lookup {
+    Found(BoundColumn),
+
+    /// the column may belong to a relation whose columns are unknown
+    Unchecked,
+
+    Error(BindError),
+}
+
+/// Resolves the table and column names of statements against a catalog.
+pub struct Binder<'c> {
+    catalog: &'c dyn Catalog,
+}
+
+impl<'c> Binder<'c> {
+    pub fn new(catalog: &'c dyn Catalog) -> Binder<'c> {
+        Binder { catalog }
+    }
+
+    pub fn bind(&self, statement: &ast::SqlStatement) -> Binding {
+        let mut state = State::new(self.catalog);
+        state.sql_statement(statement);
+        state.binding
+    }
+}
+
+/// Bind the names of a statement against the catalog
+pub fn bind(catalog: &dyn Catalog, statement: &ast::SqlStatement) -> Binding {
+    Binder::new(catalog).bind(statement)
+}
+
+fn span_of(names: &[Name]) -> Option<Span> {
+    names
+        .iter()
+        .filter_map(Name::span)
+        .fold(None, |span: Option<Span>, other| match span {
+            Some(span) => Some(span.merge(other)),
+            None => Some(other),
+        })
+}
+
+fn join(names: &[Name]) -> String {
+    names.iter().map(Name::as_str).collect::<Vec<_>>().join(".")
+}
+
+/// The name of the column produced by a select list item
+fn result_column_name(column: &ast::ExprResultColumn) -> Option<Name> {
+    match (&column.rename, &column.expr) {
+        (Some(rename), _) => Some(rename.clone()),
+        (None, ast::Expression::QualifiedIdentifier(identifier)) => {
+            identifier.identifiers.last().cloned()
+        }
+        _ => None,
+    }
+}
+
+/// A select list item referring to a column
+fn column_reference(qualifier: Option<&Name>, column: &Name) -> ast::ResultColumn {
+    // the names are copied without their spans, as they don't appear at that position
+    let identifiers = qualifier
+        .into_iter()
+        .chain(Some(column))
+        .map(|name| Name::from(name.as_str()))
+        .collect();
+
+    ast::ResultColumn::Expr(ast::ExprResultColumn {
+        expr: ast::Expression::QualifiedIdentifier(ast::QualifiedIdentifierExpression {
+            identifiers,
+        }),
+        rename: None,
+    })
+}
+
+pub(crate) struct State<'c> {
+    catalog: &'c dyn Catalog,
+
+    /// the common table expressions visible from the current query, innermost last
+    ctes: Vec<Vec<(Name, Option<Vec<RelationColumn>>)>>,
+
+    /// the scopes of the queries being bound, innermost last
+    scopes: Vec<Scope>,
+
+    pub binding: Binding,
+
+    /// the position of each query of the statement being bound in the order `Visitor` walks
+    /// them, by address; the statement stays borrowed while it's bound, so its queries can't
+    /// move, and the positions are dropped afterwards
+    positions: HashMap<*const ast::QuerySetExpression, usize>,
+
+    /// the columns `*` and `t.*` stand for by their position in the select list, keyed by the
+    /// position of the query in the order `Visitor` and `VisitorMut` walk the statement
+    pub expansions: HashMap<usize, Vec<(usize, Vec<ast::ResultColumn>)>>,
+}
+
+impl<'c> State<'c> {
+    pub fn new(catalog: &'c dyn Catalog) -> State<'c> {
+        State {
+            catalog,
+            ctes: Vec::new(),
+            scopes: Vec::new(),
+            binding: Binding::default(),
+            positions: HashMap::new(),
+            expansions: HashMap::new(),
+        }
+    }
+
+    pub fn sql_statement(&mut self, statement: &ast::SqlStatement) {
+        let mut positions = QueryPositions {
+            positions: HashMap::new(),
+        };
+        positions.visit_sql_statement(statement);
+        self.positions = positions.positions;
+
+        match statement {
+            ast::SqlStatement::Statement(statement)
+            | ast::SqlStatement::ExplainQueryPlan(statement) => self.statement(statement),
+            ast::SqlStatement::Attach(_)
+            | ast::SqlStatement::Describe(_)
+            | ast::SqlStatement::Error(_) => (),
+        }
+
+        self.positions.clear();
+    }
+
+    fn error(&mut self, kind: BindErrorKind, name: &[Name]) {
+        self.binding.errors.push(BindError::new(kind, name));
+    }
+
+    fn statement(&mut self, statement: &ast::Statement) {
+        match statement {
+            ast::Statement::Select(select) => {
+                self.select_statement(select);
+            }
+            ast::Statement::Insert(insert) => {
+                let target = self.target(&insert.table_name);
+
+                if let (Some(names), Some(target)) = (&insert.columns, &target) {
+                    self.check_columns(target, names);
+                }
+
+                self.set_expression(&insert.source, &[]);
+            }
+            ast::Statement::Update(update) => {
+                if let Some(target) = self.target(&update.table_name) {
+                    for assignment in &update.assignments {
+                        self.check_columns(&target, &assignment.columns);
+                    }
+
+                    self.scopes.push(Scope {
+                        relations: vec![target],
+                        ..Scope::default()
+                    });
+                } else {
+                    self.scopes.push(Scope::default());
+                }
+
+                for assignment in &update.assignments {
+                    self.expression(&assignment.expr);
+                }
+
+                if let Some(expr) = &update.where_expr {
+                    self.expression(expr);
+                }
+
+                self.scopes.pop();
+            }
+            ast::Statement::Delete(delete) => {
+                let relations = self.target(&delete.table_name).into_iter().collect();
+                self.scopes.push(Scope {
+                    relations,
+                    ..Scope::default()
+                });
+
+                if let Some(expr) = &delete.where_expr {
+                    self.expression(expr);
+                }
+
+                self.scopes.pop();
+            }
+        }
+    }
+
+    /// The table written by a statement
+    fn target(&mut self, name: &[Name]) -> Option<Relation> {
+        match self.catalog_table(name) {
+            Some(relation) => Some(relation),
+            None => {
+                self.error(BindErrorKind::UnknownTable, name);
+                None
+            }
+        }
+    }
+
+    fn check_columns(&mut self, target: &Relation, names: &[Name]) {
+        for name in names {
+            if !target.may_have_column(name) {
+                self.error(BindErrorKind::UnknownColumn, std::slice::from_ref(name));
+            }
+        }
+    }
+
+    fn catalog_table(&self, name: &[Name]) -> Option<Relation> {
+        self.catalog.table(name).map(|(qualified, table)| Relation {
+            alias: name.last().cloned(),
+            table: Some(qualified),
+            columns: Some(
+                table
+                    .columns
+                    .iter()
+                    .map(|column| RelationColumn {
+                        name: Some(column.name.clone()),
+                        data_type: column.data_type,
+                    })
+                    .collect(),
+            ),
+        })
+    }
+
+    fn lookup_cte(&self, name: &Name) -> Option<Option<Vec<RelationColumn>>> {
+        self.ctes.iter().rev().find_map(|frame| {
+            frame
+                .iter()
+                .rev()
+                .find(|(identifier, _)| identifier == name)
+                .map(|(_, columns)| columns.clone())
+        })
+    }
+
+    fn select_statement(&mut self, select: &ast::SelectStatement) -> Option<Vec<RelationColumn>> {
+        self.ctes.push(Vec::new());
+
+        for cte in &select.common {
+            // with `WITH RECURSIVE` the query can reference its own common table expression,
+            // whose columns are unknown until the query is bound
+            if select.recursive {
+                self.ctes
+                    .last_mut()
+                    .unwrap()
+                    .push((cte.identifier.clone(), None));
+            }
+
+            let mut columns = self.select_statement(&cte.query);
+
+            if let (Some(columns), Some(names)) = (&mut columns, &cte.column_names) {
+                for (column, name) in columns.iter_mut().zip(names) {
+                    column.name = Some(name.clone());
+                }
+            }
+
+            let frame = self.ctes.last_mut().unwrap();
+
+            if select.recursive {
+                frame.pop();
+            }
+
+            frame.push((cte.identifier.clone(), columns));
+        }
+
+        let columns = self.set_expression(&select.expr, &select.order_by);
+
+        if let Some(limit) = &select.limit {
+            self.expression(&limit.number_rows);
+
+            if let Some(offset) = &limit.offset_value {
+                self.expression(offset);
+            }
+        }
+
+        self.ctes.pop();
+
+        columns
+    }
+
+    /// Bind a set expression and the ORDER BY clause applied to it, returning its columns
+    fn set_expression(
+        &mut self,
+        expr: &ast::SetExpression,
+        order_by: &[ast::Ordering],
+    ) -> Option<Vec<RelationColumn>> {
+        let columns = match expr {
+            ast::SetExpression::Query(query) => return self.query_set_expression(query, order_by),
+            ast::SetExpression::Values(values) => {
+                for row in &values.values {
+                    for expr in row {
+                        self.expression(expr);
+                    }
+                }
+
+                values.values.first().map(|row| {
+                    row.iter()
+                        .map(|expr| RelationColumn {
+                            name: None,
+                            data_type: self.expression_type(expr),
+                        })
+                        .collect()
+                })
+            }
+            ast::SetExpression::Op(op) => {
+                let left = self.set_expression(&op.left, &[]);
+                let right = self.set_expression(&op.right, &[]);
+
+                left.map(|mut columns| {
+                    for (column, other) in columns.iter_mut().zip(right.iter().flatten()) {
+                        column.data_type =
+                            typecheck::common_type(column.data_type, other.data_type)
+                                .unwrap_or(DataType::Generic);
+                    }
+
+                    columns
+                })
+            }
+        };
+
+        // the ORDER BY clause of a compound query can only refer to its result columns
+        if !order_by.is_empty() {
+            self.scopes.push(Scope {
+                relations: vec![Relation {
+                    alias: None,
+                    table: None,
+                    columns: columns.clone(),
+                }],
+                ..Scope::default()
+            });
+
+            for ordering in order_by {
+                self.expression(&ordering.expr);
+            }
+
+            self.scopes.pop();
+        }
+
+        columns
+    }
+
+    fn query_set_expression(
+        &mut self,
+        query: &ast::QuerySetExpression,
+        order_by: &[ast::Ordering],
+    ) -> Option<Vec<RelationColumn>> {
+        self.scopes.push(Scope::default());
+
+        for table in &query.from {
+            self.table_expression(table);
+        }
+
+        let expanded = self.expand_stars(&query.columns);
+
+        if !expanded.is_empty() {
+            if let Some(&position) = self.positions.get(&(query as *const _)) {
+                self.expansions.insert(position, expanded);
+            }
+        }
+
+        let columns = match &query.columns {
+            ast::ResultColumns::All => self
+                .scopes
+                .last()
+                .unwrap()
+                .star_columns()
+                .map(|columns| columns.into_iter().map(|(_, column)| column).collect()),
+            ast::ResultColumns::List(list) => {
+                let mut columns = Some(Vec::new());
+
+                for column in list {
+                    let found = self.result_column(column);
+
+                    columns = match (columns, found) {
+                        (Some(mut columns), Some(found)) => {
+                            columns.extend(found);
+                            Some(columns)
+                        }
+                        _ => None,
+                    };
+                }
+
+                columns
+            }
+        };
+
+        if let ast::ResultColumns::List(list) = &query.columns {
+            let aliases = list
+                .iter()
+                .filter_map(|column| match column {
+                    ast::ResultColumn::Expr(column) => Some(RelationColumn {
+                        name: result_column_name(column),
+                        data_type: self.expression_type(&column.expr),
+                    }),
+                    ast::ResultColumn::AllFrom(_) => None,
+                })
+                .collect();
+
+            self.scopes.last_mut().unwrap().aliases = aliases;
+        }
+
+        if let Some(expr) = &query.where_expr {
+            self.expression(expr);
+        }
+
+        if let Some(group_by) = &query.group_by {
+            for expr in &group_by.groupings {
+                self.expression(expr);
+            }
+
+            if let Some(expr) = &group_by.having {
+                self.expression(expr);
+            }
+        }
+
+        for ordering in order_by {
+            self.expression(&ordering.expr);
+        }
+
+        self.scopes.pop();
+
+        columns
+    }
+
+    /// The columns the stars of the select list stand for, by their position in the list.
+    /// Stars over relations with unknown or unnamed columns are left out.
+    fn expand_stars(&self, columns: &ast::ResultColumns) -> Vec<(usize, Vec<ast::ResultColumn>)> {
+        let scope = self.scopes.last().unwrap();
+
+        let qualify = |relation: &Relation, column: &RelationColumn| {
+            column
+                .name
+                .as_ref()
+                .map(|name| column_reference(relation.alias.as_ref(), name))
+        };
+
+        match columns {
+            ast::ResultColumns::All => scope
+                .star_columns()
+                .and_then(|columns| {
+                    columns
+                        .into_iter()
+                        .map(|(i, column)| match i {
+                            Some(i) => qualify(&scope.relations[i], &column),
+                            None => column
+                                .name
+                                .as_ref()
+                                .map(|name| column_reference(None, name)),
+                        })
+                        .collect()
+                })
+                .map(|columns| (0, columns))
+                .into_iter()
+                .collect(),
+            ast::ResultColumns::List(list) => list
+                .iter()
+                .enumerate()
+                .filter_map(|(position, column)| {
+                    let relation = match column {
+                        ast::ResultColumn::AllFrom(name) => scope.find_relation(name)?,
+                        ast::ResultColumn::Expr(_) => return None,
+                    };
+
+                    relation
+                        .columns
+                        .as_ref()?
+                        .iter()
+                        .map(|column| qualify(relation, column))
+                        .collect::<Option<Vec<_>>>()
+                        .map(|columns| (position, columns))
+                })
+                .collect(),
+        }
+    }
+
+    fn result_column(&mut self, column: &ast::ResultColumn) -> Option<Vec<RelationColumn>> {
+        match column {
+            ast::ResultColumn::AllFrom(name) => {
+                match self.scopes.last().unwrap().find_relation(name) {
+                    Some(relation) => relation.columns.clone(),
+                    None => {
+                        self.error(BindErrorKind::UnknownTable, std::slice::from_ref(name));
+                        None
+                    }
+                }
+            }
+            ast::ResultColumn::Expr(column) => {
+                self.expression(&column.expr);
+
+                Some(vec![RelationColumn {
+                    name: result_column_name(column),
+                    data_type: self.expression_type(&column.expr),
+                }])
+            }
+        }
+    }
+
+    fn push_relation(&mut self, relation: Relation) {
+        self.scopes.last_mut().unwrap().relations.push(relation);
+    }
+
+    fn table_expression(&mut self, table: &ast::TableExpression) {
+        match table {
+            ast::TableExpression::Named(named) => {
+                let cte = match named.name.as_slice() {
+                    [name] => self.lookup_cte(name),
+                    _ => None,
+                };
+
+                let relation = match cte {
+                    Some(columns) => Relation {
+                        alias: named.name.last().cloned(),
+                        table: None,
+                        columns,
+                    },
+                    None => self.catalog_table(&named.name).unwrap_or_else(|| {
+                        self.error(BindErrorKind::UnknownTable, &named.name);
+
+                        Relation {
+                            alias: named.name.last().cloned(),
+                            table: None,
+                            columns: None,
+                        }
+                    }),
+                };
+
+                self.push_relation(Relation {
+                    alias: named.alias.clone().or(relation.alias),
+                    ..relation
+                });
+            }
+            ast::TableExpression::Select(select) => {
+                // a derived table can't see the other tables of the FROM clause
+                let scope = self.scopes.pop().unwrap();
+                let columns = self.select_statement(&select.select);
+                self.scopes.push(scope);
+
+                self.push_relation(Relation {
+                    alias: select.alias.clone(),
+                    table: None,
+                    columns,
+                });
+            }
+            ast::TableExpression::Join(join) => {
+                let start = self.scopes.last().unwrap().relations.len();
+                self.table_expression(&join.left);
+                let middle = self.scopes.last().unwrap().relations.len();
+                self.table_expression(&join.right);
+                let end = self.scopes.last().unwrap().relations.len();
+
+                match &join.constraint {
+                    ast::JoinConstraint::Expr(expr) => self.expression(expr),
+                    ast::JoinConstraint::Columns(columns) => {
+                        for column in &columns.columns {
+                            self.coalesce(column, start, middle, end);
+                        }
+                    }
+                }
+
+                if let ast::JoinOperator::Natural(_) = join.op {
+                    let scope = self.scopes.last().unwrap();
+                    let common = (start..middle)
+                        .filter_map(|i| scope.relations[i].columns.as_ref())
+                        .flatten()
+                        .filter_map(|column| column.name.clone())
+                        .filter(|name| {
+                            (middle..end).any(|i| scope.relations[i].column(name).is_some())
+                        })
+                        .collect::<Vec<_>>();
+
+                    for column in common {
+                        self.coalesce(&column, start, middle, end);
+                    }
+                }
+            }
+            // a table which cannot be parsed may have any column
+            ast::TableExpression::Error(_) => self.push_relation(Relation {
+                alias: None,
+                table: None,
+                columns: None,
+            }),
+        }
+    }
+
+    /// Merge the column of the relations on the left, `start..middle`, with the one of the
+    /// relations on the right, `middle..end`
+    fn coalesce(&mut self, column: &Name, start: usize, middle: usize, end: usize) {
+        let scope = self.scopes.last_mut().unwrap();
+
+        if scope.coalesced.iter().any(|(name, _)| name == column) {
+            return;
+        }
+
+        let left = (start..middle)
+            .rev()
+            .find(|i| scope.relations[*i].may_have_column(column));
+        let right = (middle..end).find(|i| scope.relations[*i].may_have_column(column));
+
+        match (left, right) {
+            (Some(left), Some(right)) => scope.coalesced.push((column.clone(), vec![left, right])),
+            _ => self.error(BindErrorKind::UnknownColumn, std::slice::from_ref(column)),
+        }
+    }
+
+    fn expression(&mut self, expr: &ast::Expression) {
+        ExpressionBinder { state: self }.visit_expression(expr);
+    }
+
+    /// The type of an expression whose names have already been bound
+    fn expression_type(&self, expr: &ast::Expression) -> DataType {
+        TypeChecker::new()
+            .with_column_types(|identifiers| match self.lookup(identifiers) {
+                Lookup::Found(column) => Some(column.data_type),
+                Lookup::Unchecked | Lookup::Error(_) => None,
+            })
+            .annotate(expr)
+            .data_type
+    }
+
+    fn lookup(&self, identifiers: &[Name]) -> Lookup {
+        match identifiers {
+            [column] => self.lookup_unqualified(column),
+            [.., column] => {
+                let qualifier = &identifiers[..identifiers.len() - 1];
+
+                for scope in self.scopes.iter().rev() {
+                    let relation = match qualifier {
+                        [alias] => scope.find_relation(alias),
+                        _ => scope
+                            .relations
+                            .iter()
+                            .find(|relation| relation.table.as_deref() == Some(qualifier)),
+                    };
+
+                    if let Some(relation) = relation {
+                        return self.lookup_column(identifiers, relation, column);
+                    }
+                }
+
+                Lookup::Error(BindError::new(BindErrorKind::UnknownTable, qualifier))
+            }
+            [] => Lookup::Unchecked,
+        }
+    }
+
+    fn lookup_column(&self, identifiers: &[Name], relation: &Relation, column: &Name) -> Lookup {
+        match (&relation.columns, relation.column(column)) {
+            (None, _) => Lookup::Unchecked,
+            (Some(_), Some(found)) => Lookup::Found(BoundColumn {
+                identifiers: identifiers.to_vec(),
+                table: relation.table.clone(),
+                column: column.clone(),
+                data_type: found.data_type,
+            }),
+            (Some(_), None) => {
+                Lookup::Error(BindError::new(BindErrorKind::UnknownColumn, identifiers))
+            }
+        }
+    }
+
+    fn lookup_unqualified(&self, column: &Name) -> Lookup {
+        let identifiers = std::slice::from_ref(column);
+
+        for scope in self.scopes.iter().rev() {
+            if let Some((_, relations)) = scope.coalesced.iter().find(|(name, _)| name == column) {
+                return self.lookup_column(identifiers, &scope.relations[relations[0]], column);
+            }
+
+            let mut candidates = scope
+                .relations
+                .iter()
+                .filter(|relation| relation.column(column).is_some());
+
+            match (candidates.next(), candidates.next()) {
+                (Some(_), Some(_)) => {
+                    return Lookup::Error(BindError::new(
+                        BindErrorKind::AmbiguousColumn,
+                        identifiers,
+                    ))
+                }
+                (Some(relation), None) => {
+                    return self.lookup_column(identifiers, relation, column);
+                }
+                (None, _) => (),
+            }
+
+            if scope
+                .relations
+                .iter()
+                .any(|relation| relation.columns.is_none())
+            {
+                return Lookup::Unchecked;
+            }
+
+            if let Some(alias) = scope
+                .aliases
+                .iter()
+                .find(|c| c.name.as_ref() == Some(column))
+            {
+                return Lookup::Found(BoundColumn {
+                    identifiers: identifiers.to_vec(),
+                    table: None,
+                    column: column.clone(),
+                    data_type: alias.data_type,
+                });
+            }
+        }
+
+        Lookup::Error(BindError::new(BindErrorKind::UnknownColumn, identifiers))
+    }
+}
+
+/// Numbers the queries of a statement in the order they are visited
+struct QueryPositions {
+    positions: HashMap<*const ast::QuerySetExpression, usize>,
+}
+
+impl<'ast> Visitor<'ast> for QueryPositions {
+    fn visit_query_set_expression(&mut self, node: &'ast ast::QuerySetExpression) {
+        let position = self.positions.len();
+        self.positions.insert(node as *const _, position);
+
+        visit::walk_query_set_expression(self, node)
+    }
+}
+
+struct ExpressionBinder<'s, 'c> {
+    state: &'s mut State<'c>,
+}
+
+impl<'ast, 's, 'c> Visitor<'ast> for ExpressionBinder<'s, 'c> {
+    fn visit_qualified_identifier(&mut self, node: &'ast ast::QualifiedIdentifierExpression) {
+        match self.state.lookup(&node.identifiers) {
+            Lookup::Found(column) => self.state.binding.columns.push(column),
+            Lookup::Unchecked => (),
+            Lookup::Error(error) => self.state.binding.errors.push(error),
+        }
+    }
+
+    fn visit_select_statement(&mut self, node: &'ast ast::SelectStatement) {
+        self.state.select_statement(node);
+    }
+
+    fn visit_set_specification(&mut self, node: &'ast ast::SetSpecification) {
+        match node {
+            ast::SetSpecification::Name(name) => {
+                if self.state.catalog.table(name).is_none() {
+                    self.state.error(BindErrorKind::UnknownTable, name);
+                }
+            }
+            _ => visit::walk_set_specification(self, node),
+        }
+    }
+}
@@ -0,0 +1,146 @@
+// MIT License
+//
+// Copyright (c) 2019 Daniele Esposti
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use super::error::Error;
+use super::symbols::Name;
+use super::types::DataType;
+
+/// A column of a table in the catalog
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct Column {
+    pub name: Name,
+    pub data_type: DataType,
+}
+
+/// A table with its columns, in table order
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct Table {
+    pub name: Name,
+    pub columns: Vec<Column>,
+}
+
+impl Table {
+    pub fn column(&self, name: &Name) -> Option<&Column> {
+        self.columns.iter().find(|column| &column.name == name)
+    }
+}
+
+/// A schema with its tables
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct Schema {
+    pub name: Name,
+    pub tables: Vec<Table>,
+}
+
+impl Schema {
+    pub fn table(&self, name: &Name) -> Option<&Table> {
+        self.tables.iter().find(|table| &table.name == name)
+    }
+}
+
+/// The schema objects queries are resolved against
+pub trait Catalog {
+    /// Look up a table by its name as written in a query, i.e. `table` or `schema.table`.
+    ///
+    /// Returns the fully qualified name of the table together with its definition.
+    fn table(&self, name: &[Name]) -> Option<(Vec<Name>, &Table)>;
+}
+
+/// A catalog held in memory, usually loaded from a JSON document like
+///
+/// ```json
+/// {
+///     "default_schema": "sales",
+///     "schemas": [
+///         {
+///             "name": "sales",
+///             "tables": [
+///                 {
+///                     "name": "orders",
+///                     "columns": [
+///                         {"name": "id", "data_type": "Numeric"},
+///                         {"name": "placed_at", "data_type": "Timestamp"}
+///                     ]
+///                 }
+///             ]
+///         }
+///     ]
+/// }
+/// ```
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+pub struct MemoryCatalog {
+    /// the schema of the tables referenced without a schema; when missing such tables are
+    /// looked up in every schema and must be unique
+    #[serde(default)]
+    pub default_schema: Option<Name>,
+
+    #[serde(default)]
+    pub schemas: Vec<Schema>,
+}
+
+impl MemoryCatalog {
+    pub fn new() -> MemoryCatalog {
+        MemoryCatalog::default()
+    }
+
+    pub fn from_json(json: &str) -> Result<MemoryCatalog, Error> {
+        serde_json::from_str(json).map_err(|e| Error::new("Invalid catalog", Box::new(e)))
+    }
+
+    pub fn with_default_schema(mut self, schema: Name) -> MemoryCatalog {
+        self.default_schema = Some(schema);
+        self
+    }
+
+    pub fn with_schema(mut self, schema: Schema) -> MemoryCatalog {
+        self.schemas.push(schema);
+        self
+    }
+
+    pub fn schema(&self, name: &Name) -> Option<&Schema> {
+        self.schemas.iter().find(|schema| &schema.name == name)
+    }
+}
+
+impl Catalog for MemoryCatalog {
+    fn table(&self, name: &[Name]) -> Option<(Vec<Name>, &Table)> {
+        fn find<'s>(schema: &'s Schema, name: &Name) -> Option<(Vec<Name>, &'s Table)> {
+            schema
+                .table(name)
+                .map(|table| (vec![schema.name.clone(), table.name.clone()], table))
+        }
+
+        match (name, &self.default_schema) {
+            ([schema, table], _) => self.schema(schema).and_then(|schema| find(schema, table)),
+            ([table], Some(schema)) => self.schema(schema).and_then(|schema| find(schema, table)),
+            ([table], None) => {
+                let mut found = self.schemas.iter().filter_map(|schema| find(schema, table));
+
+                match (found.next(), found.next()) {
+                    (Some(table), None) => Some(table),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+}
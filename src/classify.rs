@@ -0,0 +1,280 @@
+// MIT License
+//
+// Copyright (c) 2019 Daniele Esposti
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::fmt;
+
+use dict_derive::IntoPyObject;
+use pyo3::{IntoPy, PyObject, Python};
+
+use super::ast;
+use super::symbols::Name;
+use super::visit::{self, Visitor};
+
+/// What a statement does
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum StatementKind {
+    /// a query, reading rows or the description of a schema object
+    Read,
+
+    /// a change to the rows of a table, like `INSERT`, `UPDATE` or `DELETE`
+    Dml,
+
+    /// a change to the schema objects, like `CREATE TABLE`; the grammar has none of them yet
+    Ddl,
+
+    /// a change to the users or their privileges, like `GRANT`; the grammar has none of them yet
+    Dcl,
+
+    /// a change to the state of the session, like `ATTACH`
+    Session,
+
+    /// a statement which cannot be parsed
+    Unknown,
+}
+
+impl fmt::Display for StatementKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            StatementKind::Read => write!(f, "read"),
+            StatementKind::Dml => write!(f, "dml"),
+            StatementKind::Ddl => write!(f, "ddl"),
+            StatementKind::Dcl => write!(f, "dcl"),
+            StatementKind::Session => write!(f, "session"),
+            StatementKind::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+impl IntoPy<PyObject> for StatementKind {
+    fn into_py(self, py: Python) -> PyObject {
+        IntoPy::<PyObject>::into_py(self.to_string(), py)
+    }
+}
+
+/// The description of a statement, i.e. to route the read-only ones to replicas; the grammar has
+/// no window functions, so there's no flag for them
+#[derive(IntoPyObject, Debug, PartialEq, Eq, Clone)]
+pub struct Classification {
+    pub kind: StatementKind,
+
+    /// whether the statement doesn't write to the database
+    pub read_only: bool,
+
+    /// whether the statement changes the database or the session
+    pub side_effects: bool,
+
+    /// the tables written by the statement, or by the one explained by `EXPLAIN`, as dotted names
+    pub written_tables: Vec<String>,
+
+    /// the tables the statement reads rows or the description of, common table expressions
+    /// excluded, as dotted names
+    pub read_tables: Vec<String>,
+
+    /// whether the statement groups the rows or calls an aggregate function
+    pub uses_aggregation: bool,
+
+    /// whether the statement has queries nested in `FROM`, in an expression or in `IN`; the
+    /// queries of common table expressions are not, unless nested in one of those
+    pub uses_subquery: bool,
+
+    pub is_explain: bool,
+}
+
+/// The aggregate functions which are not part of the grammar, in upper case
+const AGGREGATES: &[&str] = &[
+    "APPROXIMATE_COUNT_DISTINCT",
+    "AVG",
+    "EVERY",
+    "GROUP_CONCAT",
+    "LISTAGG",
+    "MEDIAN",
+    "STDDEV",
+    "STDDEV_POP",
+    "STDDEV_SAMP",
+    "VARIANCE",
+    "VAR_POP",
+    "VAR_SAMP",
+];
+
+/// Describe what a statement does and which tables it works on
+pub fn classify(statement: &ast::SqlStatement) -> Classification {
+    let (kind, written_tables) = match statement {
+        ast::SqlStatement::Statement(statement)
+        | ast::SqlStatement::ExplainQueryPlan(statement) => match statement {
+            ast::Statement::Select(_) => (StatementKind::Read, Vec::new()),
+            ast::Statement::Insert(insert) => (StatementKind::Dml, vec![join(&insert.table_name)]),
+            ast::Statement::Delete(delete) => (StatementKind::Dml, vec![join(&delete.table_name)]),
+            ast::Statement::Update(update) => (StatementKind::Dml, vec![join(&update.table_name)]),
+        },
+        ast::SqlStatement::Attach(attach) => {
+            (StatementKind::Session, vec![join(&attach.qualified_name)])
+        }
+        ast::SqlStatement::Describe(_) => (StatementKind::Read, Vec::new()),
+        ast::SqlStatement::Error(_) => (StatementKind::Unknown, Vec::new()),
+    };
+
+    // EXPLAIN just describes the plan of the statement
+    let is_explain = matches!(statement, ast::SqlStatement::ExplainQueryPlan(_));
+    let read_only = is_explain || kind == StatementKind::Read;
+
+    let mut aggregation = Aggregation { found: false };
+    aggregation.visit_sql_statement(statement);
+
+    let mut subquery = Subquery { found: false };
+    subquery.visit_sql_statement(statement);
+
+    Classification {
+        kind,
+        read_only,
+        side_effects: !is_explain && kind != StatementKind::Read,
+        written_tables,
+        read_tables: read_tables(statement),
+        uses_aggregation: aggregation.found,
+        uses_subquery: subquery.found,
+        is_explain,
+    }
+}
+
+/// The tables read by a statement, in order of appearance, the common table expressions
+/// excluded
+fn read_tables(statement: &ast::SqlStatement) -> Vec<String> {
+    if let ast::SqlStatement::Describe(describe) = statement {
+        return vec![join(&describe.qualified_name)];
+    }
+
+    let mut reads = Reads {
+        ctes: Vec::new(),
+        tables: Vec::new(),
+    };
+    reads.visit_sql_statement(statement);
+    reads.tables
+}
+
+/// The tables of the `FROM` clauses; the table written by a statement is read only if it's in one
+struct Reads {
+    /// the names of the common table expressions in scope, a frame for each `WITH` clause
+    ctes: Vec<Vec<Name>>,
+
+    tables: Vec<String>,
+}
+
+impl<'ast> Visitor<'ast> for Reads {
+    fn visit_select_statement(&mut self, node: &'ast ast::SelectStatement) {
+        self.ctes.push(Vec::new());
+
+        for cte in &node.common {
+            // the queries of a recursive common table expression can reference it, the ones of
+            // the others read the table of the same name, if any
+            if node.recursive {
+                self.ctes.last_mut().unwrap().push(cte.identifier.clone());
+                self.visit_common_table_expression(cte);
+            } else {
+                self.visit_common_table_expression(cte);
+                self.ctes.last_mut().unwrap().push(cte.identifier.clone());
+            }
+        }
+
+        self.visit_set_expression(&node.expr);
+
+        for ordering in &node.order_by {
+            self.visit_ordering(ordering);
+        }
+
+        if let Some(limit) = &node.limit {
+            self.visit_limit(limit);
+        }
+
+        self.ctes.pop();
+    }
+
+    fn visit_named_table_expression(&mut self, node: &'ast ast::NamedTableExpression) {
+        if let [name] = node.name.as_slice() {
+            if self.ctes.iter().flatten().any(|cte| cte == name) {
+                return;
+            }
+        }
+
+        let table = join(&node.name);
+
+        // names are compared case-insensitively, keeping the first spelling
+        if !self
+            .tables
+            .iter()
+            .any(|read| read.to_lowercase() == table.to_lowercase())
+        {
+            self.tables.push(table);
+        }
+    }
+}
+
+struct Aggregation {
+    found: bool,
+}
+
+impl<'ast> Visitor<'ast> for Aggregation {
+    fn visit_group_by(&mut self, node: &'ast ast::GroupBy) {
+        self.found = true;
+        visit::walk_group_by(self, node)
+    }
+
+    fn visit_expression(&mut self, node: &'ast ast::Expression) {
+        match node {
+            ast::Expression::Sum(_)
+            | ast::Expression::Max(_)
+            | ast::Expression::Min(_)
+            | ast::Expression::Count(_) => self.found = true,
+            ast::Expression::Unknown(unknown) => {
+                if let [name] = unknown.name.as_slice() {
+                    self.found |= AGGREGATES.contains(&name.as_str().to_uppercase().as_str());
+                }
+            }
+            _ => {}
+        }
+
+        visit::walk_expression(self, node)
+    }
+}
+
+struct Subquery {
+    found: bool,
+}
+
+impl<'ast> Visitor<'ast> for Subquery {
+    fn visit_select_table_expression(&mut self, node: &'ast ast::SelectTableExpression) {
+        self.found = true;
+        visit::walk_select_table_expression(self, node)
+    }
+
+    fn visit_expression(&mut self, node: &'ast ast::Expression) {
+        self.found |= matches!(node, ast::Expression::Select(_));
+        visit::walk_expression(self, node)
+    }
+
+    fn visit_set_specification(&mut self, node: &'ast ast::SetSpecification) {
+        self.found |= matches!(node, ast::SetSpecification::Select(_));
+        visit::walk_set_specification(self, node)
+    }
+}
+
+fn join(names: &[Name]) -> String {
+    names.iter().map(Name::as_str).collect::<Vec<_>>().join(".")
+}
@@ -0,0 +1,379 @@
+// MIT License
+//
+// Copyright (c) 2019 Daniele Esposti
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::HashSet;
+use std::fmt;
+
+use dict_derive::IntoPyObject;
+
+use super::ast;
+use super::inspect;
+use super::symbols::Name;
+use super::visit::{self, Visitor};
+
+/// The number of joins of each type; natural joins are counted by their type too
+#[derive(IntoPyObject, Serialize, Debug, PartialEq, Eq, Clone, Default)]
+pub struct JoinCounts {
+    pub inner: usize,
+    pub left: usize,
+    pub right: usize,
+    pub full: usize,
+    pub cross: usize,
+    pub natural: usize,
+}
+
+/// Metrics of how complex a statement is
+#[derive(IntoPyObject, Serialize, Debug, PartialEq, Eq, Clone, Default)]
+pub struct Complexity {
+    /// how deep the subqueries are nested, 1 for the ones of the statement itself and 0
+    /// without subqueries; the queries of common table expressions are counted by `ctes`, so
+    /// they are at the level of the query defining them
+    pub subquery_depth: usize,
+
+    pub joins: JoinCounts,
+
+    /// the number of common table expressions, at any level
+    pub ctes: usize,
+
+    /// the number of references to common table expressions after the first one of each
+    pub cte_reuses: usize,
+
+    /// the number of queries combined by `UNION`, `INTERSECT` and `EXCEPT`
+    pub set_operation_branches: usize,
+
+    /// the number of `WHEN` and `ELSE` branches of the `CASE` expressions
+    pub case_branches: usize,
+
+    /// the number of distinct tables read or written, common table expressions excluded
+    pub tables: usize,
+
+    /// the number of distinct functions called
+    pub functions: usize,
+
+    /// the number of nodes of the AST: statements, clauses, table references and expressions
+    pub nodes: usize,
+}
+
+impl fmt::Display for Complexity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let joins = &self.joins;
+
+        writeln!(f, "subquery_depth: {}", self.subquery_depth)?;
+        writeln!(
+            f,
+            "joins: inner {}, left {}, right {}, full {}, cross {}, natural {}",
+            joins.inner, joins.left, joins.right, joins.full, joins.cross, joins.natural
+        )?;
+        writeln!(f, "ctes: {}", self.ctes)?;
+        writeln!(f, "cte_reuses: {}", self.cte_reuses)?;
+        writeln!(f, "set_operation_branches: {}", self.set_operation_branches)?;
+        writeln!(f, "case_branches: {}", self.case_branches)?;
+        writeln!(f, "tables: {}", self.tables)?;
+        writeln!(f, "functions: {}", self.functions)?;
+        write!(f, "nodes: {}", self.nodes)
+    }
+}
+
+/// Compute the complexity metrics of a statement
+pub fn complexity(statement: &ast::SqlStatement) -> Complexity {
+    let mut collector = Collector {
+        complexity: Complexity {
+            functions: inspect::functions(statement).len(),
+            ..Complexity::default()
+        },
+        depth: 0,
+        scopes: Vec::new(),
+        tables: HashSet::new(),
+    };
+
+    collector.visit_sql_statement(statement);
+    collector.complexity.tables = collector.tables.len();
+    collector.complexity
+}
+
+/// The common table expressions visible from a query, with the number of references to them
+struct Scope {
+    ctes: Vec<(Name, usize)>,
+}
+
+struct Collector {
+    complexity: Complexity,
+
+    /// the number of queries the visited node is nested in, the statement itself excluded
+    depth: usize,
+
+    scopes: Vec<Scope>,
+    tables: HashSet<String>,
+}
+
+impl Collector {
+    fn add_table(&mut self, name: &[Name]) {
+        let name: Vec<String> = name
+            .iter()
+            .map(|name| name.as_str().to_lowercase())
+            .collect();
+        self.tables.insert(name.join("."));
+    }
+
+    fn add_cte(&mut self, name: &Name) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.ctes.push((name.clone(), 0));
+        }
+    }
+
+    /// Count a reference to a common table expression, returning whether it is one
+    fn reference_cte(&mut self, name: &[Name]) -> bool {
+        let name = match name {
+            [name] => name,
+            _ => return false,
+        };
+
+        for scope in self.scopes.iter_mut().rev() {
+            if let Some((_, references)) = scope.ctes.iter_mut().find(|(cte, _)| cte == name) {
+                *references += 1;
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// Visit a query, which is nested in another one unless it's the whole statement
+    fn query(&mut self, node: &ast::SelectStatement) {
+        self.complexity.nodes += 1;
+        self.complexity.set_operation_branches += branches(&node.expr);
+        self.scopes.push(Scope { ctes: Vec::new() });
+
+        // each common table expression can reference the ones defined before it, and itself
+        // with `WITH RECURSIVE`
+        for cte in &node.common {
+            if node.recursive {
+                self.add_cte(&cte.identifier);
+            }
+
+            self.visit_common_table_expression(cte);
+
+            if !node.recursive {
+                self.add_cte(&cte.identifier);
+            }
+        }
+
+        self.visit_set_expression(&node.expr);
+
+        for ordering in &node.order_by {
+            self.visit_ordering(ordering);
+        }
+
+        if let Some(limit) = &node.limit {
+            self.visit_limit(limit);
+        }
+
+        if let Some(scope) = self.scopes.pop() {
+            for (_, references) in scope.ctes {
+                self.complexity.cte_reuses += references.saturating_sub(1);
+            }
+        }
+    }
+}
+
+/// The number of queries combined by a set operation, 0 if it isn't one
+fn branches(node: &ast::SetExpression) -> usize {
+    match node {
+        ast::SetExpression::Op(op) => branches(&op.left).max(1) + branches(&op.right).max(1),
+        _ => 0,
+    }
+}
+
+// The enums wrapping a single node, like `Expression` or `TableExpression`, are counted either
+// as the enum or as the node they wrap, but not both
+impl<'ast> Visitor<'ast> for Collector {
+    fn visit_sql_statement(&mut self, node: &'ast ast::SqlStatement) {
+        match node {
+            ast::SqlStatement::Statement(_) | ast::SqlStatement::ExplainQueryPlan(_) => {}
+            _ => self.complexity.nodes += 1,
+        }
+
+        visit::walk_sql_statement(self, node)
+    }
+
+    fn visit_statement(&mut self, node: &'ast ast::Statement) {
+        match node {
+            ast::Statement::Select(select) => self.query(select),
+            _ => visit::walk_statement(self, node),
+        }
+    }
+
+    fn visit_insert_statement(&mut self, node: &'ast ast::InsertStatement) {
+        self.complexity.nodes += 1;
+        self.complexity.set_operation_branches += branches(&node.source);
+        self.add_table(&node.table_name);
+
+        visit::walk_insert_statement(self, node)
+    }
+
+    fn visit_delete_statement(&mut self, node: &'ast ast::DeleteStatement) {
+        self.complexity.nodes += 1;
+        self.add_table(&node.table_name);
+
+        visit::walk_delete_statement(self, node)
+    }
+
+    fn visit_update_statement(&mut self, node: &'ast ast::UpdateStatement) {
+        self.complexity.nodes += 1;
+        self.add_table(&node.table_name);
+
+        visit::walk_update_statement(self, node)
+    }
+
+    fn visit_assignment(&mut self, node: &'ast ast::Assignment) {
+        self.complexity.nodes += 1;
+        visit::walk_assignment(self, node)
+    }
+
+    fn visit_select_statement(&mut self, node: &'ast ast::SelectStatement) {
+        self.depth += 1;
+        self.complexity.subquery_depth = self.complexity.subquery_depth.max(self.depth);
+
+        self.query(node);
+
+        self.depth -= 1;
+    }
+
+    fn visit_common_table_expression(&mut self, node: &'ast ast::CommonTableExpression) {
+        self.complexity.nodes += 1;
+        self.complexity.ctes += 1;
+
+        // counted by `ctes`, so its query isn't a subquery
+        self.query(&node.query)
+    }
+
+    fn visit_set_expression(&mut self, node: &'ast ast::SetExpression) {
+        self.complexity.nodes += 1;
+        visit::walk_set_expression(self, node)
+    }
+
+    fn visit_table_expression(&mut self, node: &'ast ast::TableExpression) {
+        match node {
+            ast::TableExpression::Named(named) => {
+                self.complexity.nodes += 1;
+
+                if !self.reference_cte(&named.name) {
+                    self.add_table(&named.name);
+                }
+            }
+            // counted as a query
+            ast::TableExpression::Select(_) => {}
+            _ => self.complexity.nodes += 1,
+        }
+
+        visit::walk_table_expression(self, node)
+    }
+
+    fn visit_join_table_expression(&mut self, node: &'ast ast::JoinTableExpression) {
+        let joins = &mut self.complexity.joins;
+
+        let join_type = match &node.op {
+            ast::JoinOperator::Join(join_type) => Some(join_type),
+            ast::JoinOperator::Natural(join_type) => {
+                joins.natural += 1;
+                Some(join_type)
+            }
+            ast::JoinOperator::Cross => {
+                joins.cross += 1;
+                None
+            }
+        };
+
+        match join_type {
+            Some(ast::JoinType::Inner) => joins.inner += 1,
+            Some(ast::JoinType::Left) => joins.left += 1,
+            Some(ast::JoinType::Right) => joins.right += 1,
+            Some(ast::JoinType::Full) => joins.full += 1,
+            None => {}
+        }
+
+        visit::walk_join_table_expression(self, node)
+    }
+
+    fn visit_join_constraint(&mut self, node: &'ast ast::JoinConstraint) {
+        if let ast::JoinConstraint::Columns(_) = node {
+            self.complexity.nodes += 1;
+        }
+
+        visit::walk_join_constraint(self, node)
+    }
+
+    fn visit_result_columns(&mut self, node: &'ast ast::ResultColumns) {
+        self.complexity.nodes += 1;
+        visit::walk_result_columns(self, node)
+    }
+
+    fn visit_result_column(&mut self, node: &'ast ast::ResultColumn) {
+        self.complexity.nodes += 1;
+        visit::walk_result_column(self, node)
+    }
+
+    fn visit_group_by(&mut self, node: &'ast ast::GroupBy) {
+        self.complexity.nodes += 1;
+        visit::walk_group_by(self, node)
+    }
+
+    fn visit_ordering(&mut self, node: &'ast ast::Ordering) {
+        self.complexity.nodes += 1;
+        visit::walk_ordering(self, node)
+    }
+
+    fn visit_limit(&mut self, node: &'ast ast::Limit) {
+        self.complexity.nodes += 1;
+        visit::walk_limit(self, node)
+    }
+
+    fn visit_expression(&mut self, node: &'ast ast::Expression) {
+        match node {
+            // counted as a query
+            ast::Expression::Select(_) => {}
+            ast::Expression::Case(case) => {
+                self.complexity.nodes += 1;
+                self.complexity.case_branches +=
+                    case.when_part.len() + case.else_part.iter().count();
+            }
+            _ => self.complexity.nodes += 1,
+        }
+
+        visit::walk_expression(self, node)
+    }
+
+    fn visit_set_specification(&mut self, node: &'ast ast::SetSpecification) {
+        match node {
+            // counted as a query
+            ast::SetSpecification::Select(_) => {}
+            _ => self.complexity.nodes += 1,
+        }
+
+        visit::walk_set_specification(self, node)
+    }
+
+    fn visit_when_clause(&mut self, node: &'ast ast::WhenClause) {
+        self.complexity.nodes += 1;
+        visit::walk_when_clause(self, node)
+    }
+}
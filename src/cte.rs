@@ -0,0 +1,578 @@
+// MIT License
+//
+// Copyright (c) 2019 Daniele Esposti
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use super::ast;
+use super::symbols::Name;
+use super::visit::{self, Visitor, VisitorMut};
+
+/// Replace the references to a common table expression with its query, as nested queries
+/// keeping the aliases of the references, or else aliased with the name of the common table
+/// expression, and drop it from its `WITH` clause. The query's columns take the names given
+/// after the name of the common table expression, if any.
+///
+/// The common table expression is the first one with the given name, outer `WITH` clauses
+/// first; the references below a nested `WITH` clause defining the same name are left alone.
+/// It's an error if moving the query would bind its tables to different common table
+/// expressions, or if its columns cannot be renamed.
+pub fn inline_cte(statement: &ast::SqlStatement, name: &str) -> Result<ast::SqlStatement, String> {
+    let mut inliner = Inliner {
+        name: Name::from(name),
+        cte: None,
+        tables: Vec::new(),
+        scopes: Vec::new(),
+        done: false,
+        error: None,
+    };
+    let mut statement = statement.clone();
+
+    inliner.visit_sql_statement_mut(&mut statement);
+
+    match (inliner.error, inliner.done) {
+        (Some(error), _) => Err(error),
+        (None, false) => Err(format!("no common table expression named {}", name)),
+        (None, true) => Ok(statement),
+    }
+}
+
+/// Replace the first nested query of the `FROM` clauses which appears more than once with
+/// references to a new common table expression of the outer query, named as given.
+///
+/// The new common table expression comes after the ones its query references. It's an error if
+/// the name is already used by a table or a common table expression, or if the nested query
+/// references a common table expression of a nested `WITH` clause, which it would be moved out
+/// of.
+pub fn extract_cte(statement: &ast::SqlStatement, name: &str) -> Result<ast::SqlStatement, String> {
+    let name = Name::from(name);
+    let mut statement = statement.clone();
+
+    let select = match &mut statement {
+        ast::SqlStatement::Statement(ast::Statement::Select(select)) => select,
+        _ => return Err("only queries can have common table expressions".to_string()),
+    };
+
+    let mut collector = Collector::default();
+    collector.visit_select_statement(select);
+
+    let query = match collector.repeated() {
+        Some(query) => query,
+        None => return Err("no nested query appears more than once".to_string()),
+    };
+
+    if collector.tables.contains(&name) || collector.ctes.contains(&name) {
+        return Err(format!("{} is already the name of a table", name));
+    }
+
+    let tables = table_names(&query);
+
+    // the common table expressions of the nested `WITH` clauses aren't visible from the outer one
+    if let Some(table) = collector
+        .nested_ctes
+        .iter()
+        .find(|cte| tables.contains(cte))
+    {
+        return Err(format!(
+            "the nested query references the common table expression {} of a nested query",
+            table
+        ));
+    }
+
+    let position = select
+        .common
+        .iter()
+        .rposition(|cte| tables.contains(&cte.identifier))
+        .map_or(0, |position| position + 1);
+
+    let mut extractor = Extractor {
+        name: name.clone(),
+        query: &query,
+        replaced: false,
+    };
+
+    for (index, cte) in select.common.iter_mut().enumerate() {
+        extractor.visit_common_table_expression_mut(cte);
+
+        if extractor.replaced && index < position {
+            return Err(format!(
+                "the common table expression {} would reference {} before its definition",
+                cte.identifier, name
+            ));
+        }
+    }
+
+    extractor.visit_set_expression_mut(&mut select.expr);
+
+    for ordering in &mut select.order_by {
+        extractor.visit_ordering_mut(ordering);
+    }
+
+    if let Some(limit) = &mut select.limit {
+        extractor.visit_limit_mut(limit);
+    }
+
+    select.common.insert(
+        position,
+        ast::CommonTableExpression {
+            identifier: name,
+            column_names: None,
+            query,
+        },
+    );
+
+    Ok(statement)
+}
+
+/// Whether the query of a common table expression references it, but below the nested `WITH`
+/// clauses defining the same name
+pub fn references_itself(cte: &ast::CommonTableExpression) -> bool {
+    let mut finder = SelfReference::new(&cte.identifier);
+
+    finder.visit_select_statement(&cte.query);
+    finder.found
+}
+
+/// Check that the common table expressions of a `WITH RECURSIVE` clause which reference
+/// themselves are the `UNION ALL` of the queries which don't, the anchor, followed by the
+/// queries which do
+pub(crate) fn check_recursive(ctes: &[ast::CommonTableExpression]) -> Result<(), &'static str> {
+    for cte in ctes.iter().filter(|cte| references_itself(cte)) {
+        let mut branches = Vec::new();
+        let mut expr = cte.query.expr.as_ref();
+
+        while let ast::SetExpression::Op(op) = expr {
+            if op.op != ast::SetOperator::UnionAll {
+                break;
+            }
+
+            branches.push(op.right.as_ref());
+            expr = op.left.as_ref();
+        }
+
+        branches.push(expr);
+        branches.reverse();
+
+        let recursive: Vec<bool> = branches
+            .into_iter()
+            .map(|branch| {
+                let mut finder = SelfReference::new(&cte.identifier);
+
+                finder.visit_set_expression(branch);
+                finder.found
+            })
+            .collect();
+
+        match recursive.iter().position(|recursive| *recursive) {
+            None => {
+                return Err(
+                    "a recursive common table expression must be the UNION ALL of \
+                            its anchor and of the queries referencing it",
+                )
+            }
+            Some(0) => {
+                return Err(
+                    "the first query of a recursive common table expression must not \
+                            reference it",
+                )
+            }
+            Some(first) if recursive[first..].contains(&false) => {
+                return Err(
+                    "the queries of a recursive common table expression referencing it \
+                            must follow the ones which don't",
+                )
+            }
+            Some(_) => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// The query of a common table expression with its columns renamed
+fn renamed_query(cte: &ast::CommonTableExpression) -> Result<ast::SelectStatement, String> {
+    let mut query = cte.query.clone();
+
+    let names = match &cte.column_names {
+        Some(names) => names,
+        None => return Ok(query),
+    };
+
+    // the names of the columns of a set operation are the ones of its first query
+    let mut expr = query.expr.as_mut();
+
+    while let ast::SetExpression::Op(op) = expr {
+        expr = op.left.as_mut();
+    }
+
+    let columns = match expr {
+        ast::SetExpression::Query(ast::QuerySetExpression {
+            columns: ast::ResultColumns::List(columns),
+            ..
+        }) if columns.len() == names.len() => columns,
+        _ => {
+            return Err(format!(
+                "cannot rename the columns of the common table expression {}",
+                cte.identifier
+            ))
+        }
+    };
+
+    for (column, name) in columns.iter_mut().zip(names) {
+        match column {
+            ast::ResultColumn::Expr(column) => column.rename = Some(name.clone()),
+            ast::ResultColumn::AllFrom(_) => {
+                return Err(format!(
+                    "cannot rename the columns of the common table expression {}",
+                    cte.identifier
+                ))
+            }
+        }
+    }
+
+    Ok(query)
+}
+
+/// The names of the tables, or common table expressions, a query reads from, but the common
+/// table expressions it defines
+fn table_names(query: &ast::SelectStatement) -> Vec<Name> {
+    let mut collector = Collector::default();
+
+    collector.visit_select_statement(query);
+
+    let Collector {
+        tables,
+        ctes,
+        nested_ctes,
+        ..
+    } = collector;
+
+    tables
+        .into_iter()
+        .filter(|table| !ctes.contains(table) && !nested_ctes.contains(table))
+        .collect()
+}
+
+struct Inliner {
+    name: Name,
+
+    /// the common table expression being inlined, with its columns renamed
+    cte: Option<(Name, ast::SelectStatement)>,
+
+    /// the tables read by the common table expression being inlined
+    tables: Vec<Name>,
+
+    /// the names of the common table expressions visible from the visited node, within the
+    /// scope of the one being inlined
+    scopes: Vec<Vec<Name>>,
+
+    done: bool,
+    error: Option<String>,
+}
+
+impl Inliner {
+    /// Visit a query, tracking the common table expressions it defines
+    fn scoped(&mut self, node: &mut ast::SelectStatement) {
+        self.scopes.push(Vec::new());
+
+        for cte in &mut node.common {
+            self.visit_common_table_expression_mut(cte);
+
+            if let Some(scope) = self.scopes.last_mut() {
+                scope.push(cte.identifier.clone());
+            }
+        }
+
+        self.visit_set_expression_mut(&mut node.expr);
+
+        for ordering in &mut node.order_by {
+            self.visit_ordering_mut(ordering);
+        }
+
+        if let Some(limit) = &mut node.limit {
+            self.visit_limit_mut(limit);
+        }
+
+        self.scopes.pop();
+    }
+
+    /// The query replacing a reference to the common table expression being inlined, if the
+    /// name references it
+    fn replacement(&mut self, name: &[Name]) -> Option<(Name, ast::SelectStatement)> {
+        let (identifier, query) = match (name, &self.cte) {
+            ([name], Some(cte)) if *name == self.name => cte,
+            _ => return None,
+        };
+
+        if self.scopes.iter().flatten().any(|cte| *cte == self.name) {
+            return None;
+        }
+
+        if let Some(table) = self
+            .scopes
+            .iter()
+            .flatten()
+            .find(|cte| self.tables.contains(cte))
+        {
+            self.error.get_or_insert(format!(
+                "inlining {} would bind {} to another common table expression",
+                identifier, table
+            ));
+        }
+
+        Some((identifier.clone(), query.clone()))
+    }
+}
+
+impl VisitorMut for Inliner {
+    fn visit_select_statement_mut(&mut self, node: &mut ast::SelectStatement) {
+        if self.done {
+            return self.scoped(node);
+        }
+
+        let position = match node
+            .common
+            .iter()
+            .position(|cte| cte.identifier == self.name)
+        {
+            Some(position) => position,
+            None => return visit::walk_select_statement_mut(self, node),
+        };
+
+        let cte = node.common.remove(position);
+
+        if node.recursive && references_itself(&cte) {
+            self.error = Some(format!(
+                "cannot inline the recursive common table expression {}",
+                cte.identifier
+            ));
+        }
+
+        match renamed_query(&cte) {
+            Ok(query) => {
+                self.tables = table_names(&query);
+                self.cte = Some((cte.identifier, query));
+            }
+            Err(error) => {
+                self.error.get_or_insert(error);
+            }
+        }
+
+        self.done = true;
+
+        if self.error.is_some() {
+            return;
+        }
+
+        // the common table expressions defined before can't reference the inlined one, and
+        // don't shadow the tables of its query
+        let mut common: Vec<_> = node.common.drain(..position).collect();
+        self.scoped(node);
+
+        common.append(&mut node.common);
+        node.recursive &= !common.is_empty();
+        node.common = common;
+
+        self.cte = None;
+    }
+
+    fn visit_table_expression_mut(&mut self, node: &mut ast::TableExpression) {
+        if let ast::TableExpression::Named(named) = node {
+            if let Some((identifier, select)) = self.replacement(&named.name) {
+                *node = ast::TableExpression::Select(ast::SelectTableExpression {
+                    select,
+                    alias: named.alias.clone().or(Some(identifier)),
+                });
+                return;
+            }
+        }
+
+        visit::walk_table_expression_mut(self, node)
+    }
+
+    fn visit_set_specification_mut(&mut self, node: &mut ast::SetSpecification) {
+        if let ast::SetSpecification::Name(name) = node {
+            if let Some((_, select)) = self.replacement(name) {
+                *node = ast::SetSpecification::Select(select);
+                return;
+            }
+        }
+
+        visit::walk_set_specification_mut(self, node)
+    }
+}
+
+/// The nested queries of the `FROM` clauses, the tables and the common table expressions of
+/// a query
+#[derive(Default)]
+struct Collector {
+    queries: Vec<(ast::SelectStatement, usize)>,
+    tables: Vec<Name>,
+
+    /// the common table expressions of the query itself
+    ctes: Vec<Name>,
+
+    /// the common table expressions of the nested queries
+    nested_ctes: Vec<Name>,
+
+    /// the number of queries the visited node is nested in
+    depth: usize,
+}
+
+impl Collector {
+    /// The first nested query which appears more than once
+    fn repeated(&self) -> Option<ast::SelectStatement> {
+        self.queries
+            .iter()
+            .find(|(_, count)| *count > 1)
+            .map(|(query, _)| query.clone())
+    }
+}
+
+impl<'ast> Visitor<'ast> for Collector {
+    fn visit_select_statement(&mut self, node: &'ast ast::SelectStatement) {
+        let ctes = node.common.iter().map(|cte| cte.identifier.clone());
+
+        if self.depth == 0 {
+            self.ctes.extend(ctes);
+        } else {
+            self.nested_ctes.extend(ctes);
+        }
+
+        self.depth += 1;
+        visit::walk_select_statement(self, node);
+        self.depth -= 1;
+    }
+
+    fn visit_named_table_expression(&mut self, node: &'ast ast::NamedTableExpression) {
+        if let [name] = node.name.as_slice() {
+            self.tables.push(name.clone());
+        }
+    }
+
+    fn visit_select_table_expression(&mut self, node: &'ast ast::SelectTableExpression) {
+        match self
+            .queries
+            .iter_mut()
+            .find(|(query, _)| *query == node.select)
+        {
+            Some((_, count)) => *count += 1,
+            None => self.queries.push((node.select.clone(), 1)),
+        }
+
+        visit::walk_select_table_expression(self, node)
+    }
+
+    fn visit_set_specification(&mut self, node: &'ast ast::SetSpecification) {
+        if let ast::SetSpecification::Name(name) = node {
+            if let [name] = name.as_slice() {
+                self.tables.push(name.clone());
+            }
+        }
+
+        visit::walk_set_specification(self, node)
+    }
+}
+
+/// Finds the references to a common table expression
+struct SelfReference<'n> {
+    name: &'n Name,
+
+    /// the number of nested `WITH` clauses defining the same name the visited node is below
+    shadowed: usize,
+
+    found: bool,
+}
+
+impl<'n> SelfReference<'n> {
+    fn new(name: &'n Name) -> Self {
+        SelfReference {
+            name,
+            shadowed: 0,
+            found: false,
+        }
+    }
+
+    fn reference(&mut self, name: &[Name]) {
+        if let [name] = name {
+            self.found |= self.shadowed == 0 && name == self.name;
+        }
+    }
+}
+
+impl<'ast, 'n> Visitor<'ast> for SelfReference<'n> {
+    fn visit_select_statement(&mut self, node: &'ast ast::SelectStatement) {
+        let shadowed = self.shadowed;
+
+        for cte in &node.common {
+            self.visit_common_table_expression(cte);
+
+            if cte.identifier == *self.name {
+                self.shadowed += 1;
+            }
+        }
+
+        self.visit_set_expression(&node.expr);
+
+        for ordering in &node.order_by {
+            self.visit_ordering(ordering);
+        }
+
+        if let Some(limit) = &node.limit {
+            self.visit_limit(limit);
+        }
+
+        self.shadowed = shadowed;
+    }
+
+    fn visit_named_table_expression(&mut self, node: &'ast ast::NamedTableExpression) {
+        self.reference(&node.name);
+    }
+
+    fn visit_set_specification(&mut self, node: &'ast ast::SetSpecification) {
+        if let ast::SetSpecification::Name(name) = node {
+            self.reference(name);
+        }
+
+        visit::walk_set_specification(self, node)
+    }
+}
+
+struct Extractor<'q> {
+    name: Name,
+    query: &'q ast::SelectStatement,
+    replaced: bool,
+}
+
+impl<'q> VisitorMut for Extractor<'q> {
+    fn visit_table_expression_mut(&mut self, node: &mut ast::TableExpression) {
+        if let ast::TableExpression::Select(select) = node {
+            if select.select == *self.query {
+                *node = ast::TableExpression::Named(ast::NamedTableExpression {
+                    name: vec![self.name.clone()],
+                    alias: select.alias.clone(),
+                });
+                self.replaced = true;
+                return;
+            }
+        }
+
+        visit::walk_table_expression_mut(self, node)
+    }
+}
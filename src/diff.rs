@@ -0,0 +1,491 @@
+// MIT License
+//
+// Copyright (c) 2019 Daniele Esposti
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::fmt;
+
+use dict_derive::IntoPyObject;
+use pyo3::{IntoPy, PyObject, Python};
+
+use super::ast::*;
+use super::symbols::Name;
+
+/// The kinds of differences between two statements
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum EditKind {
+    /// the node is only in the second statement
+    Added,
+
+    /// the node is only in the first statement
+    Removed,
+
+    /// the node differs between the statements
+    Changed,
+}
+
+impl fmt::Display for EditKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            EditKind::Added => write!(f, "added"),
+            EditKind::Removed => write!(f, "removed"),
+            EditKind::Changed => write!(f, "changed"),
+        }
+    }
+}
+
+impl IntoPy<PyObject> for EditKind {
+    fn into_py(self, py: Python) -> PyObject {
+        IntoPy::<PyObject>::into_py(self.to_string(), py)
+    }
+}
+
+/// A difference between two statements
+#[derive(IntoPyObject, Debug, PartialEq, Eq, Clone)]
+pub struct Edit {
+    pub kind: EditKind,
+
+    /// the path of the node from the statement, like `expr.from[1].constraint`; the index of
+    /// a removed node refers to the first statement, otherwise to the second one
+    pub path: String,
+
+    /// the node in the first statement, as SQL
+    pub old: Option<String>,
+
+    /// the node in the second statement, as SQL
+    pub new: Option<String>,
+}
+
+impl fmt::Display for Edit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match (&self.old, &self.new) {
+            (Some(old), Some(new)) => write!(f, "{} {}: {} -> {}", self.kind, self.path, old, new),
+            (Some(node), None) | (None, Some(node)) => {
+                write!(f, "{} {}: {}", self.kind, self.path, node)
+            }
+            (None, None) => write!(f, "{} {}", self.kind, self.path),
+        }
+    }
+}
+
+/// Compare two statements node by node, returning the differences in tree order. Names are
+/// compared case-insensitively, so aliases differing only in case are not reported.
+pub fn diff(a: &SqlStatement, b: &SqlStatement) -> Vec<Edit> {
+    let mut edits = Vec::new();
+    a.diff(b, "", &mut edits);
+    edits
+}
+
+fn child(path: &str, field: &str) -> String {
+    match path.is_empty() {
+        true => field.to_string(),
+        false => format!("{}.{}", path, field),
+    }
+}
+
+fn added<T: Render>(path: String, node: &T) -> Edit {
+    Edit {
+        kind: EditKind::Added,
+        path,
+        old: None,
+        new: Some(node.render()),
+    }
+}
+
+fn removed<T: Render>(path: String, node: &T) -> Edit {
+    Edit {
+        kind: EditKind::Removed,
+        path,
+        old: Some(node.render()),
+        new: None,
+    }
+}
+
+fn changed<T: Render>(path: &str, old: &T, new: &T) -> Edit {
+    Edit {
+        kind: EditKind::Changed,
+        path: path.to_string(),
+        old: Some(old.render()),
+        new: Some(new.render()),
+    }
+}
+
+/// The SQL text of a node, as reported in the edits
+trait Render {
+    fn render(&self) -> String;
+}
+
+macro_rules! render_display {
+    ($($type:ty),*) => {
+        $(
+            impl Render for $type {
+                fn render(&self) -> String {
+                    self.to_string()
+                }
+            }
+        )*
+    };
+}
+
+render_display!(
+    bool,
+    Name,
+    String,
+    SqlStatement,
+    Statement,
+    CommonTableExpression,
+    SetExpression,
+    TableExpression,
+    JoinOperator,
+    JoinConstraint,
+    ResultColumns,
+    ResultColumn,
+    GroupBy,
+    SetOperator,
+    UnaryOperator,
+    BinaryOperator,
+    ComparisonOperator,
+    Expression,
+    SetSpecification,
+    WhenClause,
+    Literal,
+    Parameter,
+    ErrorNode,
+    OrderingDirection,
+    Ordering,
+    Limit,
+    DataType,
+    Assignment
+);
+
+impl Render for SelectMode {
+    fn render(&self) -> String {
+        match self {
+            SelectMode::All => "ALL".to_string(),
+            SelectMode::Distinct => "DISTINCT".to_string(),
+        }
+    }
+}
+
+impl<T: Render> Render for Box<T> {
+    fn render(&self) -> String {
+        self.as_ref().render()
+    }
+}
+
+impl<T: Render> Render for Vec<T> {
+    fn render(&self) -> String {
+        let items = self.iter().map(Render::render).collect::<Vec<_>>();
+        format!("({})", items.join(", "))
+    }
+}
+
+/// Structural comparison of two nodes of the same type
+trait Diff {
+    fn diff(&self, other: &Self, path: &str, edits: &mut Vec<Edit>);
+}
+
+/// Nodes compared as a whole
+macro_rules! diff_leaf {
+    ($($type:ty),*) => {
+        $(
+            impl Diff for $type {
+                fn diff(&self, other: &Self, path: &str, edits: &mut Vec<Edit>) {
+                    if self != other {
+                        edits.push(changed(path, self, other));
+                    }
+                }
+            }
+        )*
+    };
+}
+
+diff_leaf!(
+    bool,
+    Name,
+    String,
+    SelectMode,
+    JoinOperator,
+    SetOperator,
+    UnaryOperator,
+    BinaryOperator,
+    ComparisonOperator,
+    Literal,
+    Parameter,
+    ErrorNode,
+    OrderingDirection,
+    DataType
+);
+
+/// Structs, compared field by field
+macro_rules! diff_struct {
+    ($type:ident { $($field:ident),* }) => {
+        impl Diff for $type {
+            fn diff(&self, other: &Self, path: &str, edits: &mut Vec<Edit>) {
+                $(self.$field.diff(&other.$field, &child(path, stringify!($field)), edits);)*
+            }
+        }
+    };
+}
+
+diff_struct!(InsertStatement {
+    table_name,
+    columns,
+    source
+});
+diff_struct!(CommonTableExpression {
+    identifier,
+    column_names,
+    query
+});
+diff_struct!(SelectStatement {
+    common,
+    recursive,
+    expr,
+    order_by,
+    limit
+});
+diff_struct!(DeleteStatement {
+    table_name,
+    where_expr
+});
+diff_struct!(UpdateStatement {
+    table_name,
+    assignments,
+    where_expr
+});
+diff_struct!(AttachStatement {
+    qualified_name,
+    path
+});
+diff_struct!(DescribeStatement { qualified_name });
+diff_struct!(Assignment { columns, expr });
+diff_struct!(ValuesSetExpression { values });
+diff_struct!(QuerySetExpression {
+    mode,
+    columns,
+    from,
+    where_expr,
+    group_by
+});
+diff_struct!(OpSetExpression { op, left, right });
+diff_struct!(NamedTableExpression { name, alias });
+diff_struct!(SelectTableExpression { select, alias });
+diff_struct!(JoinTableExpression {
+    left,
+    right,
+    op,
+    constraint
+});
+diff_struct!(ColumnsJoinConstraint { columns });
+diff_struct!(ExprResultColumn { expr, rename });
+diff_struct!(GroupBy { groupings, having });
+diff_struct!(QualifiedIdentifierExpression { identifiers });
+diff_struct!(MakeTupleExpression { exprs });
+diff_struct!(UnaryExpression { op, expr });
+diff_struct!(BinaryExpression { op, left, right });
+diff_struct!(ComparisonExpression { op, left, right });
+diff_struct!(InExpression { expr, set });
+diff_struct!(BetweenExpression { expr, lower, upper });
+diff_struct!(CaseExpression {
+    expr,
+    when_part,
+    else_part
+});
+diff_struct!(CoalesceExpression { exprs });
+diff_struct!(ReplaceExpression {
+    string,
+    search_string,
+    replace_string
+});
+diff_struct!(SubstringExpression {
+    string,
+    position,
+    length
+});
+diff_struct!(ToDateExpression { string, format });
+diff_struct!(PowerExpression { base, exponent });
+diff_struct!(ConcatExpression { exprs });
+diff_struct!(MaxExpression { mode, expr });
+diff_struct!(MinExpression { mode, expr });
+diff_struct!(SumExpression { mode, expr });
+diff_struct!(CastExpression { expr, data_type });
+diff_struct!(RightExpression { string, length });
+diff_struct!(CountExpression { columns, mode });
+diff_struct!(UnknownExpression { name, exprs });
+diff_struct!(WhenClause { guard, body });
+diff_struct!(Ordering {
+    expr,
+    collation,
+    direction
+});
+diff_struct!(Limit {
+    number_rows,
+    offset_value
+});
+
+/// Enums, whose variants are compared when they match and reported as changed otherwise
+macro_rules! diff_enum {
+    ($type:ident { $($variant:ident),* } $(, unit { $($unit:ident),* })?) => {
+        impl Diff for $type {
+            fn diff(&self, other: &Self, path: &str, edits: &mut Vec<Edit>) {
+                match (self, other) {
+                    $(($type::$variant(left), $type::$variant(right)) => {
+                        left.diff(right, path, edits)
+                    })*
+                    $($(($type::$unit, $type::$unit) => (),)*)?
+                    _ => edits.push(changed(path, self, other)),
+                }
+            }
+        }
+    };
+}
+
+diff_enum!(SqlStatement {
+    Statement,
+    ExplainQueryPlan,
+    Attach,
+    Describe,
+    Error
+});
+diff_enum!(Statement {
+    Select,
+    Insert,
+    Delete,
+    Update
+});
+diff_enum!(SetExpression { Values, Query, Op });
+diff_enum!(TableExpression {
+    Named,
+    Select,
+    Join,
+    Error
+});
+diff_enum!(JoinConstraint { Expr, Columns });
+diff_enum!(ResultColumns { List }, unit { All });
+diff_enum!(ResultColumn { AllFrom, Expr });
+diff_enum!(SetSpecification { Select, List, Name });
+diff_enum!(Expression {
+    Literal,
+    QualifiedIdentifier,
+    MakeTuple,
+    Select,
+    Unary,
+    Binary,
+    Comparison,
+    In,
+    Between,
+    Case,
+    Coalesce,
+    Replace,
+    Substring,
+    ToDate,
+    Power,
+    Concat,
+    Sum,
+    Max,
+    Min,
+    Cast,
+    Right,
+    Count,
+    Unknown,
+    Parameter,
+    Error
+});
+
+impl<T: Diff> Diff for Box<T> {
+    fn diff(&self, other: &Self, path: &str, edits: &mut Vec<Edit>) {
+        self.as_ref().diff(other.as_ref(), path, edits)
+    }
+}
+
+impl<T: Diff + Render> Diff for Option<T> {
+    fn diff(&self, other: &Self, path: &str, edits: &mut Vec<Edit>) {
+        match (self, other) {
+            (Some(left), Some(right)) => left.diff(right, path, edits),
+            (Some(left), None) => edits.push(removed(path.to_string(), left)),
+            (None, Some(right)) => edits.push(added(path.to_string(), right)),
+            (None, None) => (),
+        }
+    }
+}
+
+/// Lists are aligned on their longest common subsequence, so that inserting or removing an
+/// item doesn't report the following ones as changed. The items left out of the alignment
+/// between two aligned ones are compared pairwise, the remaining ones are added or removed.
+impl<T: Diff + Render + PartialEq> Diff for Vec<T> {
+    fn diff(&self, other: &Self, path: &str, edits: &mut Vec<Edit>) {
+        let (n, m) = (self.len(), other.len());
+
+        // lengths[i][j] is the length of the common subsequence of self[i..] and other[j..]
+        let mut lengths = vec![vec![0; m + 1]; n + 1];
+
+        for i in (0..n).rev() {
+            for j in (0..m).rev() {
+                lengths[i][j] = match self[i] == other[j] {
+                    true => lengths[i + 1][j + 1] + 1,
+                    false => lengths[i + 1][j].max(lengths[i][j + 1]),
+                };
+            }
+        }
+
+        let item = |i: usize| format!("{}[{}]", path, i);
+        let (mut i, mut j) = (0, 0);
+
+        while i < n || j < m {
+            // collect the items up to the next aligned pair
+            let (start_i, start_j) = (i, j);
+
+            while i < n && j < m && self[i] != other[j] {
+                if lengths[i + 1][j] >= lengths[i][j + 1] {
+                    i += 1;
+                } else {
+                    j += 1;
+                }
+            }
+
+            if i == n || j == m {
+                i = n;
+                j = m;
+            }
+
+            let (old, new) = (start_i..i, start_j..j);
+            let paired = old.len().min(new.len());
+
+            for k in 0..paired {
+                self[start_i + k].diff(&other[start_j + k], &item(start_j + k), edits);
+            }
+
+            for (k, node) in self.iter().enumerate().take(i).skip(start_i + paired) {
+                edits.push(removed(item(k), node));
+            }
+
+            for (k, node) in other.iter().enumerate().take(j).skip(start_j + paired) {
+                edits.push(added(item(k), node));
+            }
+
+            // skip the aligned pair
+            if i < n && j < m {
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+}
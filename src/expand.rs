@@ -0,0 +1,93 @@
+// MIT License
+//
+// Copyright (c) 2019 Daniele Esposti
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::HashMap;
+
+use super::ast;
+use super::binder::{BindError, State};
+use super::catalog::Catalog;
+use super::visit::{self, VisitorMut};
+
+/// Replace `*` and `t.*` in the select lists of a statement with the columns they stand for,
+/// in table order. The columns merged by `JOIN ... USING` and `NATURAL JOIN` appear once,
+/// unqualified and before the other columns.
+///
+/// Stars over tables missing from the catalog, or over nested queries with unnamed columns,
+/// are left in place. Returns the errors found while binding the names of the statement.
+pub fn expand_stars(catalog: &dyn Catalog, statement: &mut ast::SqlStatement) -> Vec<BindError> {
+    let mut state = State::new(catalog);
+    state.sql_statement(statement);
+
+    let mut expander = StarExpander {
+        expansions: state.expansions,
+        position: 0,
+    };
+    expander.visit_sql_statement_mut(statement);
+
+    state.binding.errors
+}
+
+struct StarExpander {
+    expansions: HashMap<usize, Vec<(usize, Vec<ast::ResultColumn>)>>,
+
+    /// the position of the next query, counted like the binder does
+    position: usize,
+}
+
+impl VisitorMut for StarExpander {
+    fn visit_query_set_expression_mut(&mut self, node: &mut ast::QuerySetExpression) {
+        let position = self.position;
+        self.position += 1;
+
+        visit::walk_query_set_expression_mut(self, node);
+
+        let expansions = match self.expansions.remove(&position) {
+            Some(expansions) => expansions,
+            None => return,
+        };
+
+        let expanded = match std::mem::replace(&mut node.columns, ast::ResultColumns::All) {
+            // `*` is the only item of its select list
+            ast::ResultColumns::All => expansions
+                .into_iter()
+                .flat_map(|(_, columns)| columns)
+                .collect(),
+            ast::ResultColumns::List(columns) => {
+                let mut expansions = expansions.into_iter().peekable();
+                let mut expanded = Vec::new();
+
+                for (position, column) in columns.into_iter().enumerate() {
+                    match expansions.peek() {
+                        Some((star, _)) if *star == position => {
+                            expanded.extend(expansions.next().unwrap().1)
+                        }
+                        _ => expanded.push(column),
+                    }
+                }
+
+                expanded
+            }
+        };
+
+        node.columns = ast::ResultColumns::List(expanded);
+    }
+}
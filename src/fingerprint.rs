@@ -0,0 +1,155 @@
+// MIT License
+//
+// Copyright (c) 2019 Daniele Esposti
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::fmt;
+
+use super::ast;
+use super::visit::{self, VisitorMut};
+
+/// The fingerprint of a query: queries differing only in their literal values, in the length
+/// of their `IN` lists or in the order of their `AND`/`OR` operands share the same fingerprint
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Fingerprint {
+    /// a stable hash of the normalised query
+    pub hash: u64,
+
+    /// the normalised query
+    pub sql: String,
+}
+
+impl fmt::Display for Fingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "{:016x}", self.hash)
+    }
+}
+
+/// Normalise a statement in place: literals are replaced by placeholders, `IN` lists are
+/// reduced to their distinct items and the operands of `AND`/`OR` chains are sorted.
+pub fn normalize(statement: &mut ast::SqlStatement) {
+    Normalizer.visit_sql_statement_mut(statement);
+}
+
+/// Compute the fingerprint of a statement
+pub fn fingerprint(statement: &ast::SqlStatement) -> Fingerprint {
+    let mut normalized = statement.clone();
+    normalize(&mut normalized);
+
+    // the hash is computed on the printed query, which is stable across releases and platforms
+    // unlike the hashes of the standard library
+    let sql = normalized.to_string();
+
+    Fingerprint {
+        hash: fnv1a(sql.as_bytes()),
+        sql,
+    }
+}
+
+/// The 64-bit FNV-1a hash
+fn fnv1a(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    bytes.iter().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(PRIME)
+    })
+}
+
+struct Normalizer;
+
+impl Normalizer {
+    /// Collect the operands of a chain of the same logical operator
+    fn flatten(
+        expr: ast::Expression,
+        op: &ast::BinaryOperator,
+        operands: &mut Vec<ast::Expression>,
+    ) {
+        match expr {
+            ast::Expression::Binary(binary) if &binary.op == op => {
+                Normalizer::flatten(*binary.left, op, operands);
+                Normalizer::flatten(*binary.right, op, operands);
+            }
+            expr => operands.push(expr),
+        }
+    }
+}
+
+/// Sort expressions by their printed form and remove the duplicates if asked to
+fn sort_expressions(exprs: Vec<ast::Expression>, dedup: bool) -> Vec<ast::Expression> {
+    let mut keyed = exprs
+        .into_iter()
+        .map(|expr| (expr.to_string(), expr))
+        .collect::<Vec<_>>();
+
+    keyed.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    if dedup {
+        keyed.dedup_by(|(a, _), (b, _)| a == b);
+    }
+
+    keyed.into_iter().map(|(_, expr)| expr).collect()
+}
+
+impl VisitorMut for Normalizer {
+    fn visit_literal_mut(&mut self, node: &mut ast::Literal) {
+        *node = ast::Literal::Placeholder;
+    }
+
+    fn visit_expression_mut(&mut self, node: &mut ast::Expression) {
+        visit::walk_expression_mut(self, node);
+
+        let op = match node {
+            ast::Expression::Binary(binary) => match binary.op {
+                ast::BinaryOperator::And | ast::BinaryOperator::Or => binary.op.clone(),
+                _ => return,
+            },
+            _ => return,
+        };
+
+        // the operands have been normalised already, so their printed forms are canonical
+        let expr = std::mem::replace(node, ast::Expression::Literal(ast::Literal::Placeholder));
+        let mut operands = Vec::new();
+        Normalizer::flatten(expr, &op, &mut operands);
+
+        *node = sort_expressions(operands, false)
+            .into_iter()
+            .fold(None, |left, right| {
+                Some(match left {
+                    Some(left) => ast::Expression::Binary(ast::BinaryExpression {
+                        op: op.clone(),
+                        left: Box::new(left),
+                        right: Box::new(right),
+                    }),
+                    None => right,
+                })
+            })
+            .unwrap();
+    }
+
+    fn visit_set_specification_mut(&mut self, node: &mut ast::SetSpecification) {
+        visit::walk_set_specification_mut(self, node);
+
+        if let ast::SetSpecification::List(exprs) = node {
+            let list = std::mem::take(exprs);
+            *exprs = sort_expressions(list, true);
+        }
+    }
+}
@@ -0,0 +1,975 @@
+// MIT License
+//
+// Copyright (c) 2019 Daniele Esposti
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::HashSet;
+use std::fmt::Display;
+
+use super::ast;
+use super::error::Error;
+use super::recovery::parse_strict;
+use super::symbols::{Name, KEYWORDS};
+use super::visit::{self, Visitor};
+use super::{split_statements, strip_comments};
+
+/// The case of the keywords
+#[derive(Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum KeywordCase {
+    Upper,
+    Lower,
+}
+
+/// Where the commas go when a list is broken over many lines
+#[derive(Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum CommaStyle {
+    /// at the end of each item but the last one
+    Trailing,
+
+    /// at the start of each item but the first one
+    Leading,
+}
+
+/// The style of the formatted SQL
+#[derive(Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct FormatOptions {
+    pub keyword_case: KeywordCase,
+
+    /// the number of spaces of an indentation level
+    pub indent_width: usize,
+
+    pub comma_style: CommaStyle,
+
+    /// the width lines are kept within, where they can be broken
+    pub max_width: usize,
+
+    /// indent the joins under the first table of the FROM clause instead of aligning them to it
+    pub indent_joins: bool,
+
+    /// break the CASE expressions of the select list over many lines even when they fit on one
+    pub expand_case: bool,
+}
+
+impl Default for FormatOptions {
+    fn default() -> FormatOptions {
+        FormatOptions {
+            keyword_case: KeywordCase::Upper,
+            indent_width: 4,
+            comma_style: CommaStyle::Trailing,
+            max_width: 80,
+            indent_joins: false,
+            expand_case: false,
+        }
+    }
+}
+
+impl FormatOptions {
+    /// Read the options from a TOML document; the missing ones keep their default value
+    pub fn from_toml(toml: &str) -> Result<FormatOptions, Error> {
+        toml::from_str(toml).map_err(|e| {
+            let message = format!("Invalid format options: {}", e);
+            Error::new(&message, Box::new(e))
+        })
+    }
+}
+
+/// Lay out a statement: every clause starts on a new line and lists, conditions and CASE
+/// expressions too long to fit on a line are broken over many lines
+pub fn format(statement: &ast::SqlStatement, options: &FormatOptions) -> String {
+    let lines = Layout { options }.sql_statement(statement);
+
+    let sql = lines
+        .iter()
+        .map(|line| format!("{}{}", " ".repeat(line.indent), line.text))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    apply_keyword_case(&sql, options.keyword_case)
+}
+
+/// Format a script made of statements separated by `;`, keeping its comments; formatting the
+/// result again doesn't change it
+pub fn format_sql(sql_str: &str, options: &FormatOptions) -> Result<String, String> {
+    let mut statements = Vec::new();
+
+    for (_, statement) in split_statements(sql_str) {
+        let statement = parse_strict(statement)?;
+
+        statements.push(format(&statement, options));
+    }
+
+    let mut formatted = statements.join(";\n\n");
+
+    if strip_comments(sql_str).trim_end().ends_with(';') {
+        formatted.push(';');
+    }
+
+    Ok(restore_comments(sql_str, &formatted))
+}
+
+/// A line of the layout
+struct Line {
+    indent: usize,
+    text: String,
+}
+
+impl Line {
+    fn new<S: Into<String>>(indent: usize, text: S) -> Line {
+        Line {
+            indent,
+            text: text.into(),
+        }
+    }
+}
+
+struct Layout<'o> {
+    options: &'o FormatOptions,
+}
+
+impl<'o> Layout<'o> {
+    fn fits(&self, indent: usize, text: &str) -> bool {
+        indent + text.chars().count() <= self.options.max_width
+    }
+
+    fn nested(&self, indent: usize) -> usize {
+        indent + self.options.indent_width
+    }
+
+    fn single<T: Display>(&self, node: &T, indent: usize) -> Vec<Line> {
+        vec![Line::new(indent, node.to_string())]
+    }
+
+    fn sql_statement(&self, node: &ast::SqlStatement) -> Vec<Line> {
+        match node {
+            ast::SqlStatement::Statement(statement) => self.statement(statement, 0),
+            ast::SqlStatement::ExplainQueryPlan(statement) => {
+                let mut lines = vec![Line::new(0, "EXPLAIN QUERY PLAN")];
+                lines.extend(self.statement(statement, 0));
+                lines
+            }
+            ast::SqlStatement::Attach(_)
+            | ast::SqlStatement::Describe(_)
+            | ast::SqlStatement::Error(_) => self.single(node, 0),
+        }
+    }
+
+    fn statement(&self, node: &ast::Statement, indent: usize) -> Vec<Line> {
+        match node {
+            ast::Statement::Select(select) => self.select_statement(select, indent),
+            ast::Statement::Insert(insert) => {
+                let mut header = format!("INSERT INTO {}", join(&insert.table_name, "."));
+
+                if let Some(columns) = &insert.columns {
+                    header.push_str(&format!(" ({})", join(columns, ", ")));
+                }
+
+                let mut lines = vec![Line::new(indent, header)];
+                lines.extend(self.set_expression(&insert.source, indent));
+                lines
+            }
+            ast::Statement::Delete(delete) => {
+                let header = format!("DELETE FROM {}", join(&delete.table_name, "."));
+                let mut lines = vec![Line::new(indent, header)];
+
+                if let Some(expr) = &delete.where_expr {
+                    lines.extend(self.condition("WHERE", expr, indent));
+                }
+
+                lines
+            }
+            ast::Statement::Update(update) => {
+                let header = format!("UPDATE {}", join(&update.table_name, "."));
+                let assignments = update
+                    .assignments
+                    .iter()
+                    .map(|assignment| self.single(assignment, self.nested(indent)))
+                    .collect();
+
+                let mut lines = vec![Line::new(indent, header)];
+                lines.extend(self.list("SET", assignments, indent));
+
+                if let Some(expr) = &update.where_expr {
+                    lines.extend(self.condition("WHERE", expr, indent));
+                }
+
+                lines
+            }
+        }
+    }
+
+    fn select_statement(&self, node: &ast::SelectStatement, indent: usize) -> Vec<Line> {
+        let mut lines = Vec::new();
+
+        if !node.common.is_empty() {
+            let ctes = node
+                .common
+                .iter()
+                .map(|cte| self.common_table_expression(cte, indent))
+                .collect();
+
+            let mut ctes = self.items(ctes);
+            let with = if node.recursive {
+                "WITH RECURSIVE "
+            } else {
+                "WITH "
+            };
+            ctes[0].text.insert_str(0, with);
+            lines.extend(ctes);
+        }
+
+        lines.extend(self.set_expression(&node.expr, indent));
+
+        if !node.order_by.is_empty() {
+            let orderings = node
+                .order_by
+                .iter()
+                .map(|ordering| self.single(ordering, self.nested(indent)))
+                .collect();
+
+            lines.extend(self.list("ORDER BY", orderings, indent));
+        }
+
+        if let Some(limit) = &node.limit {
+            lines.extend(self.single(limit, indent));
+        }
+
+        lines
+    }
+
+    fn common_table_expression(
+        &self,
+        node: &ast::CommonTableExpression,
+        indent: usize,
+    ) -> Vec<Line> {
+        let mut header = node.identifier.to_string();
+
+        if let Some(columns) = &node.column_names {
+            header.push_str(&format!(" ({})", join(columns, ", ")));
+        }
+
+        header.push_str(" AS ");
+
+        let query = self.select_statement(&node.query, self.nested(indent));
+        self.parenthesized(header, query, String::new(), indent)
+    }
+
+    fn set_expression(&self, node: &ast::SetExpression, indent: usize) -> Vec<Line> {
+        match node {
+            ast::SetExpression::Values(values) => {
+                let rows = values
+                    .values
+                    .iter()
+                    .map(|row| {
+                        vec![Line::new(
+                            self.nested(indent),
+                            format!("({})", join(row, ", ")),
+                        )]
+                    })
+                    .collect();
+
+                self.list("VALUES", rows, indent)
+            }
+            ast::SetExpression::Query(query) => self.query_set_expression(query, indent),
+            ast::SetExpression::Op(op) => {
+                let mut lines = self.set_expression(&op.left, indent);
+                lines.extend(self.single(&op.op, indent));
+                lines.extend(self.set_expression(&op.right, indent));
+                lines
+            }
+        }
+    }
+
+    fn query_set_expression(&self, node: &ast::QuerySetExpression, indent: usize) -> Vec<Line> {
+        let nested = self.nested(indent);
+        let keyword = match node.mode {
+            ast::SelectMode::All => "SELECT",
+            ast::SelectMode::Distinct => "SELECT DISTINCT",
+        };
+
+        let columns = match &node.columns {
+            ast::ResultColumns::All => vec![vec![Line::new(nested, "*")]],
+            ast::ResultColumns::List(columns) => columns
+                .iter()
+                .map(|column| self.result_column(column, nested))
+                .collect(),
+        };
+
+        let mut lines = self.list(keyword, columns, indent);
+
+        if !node.from.is_empty() {
+            lines.extend(self.from(&node.from, indent));
+        }
+
+        if let Some(expr) = &node.where_expr {
+            lines.extend(self.condition("WHERE", expr, indent));
+        }
+
+        if let Some(group_by) = &node.group_by {
+            let groupings = group_by
+                .groupings
+                .iter()
+                .map(|expr| self.single(expr, nested))
+                .collect();
+
+            lines.extend(self.list("GROUP BY", groupings, indent));
+
+            if let Some(having) = &group_by.having {
+                lines.extend(self.condition("HAVING", having, indent));
+            }
+        }
+
+        lines
+    }
+
+    fn result_column(&self, node: &ast::ResultColumn, indent: usize) -> Vec<Line> {
+        if let ast::ResultColumn::Expr(column) = node {
+            if let ast::Expression::Case(case) = &column.expr {
+                if self.options.expand_case || !self.fits(indent, &node.to_string()) {
+                    let alias = match &column.rename {
+                        Some(rename) => format!(" AS {}", rename),
+                        None => String::new(),
+                    };
+
+                    return self.case(case, alias, indent);
+                }
+            }
+        }
+
+        self.single(node, indent)
+    }
+
+    /// A CASE expression with each branch on its own line
+    fn case(&self, node: &ast::CaseExpression, suffix: String, indent: usize) -> Vec<Line> {
+        let header = match &node.expr {
+            Some(expr) => format!("CASE {}", expr),
+            None => "CASE".to_string(),
+        };
+
+        let mut lines = vec![Line::new(indent, header)];
+
+        for when in &node.when_part {
+            lines.extend(self.single(when, self.nested(indent)));
+        }
+
+        if let Some(expr) = &node.else_part {
+            lines.push(Line::new(self.nested(indent), format!("ELSE {}", expr)));
+        }
+
+        lines.push(Line::new(indent, format!("END{}", suffix)));
+        lines
+    }
+
+    /// A single table follows `FROM` on the same line, together with its joins
+    fn from(&self, tables: &[ast::TableExpression], indent: usize) -> Vec<Line> {
+        if let [table] = tables {
+            let mut lines = self.table_expression(table, indent);
+            lines[0].text.insert_str(0, "FROM ");
+            return lines;
+        }
+
+        let tables = tables
+            .iter()
+            .map(|table| self.table_expression(table, self.nested(indent)))
+            .collect();
+
+        self.list("FROM", tables, indent)
+    }
+
+    fn table_expression(&self, node: &ast::TableExpression, indent: usize) -> Vec<Line> {
+        match node {
+            ast::TableExpression::Named(_) | ast::TableExpression::Error(_) => {
+                self.single(node, indent)
+            }
+            ast::TableExpression::Select(select) => {
+                let alias = match &select.alias {
+                    Some(alias) => format!(" AS {}", alias),
+                    None => String::new(),
+                };
+
+                let query = self.select_statement(&select.select, self.nested(indent));
+                self.parenthesized(String::new(), query, alias, indent)
+            }
+            ast::TableExpression::Join(join) => {
+                let join_indent = match self.options.indent_joins {
+                    true => self.nested(indent),
+                    false => indent,
+                };
+
+                let mut lines = self.table_expression(&join.left, indent);
+
+                // the right side of a join can only be a join when it's parenthesised
+                let mut right = match join.right.as_ref() {
+                    ast::TableExpression::Join(_) => {
+                        vec![Line::new(join_indent, format!("({})", join.right))]
+                    }
+                    table => self.table_expression(table, join_indent),
+                };
+
+                right[0].text.insert_str(0, &format!("{} ", join.op));
+
+                let last = right.pop().unwrap();
+                lines.extend(right);
+
+                match &join.constraint {
+                    ast::JoinConstraint::Expr(expr) => {
+                        let keyword = format!("{} ON", last.text);
+                        lines.extend(self.condition(&keyword, expr, last.indent));
+                    }
+                    ast::JoinConstraint::Columns(columns) => {
+                        lines.push(Line::new(last.indent, format!("{} {}", last.text, columns)));
+                    }
+                }
+
+                lines
+            }
+        }
+    }
+
+    /// Wrap a nested query in parentheses, on a single line if it fits
+    fn parenthesized(
+        &self,
+        prefix: String,
+        query: Vec<Line>,
+        suffix: String,
+        indent: usize,
+    ) -> Vec<Line> {
+        if let [line] = query.as_slice() {
+            let text = format!("{}({}){}", prefix, line.text, suffix);
+
+            if self.fits(indent, &text) {
+                return vec![Line::new(indent, text)];
+            }
+        }
+
+        let mut lines = vec![Line::new(indent, format!("{}(", prefix))];
+        lines.extend(query);
+        lines.push(Line::new(indent, format!("){}", suffix)));
+        lines
+    }
+
+    /// A clause made of a keyword and a list of items, on a single line if it fits; otherwise
+    /// the items follow the keyword on their own lines
+    fn list(&self, keyword: &str, items: Vec<Vec<Line>>, indent: usize) -> Vec<Line> {
+        if items.iter().all(|item| item.len() == 1) {
+            let texts: Vec<&str> = items.iter().map(|item| item[0].text.as_str()).collect();
+            let text = format!("{} {}", keyword, texts.join(", "));
+
+            if self.fits(indent, &text) {
+                return vec![Line::new(indent, text)];
+            }
+        }
+
+        let mut lines = vec![Line::new(indent, keyword)];
+        lines.extend(self.items(items));
+        lines
+    }
+
+    /// Join the lines of the items of a list, adding the commas
+    fn items(&self, items: Vec<Vec<Line>>) -> Vec<Line> {
+        let count = items.len();
+        let mut lines = Vec::new();
+
+        for (i, mut item) in items.into_iter().enumerate() {
+            match self.options.comma_style {
+                CommaStyle::Trailing if i + 1 < count => item.last_mut().unwrap().text.push(','),
+                CommaStyle::Leading if i > 0 => item[0].text.insert_str(0, ", "),
+                _ => {}
+            }
+
+            lines.extend(item);
+        }
+
+        lines
+    }
+
+    /// A clause made of a keyword and a condition, on a single line if it fits; otherwise each
+    /// operand of a top level `AND` or `OR` goes on its own line
+    fn condition(&self, keyword: &str, expr: &ast::Expression, indent: usize) -> Vec<Line> {
+        let text = format!("{} {}", keyword, expr);
+
+        let op = match expr {
+            _ if self.fits(indent, &text) => return vec![Line::new(indent, text)],
+            ast::Expression::Binary(binary)
+                if binary.op == ast::BinaryOperator::And
+                    || binary.op == ast::BinaryOperator::Or =>
+            {
+                &binary.op
+            }
+            _ => return vec![Line::new(indent, text)],
+        };
+
+        let operands = operands(expr, op);
+        let mut lines = vec![Line::new(indent, format!("{} {}", keyword, operands[0]))];
+
+        for operand in &operands[1..] {
+            lines.push(Line::new(
+                self.nested(indent),
+                format!("{} {}", op, operand),
+            ));
+        }
+
+        lines
+    }
+}
+
+/// The operands of a chain of the same logical operator, parenthesised like the printer does
+fn operands(expr: &ast::Expression, op: &ast::BinaryOperator) -> Vec<String> {
+    match expr {
+        ast::Expression::Binary(binary) if &binary.op == op => {
+            let mut operands = operands(&binary.left, op);
+
+            // the right operand is parenthesised when it's a chain of the same operator too
+            let parenthesise = match binary.right.as_ref() {
+                ast::Expression::Binary(right) => {
+                    &right.op == op || is_or_within_and(&right.op, op)
+                }
+                _ => false,
+            };
+
+            operands.push(match parenthesise {
+                true => format!("({})", binary.right),
+                false => binary.right.to_string(),
+            });
+
+            operands
+        }
+        ast::Expression::Binary(binary) if is_or_within_and(&binary.op, op) => {
+            vec![format!("({})", expr)]
+        }
+        _ => vec![expr.to_string()],
+    }
+}
+
+fn is_or_within_and(inner: &ast::BinaryOperator, outer: &ast::BinaryOperator) -> bool {
+    *inner == ast::BinaryOperator::Or && *outer == ast::BinaryOperator::And
+}
+
+fn join<T: Display>(items: &[T], separator: &str) -> String {
+    items
+        .iter()
+        .map(T::to_string)
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum TokenKind {
+    Word,
+    Literal,
+    Symbol,
+    Comment,
+}
+
+/// A token of the SQL text, as a byte range
+#[derive(Debug, Clone, Copy)]
+struct Token {
+    kind: TokenKind,
+    start: usize,
+    end: usize,
+}
+
+/// Split a SQL text into tokens; it's a simpler lexer than the grammar's one, it just needs to
+/// tell keywords and comments apart from the rest
+fn tokenize(sql_str: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = sql_str.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        let next = chars.peek().map(|(_, c)| *c);
+
+        let kind = match c {
+            _ if c.is_whitespace() => continue,
+            '-' if next == Some('-') => {
+                while chars.peek().is_some_and(|(_, c)| *c != '\n') {
+                    chars.next();
+                }
+
+                TokenKind::Comment
+            }
+            '\'' => {
+                while chars.next().is_some_and(|(_, c)| c != '\'') {}
+
+                TokenKind::Literal
+            }
+            // quoted names are never keywords
+            '"' => {
+                while chars.next().is_some_and(|(_, c)| c != '"') {}
+
+                TokenKind::Literal
+            }
+            // bind parameters are values, their names are never keywords
+            ':' if next.is_some_and(|c| c.is_ascii_alphabetic() || c == '_') => {
+                while chars
+                    .peek()
+                    .is_some_and(|(_, c)| c.is_ascii_alphanumeric() || *c == '_')
+                {
+                    chars.next();
+                }
+
+                TokenKind::Literal
+            }
+            '$' if next == Some('{') => {
+                while chars.next().is_some_and(|(_, c)| c != '}') {}
+
+                TokenKind::Literal
+            }
+            _ if c.is_ascii_alphabetic() || c == '_' => {
+                while chars
+                    .peek()
+                    .is_some_and(|(_, c)| c.is_ascii_alphanumeric() || *c == '_')
+                {
+                    chars.next();
+                }
+
+                TokenKind::Word
+            }
+            _ if c.is_ascii_digit() || (c == '.' && next.is_some_and(|c| c.is_ascii_digit())) => {
+                let mut previous = c;
+
+                while let Some(&(_, c)) = chars.peek() {
+                    let exponent_sign =
+                        (c == '+' || c == '-') && (previous == 'E' || previous == 'e');
+
+                    if !(c.is_ascii_alphanumeric() || c == '.' || exponent_sign) {
+                        break;
+                    }
+
+                    previous = c;
+                    chars.next();
+                }
+
+                TokenKind::Literal
+            }
+            _ => {
+                let pair = next.map(|next| [c, next]);
+
+                if let Some(['<', '=']) | Some(['>', '=']) | Some(['!', '=']) | Some(['<', '>'])
+                | Some(['|', '|']) = pair
+                {
+                    chars.next();
+                }
+
+                TokenKind::Symbol
+            }
+        };
+
+        let end = chars.peek().map_or(sql_str.len(), |(i, _)| *i);
+        tokens.push(Token { kind, start, end });
+    }
+
+    tokens
+}
+
+fn apply_keyword_case(sql_str: &str, case: KeywordCase) -> String {
+    let mut result = String::with_capacity(sql_str.len());
+    let mut last = 0;
+
+    // the names are the words the parser reads as names, whatever their case; SQL the parser
+    // rejects, e.g. from a statement built by hand, has none
+    let names = match parse_strict(sql_str) {
+        Ok(statement) => name_starts(&statement),
+        Err(_) => HashSet::new(),
+    };
+
+    for token in tokenize(sql_str) {
+        let word = &sql_str[token.start..token.end];
+        let upper = word.to_uppercase();
+
+        if token.kind == TokenKind::Word
+            && KEYWORDS.contains(&upper.as_str())
+            && !names.contains(&token.start)
+        {
+            result.push_str(&sql_str[last..token.start]);
+
+            match case {
+                KeywordCase::Upper => result.push_str(&upper),
+                KeywordCase::Lower => result.push_str(&word.to_lowercase()),
+            }
+
+            last = token.end;
+        }
+    }
+
+    result.push_str(&sql_str[last..]);
+    result
+}
+
+/// Where the names of a statement start in the parsed SQL
+fn name_starts(statement: &ast::SqlStatement) -> HashSet<usize> {
+    let mut collector = NameCollector {
+        starts: HashSet::new(),
+    };
+
+    collector.visit_sql_statement(statement);
+    collector.starts
+}
+
+struct NameCollector {
+    starts: HashSet<usize>,
+}
+
+impl NameCollector {
+    fn add<'a, I: IntoIterator<Item = &'a Name>>(&mut self, names: I) {
+        let starts = names.into_iter().filter_map(|name| name.span());
+        self.starts.extend(starts.map(|span| span.start));
+    }
+}
+
+impl<'ast> Visitor<'ast> for NameCollector {
+    fn visit_sql_statement(&mut self, node: &'ast ast::SqlStatement) {
+        match node {
+            ast::SqlStatement::Attach(attach) => self.add(&attach.qualified_name),
+            ast::SqlStatement::Describe(describe) => self.add(&describe.qualified_name),
+            _ => visit::walk_sql_statement(self, node),
+        }
+    }
+
+    fn visit_insert_statement(&mut self, node: &'ast ast::InsertStatement) {
+        self.add(&node.table_name);
+        self.add(node.columns.iter().flatten());
+        visit::walk_insert_statement(self, node)
+    }
+
+    fn visit_delete_statement(&mut self, node: &'ast ast::DeleteStatement) {
+        self.add(&node.table_name);
+        visit::walk_delete_statement(self, node)
+    }
+
+    fn visit_update_statement(&mut self, node: &'ast ast::UpdateStatement) {
+        self.add(&node.table_name);
+        visit::walk_update_statement(self, node)
+    }
+
+    fn visit_assignment(&mut self, node: &'ast ast::Assignment) {
+        self.add(&node.columns);
+        visit::walk_assignment(self, node)
+    }
+
+    fn visit_common_table_expression(&mut self, node: &'ast ast::CommonTableExpression) {
+        self.add(Some(&node.identifier));
+        self.add(node.column_names.iter().flatten());
+        visit::walk_common_table_expression(self, node)
+    }
+
+    fn visit_named_table_expression(&mut self, node: &'ast ast::NamedTableExpression) {
+        self.add(&node.name);
+        self.add(&node.alias);
+    }
+
+    fn visit_select_table_expression(&mut self, node: &'ast ast::SelectTableExpression) {
+        self.add(&node.alias);
+        visit::walk_select_table_expression(self, node)
+    }
+
+    fn visit_join_constraint(&mut self, node: &'ast ast::JoinConstraint) {
+        if let ast::JoinConstraint::Columns(constraint) = node {
+            self.add(&constraint.columns);
+        }
+
+        visit::walk_join_constraint(self, node)
+    }
+
+    fn visit_result_column(&mut self, node: &'ast ast::ResultColumn) {
+        match node {
+            ast::ResultColumn::AllFrom(name) => self.add(Some(name)),
+            ast::ResultColumn::Expr(column) => self.add(&column.rename),
+        }
+
+        visit::walk_result_column(self, node)
+    }
+
+    fn visit_qualified_identifier(&mut self, node: &'ast ast::QualifiedIdentifierExpression) {
+        self.add(&node.identifiers);
+    }
+
+    fn visit_expression(&mut self, node: &'ast ast::Expression) {
+        if let ast::Expression::Unknown(unknown) = node {
+            self.add(&unknown.name);
+        }
+
+        visit::walk_expression(self, node)
+    }
+
+    fn visit_set_specification(&mut self, node: &'ast ast::SetSpecification) {
+        if let ast::SetSpecification::Name(name) = node {
+            self.add(name);
+        }
+
+        visit::walk_set_specification(self, node)
+    }
+
+    fn visit_ordering(&mut self, node: &'ast ast::Ordering) {
+        self.add(&node.collation);
+        visit::walk_ordering(self, node)
+    }
+}
+
+/// Match the tokens of a source with the ones of its formatted version, which are the same but
+/// for the few the printer adds, drops or spells differently, like `AS` or the parentheses;
+/// returns the index of the matching formatted token of every source token
+fn align(
+    source: &str,
+    source_tokens: &[Token],
+    formatted: &str,
+    formatted_tokens: &[Token],
+) -> Vec<Option<usize>> {
+    // how far to look for the next matching token after a mismatch
+    const LOOKAHEAD: usize = 8;
+
+    let keys = |sql_str: &str, tokens: &[Token]| -> Vec<String> {
+        tokens
+            .iter()
+            .map(|token| sql_str[token.start..token.end].to_uppercase())
+            .collect()
+    };
+
+    let source_keys = keys(source, source_tokens);
+    let formatted_keys = keys(formatted, formatted_tokens);
+
+    let mut matches = vec![None; source_keys.len()];
+    let (mut i, mut j) = (0, 0);
+
+    while i < source_keys.len() && j < formatted_keys.len() {
+        if source_keys[i] == formatted_keys[j] {
+            matches[i] = Some(j);
+            i += 1;
+            j += 1;
+            continue;
+        }
+
+        // skip the tokens on the side with the closest match
+        let skip =
+            |keys: &[String], key: &String| keys.iter().take(LOOKAHEAD).position(|k| k == key);
+
+        match (
+            skip(&source_keys[i..], &formatted_keys[j]),
+            skip(&formatted_keys[j..], &source_keys[i]),
+        ) {
+            (Some(source_skip), Some(formatted_skip)) if source_skip <= formatted_skip => {
+                i += source_skip
+            }
+            (_, Some(formatted_skip)) => j += formatted_skip,
+            (Some(source_skip), None) => i += source_skip,
+            (None, None) => {
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+
+    matches
+}
+
+/// Put the comments of a source back into its formatted version: a comment on its own line goes
+/// on the line before the token following it, any other comment at the end of the line of the
+/// token preceding it
+fn restore_comments(source: &str, formatted: &str) -> String {
+    let (comments, source_tokens): (Vec<Token>, Vec<Token>) = tokenize(source)
+        .into_iter()
+        .partition(|token| token.kind == TokenKind::Comment);
+
+    if comments.is_empty() {
+        return formatted.to_string();
+    }
+
+    let formatted_tokens = tokenize(formatted);
+    let matches = align(source, &source_tokens, formatted, &formatted_tokens);
+
+    let lines: Vec<&str> = match formatted.is_empty() {
+        true => Vec::new(),
+        false => formatted.split('\n').collect(),
+    };
+
+    let line_starts: Vec<usize> = lines
+        .iter()
+        .scan(0, |start, line| {
+            let line_start = *start;
+            *start += line.len() + 1;
+            Some(line_start)
+        })
+        .collect();
+
+    let line_of = |token: usize| match line_starts.binary_search(&formatted_tokens[token].start) {
+        Ok(line) => line,
+        Err(line) => line - 1,
+    };
+
+    let mut before = vec![Vec::new(); lines.len()];
+    let mut after = vec![Vec::new(); lines.len()];
+    let mut end = Vec::new();
+
+    for comment in &comments {
+        let text = source[comment.start..comment.end].trim_end();
+        let line_start = source[..comment.start].rfind('\n').map_or(0, |i| i + 1);
+        let own_line = source[line_start..comment.start].trim().is_empty();
+
+        let next = match source_tokens.binary_search_by_key(&comment.start, |token| token.start) {
+            Ok(i) | Err(i) => i,
+        };
+
+        let previous = match own_line {
+            true => None,
+            false => matches[..next].iter().rev().find_map(|m| *m),
+        };
+
+        match previous {
+            Some(token) => after[line_of(token)].push(text),
+            None => match matches[next..].iter().find_map(|m| *m) {
+                Some(token) => before[line_of(token)].push(text),
+                None => end.push(text),
+            },
+        }
+    }
+
+    // a line can end with one comment only, the others go on their own lines after it
+    for (i, comments) in after.iter_mut().enumerate() {
+        if comments.len() > 1 {
+            let rest = comments.split_off(1);
+
+            let next = match before.get_mut(i + 1) {
+                Some(next) => next,
+                None => &mut end,
+            };
+
+            next.splice(0..0, rest);
+        }
+    }
+
+    let mut result = Vec::new();
+
+    for (i, line) in lines.iter().enumerate() {
+        let indent = &line[..line.len() - line.trim_start().len()];
+
+        for comment in &before[i] {
+            result.push(format!("{}{}", indent, comment));
+        }
+
+        match after[i].first() {
+            Some(comment) => result.push(format!("{} {}", line, comment)),
+            None => result.push(line.to_string()),
+        }
+    }
+
+    result.extend(end.iter().map(|comment| comment.to_string()));
+    result.join("\n")
+}
@@ -0,0 +1,183 @@
+// MIT License
+//
+// Copyright (c) 2019 Daniele Esposti
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::HashSet;
+
+use super::ast;
+use super::symbols::Name;
+use super::visit::{self, Visitor};
+
+/// The tables read or written by a statement, CTEs included, as dotted names
+pub fn tables(statement: &ast::SqlStatement) -> Vec<String> {
+    collect(statement, Kind::Tables)
+}
+
+/// The names of the common table expressions defined by a statement
+pub fn ctes(statement: &ast::SqlStatement) -> Vec<String> {
+    collect(statement, Kind::Ctes)
+}
+
+/// The columns referenced by a statement as written, possibly qualified by their table
+pub fn columns(statement: &ast::SqlStatement) -> Vec<String> {
+    collect(statement, Kind::Columns)
+}
+
+/// The names of the functions called by a statement
+pub fn functions(statement: &ast::SqlStatement) -> Vec<String> {
+    collect(statement, Kind::Functions)
+}
+
+/// The literal values of a statement, as SQL
+pub fn literals(statement: &ast::SqlStatement) -> Vec<String> {
+    collect(statement, Kind::Literals)
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Kind {
+    Tables,
+    Ctes,
+    Columns,
+    Functions,
+    Literals,
+}
+
+/// Every function returns the distinct values in order of appearance; names are compared
+/// case-insensitively, like the rest of the AST does, keeping the first spelling
+fn collect(statement: &ast::SqlStatement, kind: Kind) -> Vec<String> {
+    let mut collector = Collector {
+        kind,
+        seen: HashSet::new(),
+        values: Vec::new(),
+    };
+
+    collector.visit_sql_statement(statement);
+    collector.values
+}
+
+struct Collector {
+    kind: Kind,
+    seen: HashSet<String>,
+    values: Vec<String>,
+}
+
+impl Collector {
+    fn push(&mut self, kind: Kind, value: String) {
+        if kind == self.kind && self.seen.insert(value.to_lowercase()) {
+            self.values.push(value);
+        }
+    }
+
+    fn push_names(&mut self, kind: Kind, names: &[Name]) {
+        self.push(kind, join(names));
+    }
+}
+
+impl<'ast> Visitor<'ast> for Collector {
+    fn visit_insert_statement(&mut self, node: &'ast ast::InsertStatement) {
+        self.push_names(Kind::Tables, &node.table_name);
+
+        for column in node.columns.iter().flatten() {
+            self.push(Kind::Columns, column.as_str().to_string());
+        }
+
+        visit::walk_insert_statement(self, node)
+    }
+
+    fn visit_delete_statement(&mut self, node: &'ast ast::DeleteStatement) {
+        self.push_names(Kind::Tables, &node.table_name);
+        visit::walk_delete_statement(self, node)
+    }
+
+    fn visit_update_statement(&mut self, node: &'ast ast::UpdateStatement) {
+        self.push_names(Kind::Tables, &node.table_name);
+        visit::walk_update_statement(self, node)
+    }
+
+    fn visit_assignment(&mut self, node: &'ast ast::Assignment) {
+        for column in &node.columns {
+            self.push(Kind::Columns, column.as_str().to_string());
+        }
+
+        visit::walk_assignment(self, node)
+    }
+
+    fn visit_common_table_expression(&mut self, node: &'ast ast::CommonTableExpression) {
+        self.push(Kind::Ctes, node.identifier.as_str().to_string());
+        visit::walk_common_table_expression(self, node)
+    }
+
+    fn visit_named_table_expression(&mut self, node: &'ast ast::NamedTableExpression) {
+        self.push_names(Kind::Tables, &node.name);
+    }
+
+    fn visit_join_constraint(&mut self, node: &'ast ast::JoinConstraint) {
+        if let ast::JoinConstraint::Columns(constraint) = node {
+            for column in &constraint.columns {
+                self.push(Kind::Columns, column.as_str().to_string());
+            }
+        }
+
+        visit::walk_join_constraint(self, node)
+    }
+
+    fn visit_expression(&mut self, node: &'ast ast::Expression) {
+        if let Some(name) = function_name(node) {
+            self.push(Kind::Functions, name);
+        }
+
+        visit::walk_expression(self, node)
+    }
+
+    fn visit_literal(&mut self, node: &'ast ast::Literal) {
+        self.push(Kind::Literals, node.to_string());
+    }
+
+    fn visit_qualified_identifier(&mut self, node: &'ast ast::QualifiedIdentifierExpression) {
+        self.push_names(Kind::Columns, &node.identifiers);
+    }
+}
+
+/// The name of the function called by an expression, as printed
+fn function_name(expr: &ast::Expression) -> Option<String> {
+    let name = match expr {
+        ast::Expression::Coalesce(_) => "COALESCE",
+        ast::Expression::Replace(_) => "REPLACE",
+        ast::Expression::Substring(_) => "SUBSTRING",
+        ast::Expression::ToDate(_) => "TO_DATE",
+        ast::Expression::Power(_) => "POWER",
+        ast::Expression::Concat(_) => "CONCAT",
+        ast::Expression::Sum(_) => "SUM",
+        ast::Expression::Max(_) => "MAX",
+        ast::Expression::Min(_) => "MIN",
+        ast::Expression::Cast(_) => "CAST",
+        ast::Expression::Right(_) => "RIGHT",
+        ast::Expression::Count(_) => "COUNT",
+        ast::Expression::Unknown(unknown) => return Some(join(&unknown.name)),
+        _ => return None,
+    };
+
+    Some(name.to_string())
+}
+
+fn join(names: &[Name]) -> String {
+    names.iter().map(Name::as_str).collect::<Vec<_>>().join(".")
+}
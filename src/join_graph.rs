@@ -0,0 +1,388 @@
+// MIT License
+//
+// Copyright (c) 2019 Daniele Esposti
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::fmt::Write;
+
+use dict_derive::IntoPyObject;
+
+use super::ast;
+use super::predicates::conjuncts;
+use super::symbols::Name;
+use super::visit::{self, Visitor};
+
+/// A table a query reads from, or a nested query
+#[derive(IntoPyObject, Debug, PartialEq, Eq, Clone)]
+pub struct TableNode {
+    /// the qualified name of the table, empty for a nested query
+    pub name: Vec<Name>,
+    pub alias: Option<Name>,
+}
+
+impl TableNode {
+    /// The name the columns of the table are qualified with
+    fn reference(&self) -> Option<&Name> {
+        self.alias.as_ref().or_else(|| self.name.last())
+    }
+
+    fn label(&self) -> String {
+        let name = match self.name.as_slice() {
+            [] => "(subquery)".to_string(),
+            name => join(name),
+        };
+
+        match &self.alias {
+            Some(alias) => format!("{} AS {}", name, alias),
+            None => name,
+        }
+    }
+}
+
+/// A pair of columns a join compares for equality
+#[derive(IntoPyObject, Debug, PartialEq, Eq, Clone)]
+pub struct JoinKey {
+    pub left: Vec<Name>,
+    pub right: Vec<Name>,
+}
+
+/// A join between two tables
+#[derive(IntoPyObject, Debug, PartialEq, Eq, Clone)]
+pub struct JoinEdge {
+    /// the index of the table on the left side of the join
+    pub left: usize,
+
+    /// the index of the table on the right side of the join
+    pub right: usize,
+
+    /// `None` for the tables of different `FROM` entries joined by a condition in `WHERE`
+    pub op: Option<ast::JoinOperator>,
+
+    /// the columns compared for equality in the join condition
+    pub keys: Vec<JoinKey>,
+}
+
+/// The tables of a query and how they are joined
+#[derive(IntoPyObject, Debug, PartialEq, Eq, Clone, Default)]
+pub struct JoinGraph {
+    pub nodes: Vec<TableNode>,
+    pub edges: Vec<JoinEdge>,
+}
+
+impl JoinGraph {
+    /// The graph as a Graphviz DOT digraph
+    pub fn to_dot(&self) -> String {
+        to_dot(std::slice::from_ref(self))
+    }
+
+    fn write_dot(&self, dot: &mut String, prefix: &str, indent: &str) {
+        for (index, node) in self.nodes.iter().enumerate() {
+            writeln!(
+                dot,
+                "{}{}{} [label=\"{}\"];",
+                indent,
+                prefix,
+                index,
+                escape(&node.label())
+            )
+            .unwrap();
+        }
+
+        for edge in &self.edges {
+            let mut label = match &edge.op {
+                Some(op) => op.to_string(),
+                None => "WHERE".to_string(),
+            };
+
+            for key in &edge.keys {
+                write!(label, "\n{} = {}", join(&key.left), join(&key.right)).unwrap();
+            }
+
+            let style = if edge.op.is_none() {
+                ", style=dashed"
+            } else {
+                ""
+            };
+
+            writeln!(
+                dot,
+                "{}{}{} -> {}{} [label=\"{}\"{}];",
+                indent,
+                prefix,
+                edge.left,
+                prefix,
+                edge.right,
+                escape(&label),
+                style
+            )
+            .unwrap();
+        }
+    }
+}
+
+/// The join graphs of the queries of a statement which read from tables, nested queries and
+/// the branches of set operations included, in order of appearance.
+///
+/// A join links the tables whose columns are compared for equality in its condition, or else
+/// the last table of its left side and the first one of its right side; it carries the pairs of
+/// compared columns, qualified with the tables' aliases for `USING`. The `FROM` entries
+/// separated by commas are linked by the equalities between their columns in `WHERE`. Columns
+/// are matched to tables by their qualifier, so unqualified ones can't link two tables.
+pub fn join_graphs(statement: &ast::SqlStatement) -> Vec<JoinGraph> {
+    let mut collector = Collector { graphs: Vec::new() };
+
+    collector.visit_sql_statement(statement);
+    collector.graphs
+}
+
+/// Graphviz DOT digraph of many join graphs, one cluster each if they are more than one
+pub fn to_dot(graphs: &[JoinGraph]) -> String {
+    let mut dot = "digraph joins {\n".to_string();
+
+    match graphs {
+        [graph] => graph.write_dot(&mut dot, "t", "    "),
+        _ => {
+            for (index, graph) in graphs.iter().enumerate() {
+                writeln!(dot, "    subgraph cluster_{} {{", index).unwrap();
+                writeln!(dot, "        label=\"query {}\";", index + 1).unwrap();
+                graph.write_dot(&mut dot, &format!("q{}_t", index), "        ");
+                dot.push_str("    }\n");
+            }
+        }
+    }
+
+    dot.push_str("}\n");
+    dot
+}
+
+struct Collector {
+    graphs: Vec<JoinGraph>,
+}
+
+impl<'ast> Visitor<'ast> for Collector {
+    fn visit_query_set_expression(&mut self, node: &'ast ast::QuerySetExpression) {
+        let mut builder = Builder {
+            graph: JoinGraph::default(),
+        };
+        let entries: Vec<Vec<usize>> = node
+            .from
+            .iter()
+            .map(|table| builder.table_expression(table))
+            .collect();
+
+        if entries.len() > 1 {
+            if let Some(where_expr) = &node.where_expr {
+                builder.where_joins(where_expr, &entries);
+            }
+        }
+
+        if !builder.graph.nodes.is_empty() {
+            self.graphs.push(builder.graph);
+        }
+
+        visit::walk_query_set_expression(self, node);
+    }
+}
+
+struct Builder {
+    graph: JoinGraph,
+}
+
+impl Builder {
+    /// Add the tables of a `FROM` entry and its joins, returning the indexes of the tables
+    fn table_expression(&mut self, node: &ast::TableExpression) -> Vec<usize> {
+        let node = match node {
+            ast::TableExpression::Named(named) => TableNode {
+                name: named.name.clone(),
+                alias: named.alias.clone(),
+            },
+            ast::TableExpression::Select(select) => TableNode {
+                name: Vec::new(),
+                alias: select.alias.clone(),
+            },
+            ast::TableExpression::Join(join) => return self.join(join),
+            ast::TableExpression::Error(_) => return Vec::new(),
+        };
+
+        self.graph.nodes.push(node);
+        vec![self.graph.nodes.len() - 1]
+    }
+
+    fn join(&mut self, node: &ast::JoinTableExpression) -> Vec<usize> {
+        let left = self.table_expression(&node.left);
+        let right = self.table_expression(&node.right);
+
+        let mut edges: Vec<JoinEdge> = Vec::new();
+        let mut add_key = |left: usize, right: usize, key: JoinKey| match edges
+            .iter_mut()
+            .find(|edge| edge.left == left && edge.right == right)
+        {
+            Some(edge) => edge.keys.push(key),
+            None => edges.push(JoinEdge {
+                left,
+                right,
+                op: Some(node.op.clone()),
+                keys: vec![key],
+            }),
+        };
+
+        let (default_left, default_right) = match (left.last(), right.first()) {
+            (Some(left), Some(right)) => (*left, *right),
+            _ => return left.into_iter().chain(right).collect(),
+        };
+
+        match &node.constraint {
+            ast::JoinConstraint::Expr(expr) => {
+                for (a, b) in equalities(expr) {
+                    let key = match (self.find(&a, &left), self.find(&b, &right)) {
+                        (Some(l), Some(r)) => (Some(l), Some(r), a, b),
+                        _ => match (self.find(&b, &left), self.find(&a, &right)) {
+                            (Some(l), Some(r)) => (Some(l), Some(r), b, a),
+                            _ => (None, None, a, b),
+                        },
+                    };
+
+                    match key {
+                        (Some(l), Some(r), a, b) => add_key(l, r, JoinKey { left: a, right: b }),
+                        (_, _, a, b) => {
+                            add_key(default_left, default_right, JoinKey { left: a, right: b })
+                        }
+                    }
+                }
+            }
+            ast::JoinConstraint::Columns(columns) => {
+                for column in &columns.columns {
+                    let qualified = |index: usize| -> Vec<Name> {
+                        let node = &self.graph.nodes[index];
+                        node.reference()
+                            .into_iter()
+                            .chain(std::iter::once(column))
+                            .cloned()
+                            .collect()
+                    };
+
+                    add_key(
+                        default_left,
+                        default_right,
+                        JoinKey {
+                            left: qualified(default_left),
+                            right: qualified(default_right),
+                        },
+                    );
+                }
+            }
+        }
+
+        if edges.is_empty() {
+            edges.push(JoinEdge {
+                left: default_left,
+                right: default_right,
+                op: Some(node.op.clone()),
+                keys: Vec::new(),
+            });
+        }
+
+        self.graph.edges.extend(edges);
+        left.into_iter().chain(right).collect()
+    }
+
+    /// Link the `FROM` entries whose columns are compared for equality in `WHERE`
+    fn where_joins(&mut self, where_expr: &ast::Expression, entries: &[Vec<usize>]) {
+        let all: Vec<usize> = entries.iter().flatten().cloned().collect();
+        let entry = |index: usize| entries.iter().position(|tables| tables.contains(&index));
+
+        for (a, b) in equalities(where_expr) {
+            let (left, right) = match (self.find(&a, &all), self.find(&b, &all)) {
+                (Some(left), Some(right)) if entry(left) != entry(right) => (left, right),
+                _ => continue,
+            };
+
+            let key = JoinKey { left: a, right: b };
+
+            match self
+                .graph
+                .edges
+                .iter_mut()
+                .find(|edge| edge.op.is_none() && edge.left == left && edge.right == right)
+            {
+                Some(edge) => edge.keys.push(key),
+                None => self.graph.edges.push(JoinEdge {
+                    left,
+                    right,
+                    op: None,
+                    keys: vec![key],
+                }),
+            }
+        }
+    }
+
+    /// The table among the given ones a column is qualified with
+    fn find(&self, column: &[Name], tables: &[usize]) -> Option<usize> {
+        let qualifier = match column {
+            [qualifier @ .., _] if !qualifier.is_empty() => qualifier,
+            _ => return None,
+        };
+
+        tables.iter().cloned().find(|index| {
+            let node = &self.graph.nodes[*index];
+
+            match (&node.alias, qualifier) {
+                (Some(alias), [name]) => alias == name,
+                (Some(_), _) => false,
+                (None, qualifier) => node.name.ends_with(qualifier),
+            }
+        })
+    }
+}
+
+/// The pairs of columns compared for equality by the conjuncts of a condition
+fn equalities(expr: &ast::Expression) -> Vec<(Vec<Name>, Vec<Name>)> {
+    conjuncts(expr)
+        .into_iter()
+        .filter_map(|conjunct| match conjunct {
+            ast::Expression::Comparison(ast::ComparisonExpression {
+                op: ast::ComparisonOperator::Equal,
+                left,
+                right,
+            }) => match (*left, *right) {
+                (
+                    ast::Expression::QualifiedIdentifier(left),
+                    ast::Expression::QualifiedIdentifier(right),
+                ) => Some((left.identifiers, right.identifiers)),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+fn join(name: &[Name]) -> String {
+    name.iter()
+        .map(|name| name.to_string())
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+fn escape(label: &str) -> String {
+    label
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
@@ -25,53 +25,413 @@ extern crate serde;
 #[macro_use]
 extern crate serde_derive;
 extern crate dict_derive;
+extern crate num_cpus;
 extern crate serde_json;
+extern crate toml;
 
 use pyo3::exceptions;
 use pyo3::prelude::*;
+use pyo3::types::{PyBool, PyDict, PyTuple};
 use pyo3::wrap_pyfunction;
+use pyo3::PyTryFrom;
+use std::collections::HashMap;
 
 pub mod ast;
+pub mod batch;
+pub mod binder;
+pub mod catalog;
+pub mod classify;
+pub mod complexity;
+pub mod cte;
+pub mod diff;
 pub mod error;
+pub mod expand;
+pub mod fingerprint;
+pub mod format;
+pub mod inspect;
+pub mod join_graph;
+pub mod lineage;
+pub mod lint;
+pub mod params;
+pub mod predicates;
+pub mod recovery;
+pub mod simplify;
 pub mod sql;
 pub mod symbols;
+pub mod tree;
+pub mod typecheck;
 pub mod types;
+pub mod visit;
 
-mod ast_py;
+mod printer;
 
-fn strip_inline_comments(s: &str) -> String {
-    match s.rfind("--") {
-        Some(pos) => match s[..pos].matches('\'').count() % 2 {
-            1 => s.to_owned(),
-            _ => s[..pos].to_owned(),
-        },
-        None => s.to_owned(),
+/// Blank out `--` comments, keeping line breaks and byte offsets intact so that the spans
+/// reported by the parser still point into the original SQL.
+///
+/// This is an hack to strip comments from the original SQL because LALRPOP doesn't support that
+/// yet. See https://github.com/lalrpop/lalrpop/issues/10.
+pub fn strip_comments(sql_str: &str) -> String {
+    let mut stripped = String::with_capacity(sql_str.len());
+    let mut in_string = false;
+    let mut in_comment = false;
+    let mut chars = sql_str.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_comment {
+            if c == '\n' {
+                in_comment = false;
+                stripped.push(c);
+            } else {
+                stripped.extend(std::iter::repeat(' ').take(c.len_utf8()));
+            }
+        } else if !in_string && c == '-' && chars.peek() == Some(&'-') {
+            in_comment = true;
+            stripped.push(' ');
+        } else {
+            if c == '\'' {
+                in_string = !in_string;
+            }
+            stripped.push(c);
+        }
     }
+
+    stripped
 }
 
-#[pyfunction]
+/// Split a script on the `;` outside of strings and comments, returning each statement with its
+/// byte offset in the script; statements which are blank or just comments are skipped.
+pub fn split_statements(sql_str: &str) -> Vec<(usize, &str)> {
+    let stripped = strip_comments(sql_str);
+    let mut statements = Vec::new();
+    let mut in_string = false;
+    let mut start = 0;
+
+    let mut push = |start: usize, end: usize| {
+        if !stripped[start..end].trim().is_empty() {
+            statements.push((start, &sql_str[start..end]));
+        }
+    };
+
+    for (i, c) in stripped.char_indices() {
+        if c == '\'' {
+            in_string = !in_string;
+        } else if c == ';' && !in_string {
+            push(start, i);
+            start = i + 1;
+        }
+    }
+
+    push(start, sql_str.len());
+
+    statements
+}
+
+/// Parse a statement, failing with a `ValueError` on syntax errors; `parse()` in Python converts
+/// the statement with `try_into_py()`
 pub fn parse(sql_str: &str) -> PyResult<ast::SqlStatement> {
-    // This is an hack to strip comments from the original SQL
-    // because LALRPOP doesn't support that yet.
-    // See https://github.com/lalrpop/lalrpop/issues/10.
+    recovery::parse_strict(sql_str).map_err(PyErr::new::<exceptions::ValueError, _>)
+}
+
+/// Parse a statement recovering from its syntax errors, returning the statement, where the SQL
+/// which cannot be parsed is replaced by `ErrorNode` placeholders, and the errors
+#[pyfunction]
+pub fn parse_lenient(py: Python, sql_str: &str) -> PyResult<PyObject> {
+    recovery::parse_lenient(sql_str).try_into_py(py)
+}
+
+/// Parse many statements in parallel with the GIL released, on one thread per CPU by default;
+/// a statement that fails to parse gives a result with the error message instead of raising
+#[pyfunction(threads = "None")]
+pub fn parse_many(py: Python, sql_strs: Vec<String>, threads: Option<usize>) -> PyResult<PyObject> {
+    let threads = match threads {
+        Some(0) => {
+            return Err(PyErr::new::<exceptions::ValueError, _>(
+                "threads must be at least 1",
+            ))
+        }
+        Some(threads) => threads,
+        None => num_cpus::get(),
+    };
+
+    let results = py.allow_threads(|| batch::parse_many(sql_strs, threads));
+
+    let results = results.into_iter().map(batch::ParseResult::from).collect();
+
+    try_list(py, results, batch::ParseResult::try_into_py)
+}
+
+/// Parse a statement into a tree of node objects, rather than dictionaries
+#[pyfunction]
+pub fn parse_tree(py: Python, sql_str: &str) -> PyResult<PyObject> {
+    Ok(tree::into_tree(parse(sql_str)?, py))
+}
+
+/// Print a statement, given as the dictionary returned by `parse()`, back to SQL
+#[pyfunction]
+pub fn to_sql(statement: ast::SqlStatement) -> PyResult<String> {
+    Ok(statement.to_string())
+}
+
+/// Format a script keeping its comments; the keyword arguments set the fields of `FormatOptions`
+#[pyfunction(options = "**")]
+pub fn format_sql(sql_str: &str, options: Option<&PyDict>) -> PyResult<String> {
+    let options = format_options(options)?;
+
+    format::format_sql(sql_str, &options).map_err(PyErr::new::<exceptions::ValueError, _>)
+}
+
+fn format_options(options: Option<&PyDict>) -> PyResult<format::FormatOptions> {
+    let mut values = serde_json::Map::new();
+
+    if let Some(options) = options {
+        for (key, value) in options.iter() {
+            let value = if let Ok(value) = <PyBool as PyTryFrom>::try_from(value) {
+                serde_json::Value::from(value.is_true())
+            } else if let Ok(value) = value.extract::<u64>() {
+                serde_json::Value::from(value)
+            } else {
+                serde_json::Value::from(value.extract::<String>()?)
+            };
+
+            values.insert(key.extract()?, value);
+        }
+    }
+
+    serde_json::from_value(serde_json::Value::Object(values)).map_err(|e| {
+        PyErr::new::<exceptions::ValueError, _>(format!("Invalid format options: {}", e))
+    })
+}
+
+/// Return the fingerprint of a query as an hexadecimal hash and the normalised SQL
+#[pyfunction]
+pub fn fingerprint(sql_str: &str) -> PyResult<(String, String)> {
+    let result = fingerprint::fingerprint(&parse(sql_str)?);
+
+    Ok((result.to_string(), result.sql))
+}
+
+/// Compare two statements, returning the nodes added, removed or changed in the second one
+#[pyfunction]
+pub fn diff(py: Python, sql_a: &str, sql_b: &str) -> PyResult<PyObject> {
+    let edits = diff::diff(&parse(sql_a)?, &parse(sql_b)?);
+
+    try_list(py, edits, diff::Edit::try_into_py)
+}
+
+/// Return the tables read or written by a statement
+#[pyfunction]
+pub fn tables(sql_str: &str) -> PyResult<Vec<String>> {
+    Ok(inspect::tables(&parse(sql_str)?))
+}
+
+/// Return the names of the common table expressions defined by a statement
+#[pyfunction]
+pub fn ctes(sql_str: &str) -> PyResult<Vec<String>> {
+    Ok(inspect::ctes(&parse(sql_str)?))
+}
+
+/// Return the columns referenced by a statement
+#[pyfunction]
+pub fn columns(sql_str: &str) -> PyResult<Vec<String>> {
+    Ok(inspect::columns(&parse(sql_str)?))
+}
+
+/// Return the names of the functions called by a statement
+#[pyfunction]
+pub fn functions(sql_str: &str) -> PyResult<Vec<String>> {
+    Ok(inspect::functions(&parse(sql_str)?))
+}
+
+/// Return the literal values of a statement as SQL
+#[pyfunction]
+pub fn literals(sql_str: &str) -> PyResult<Vec<String>> {
+    Ok(inspect::literals(&parse(sql_str)?))
+}
+
+/// Check a statement with the built-in lint rules, but the disabled ones
+#[pyfunction(disable = "None")]
+pub fn lint(py: Python, sql_str: &str, disable: Option<Vec<String>>) -> PyResult<PyObject> {
+    let mut linter = lint::Linter::new();
+
+    for name in disable.unwrap_or_default() {
+        linter = linter
+            .without_rule(&name)
+            .map_err(PyErr::new::<exceptions::ValueError, _>)?;
+    }
+
+    let diagnostics = linter.lint(&parse(sql_str)?);
+
+    try_list(py, diagnostics, lint::Diagnostic::try_into_py)
+}
+
+/// Return the bind parameters of a statement, with the type inferred from where they are used
+#[pyfunction]
+pub fn parameters(py: Python, sql_str: &str) -> PyResult<PyObject> {
+    let parameters = params::parameters(&parse(sql_str)?);
+
+    try_list(py, parameters, params::ParameterType::try_into_py)
+}
+
+/// Replace the bind parameters of a statement with the given values and return it as SQL; the
+/// positional arguments are the values of the `?` parameters, the keyword arguments the values of
+/// the `:name` and `${name}` ones
+#[pyfunction(values = "*", named = "**")]
+pub fn substitute(sql_str: &str, values: &PyTuple, named: Option<&PyDict>) -> PyResult<String> {
+    let positional = values.iter().map(literal).collect::<PyResult<Vec<_>>>()?;
+    let mut names = HashMap::new();
+
+    if let Some(named) = named {
+        for (key, value) in named.iter() {
+            names.insert(key.extract::<String>()?, literal(value)?);
+        }
+    }
+
+    params::substitute(&parse(sql_str)?, &positional, &names)
+        .map(|statement| statement.to_string())
+        .map_err(PyErr::new::<exceptions::ValueError, _>)
+}
+
+/// Return the filters on columns of the `WHERE`, `HAVING` and `JOIN ... ON` clauses of a
+/// statement, split into conjuncts
+#[pyfunction]
+pub fn predicates(py: Python, sql_str: &str) -> PyResult<PyObject> {
+    let predicates = predicates::predicates(&parse(sql_str)?);
+
+    try_list(py, predicates, predicates::Predicate::try_into_py)
+}
+
+/// Return the join graphs of the queries of a statement: the tables they read from and the
+/// joins between them, with the columns compared for equality
+#[pyfunction]
+pub fn join_graphs(py: Python, sql_str: &str) -> PyResult<PyObject> {
+    let graphs = join_graph::join_graphs(&parse(sql_str)?);
 
-    let stripped_sql: String = sql_str
-        .lines()
-        .filter(|s| !s.trim_start().starts_with("--"))
-        .map(strip_inline_comments)
-        .collect::<String>();
+    try_list(py, graphs, join_graph::JoinGraph::try_into_py)
+}
+
+/// Return the join graphs of the queries of a statement as a Graphviz DOT digraph
+#[pyfunction]
+pub fn join_graphs_dot(sql_str: &str) -> PyResult<String> {
+    let graphs = join_graph::join_graphs(&parse(sql_str)?);
+
+    Ok(join_graph::to_dot(&graphs))
+}
+
+/// Replace the references to a common table expression with its query, returning the
+/// rewritten statement as SQL
+#[pyfunction]
+pub fn inline_cte(sql_str: &str, name: &str) -> PyResult<String> {
+    cte::inline_cte(&parse(sql_str)?, name)
+        .map(|statement| statement.to_string())
+        .map_err(PyErr::new::<exceptions::ValueError, _>)
+}
+
+/// Replace a nested query which appears more than once with a new common table expression,
+/// returning the rewritten statement as SQL
+#[pyfunction]
+pub fn extract_cte(sql_str: &str, name: &str) -> PyResult<String> {
+    cte::extract_cte(&parse(sql_str)?, name)
+        .map(|statement| statement.to_string())
+        .map_err(PyErr::new::<exceptions::ValueError, _>)
+}
+
+/// Return what kind of statement a statement is, which tables it works on and which features
+/// it uses
+#[pyfunction]
+pub fn classify(py: Python, sql_str: &str) -> PyResult<PyObject> {
+    classify::classify(&parse(sql_str)?).try_into_py(py)
+}
+
+/// Return the complexity metrics of a statement
+#[pyfunction]
+pub fn complexity(py: Python, sql_str: &str) -> PyResult<PyObject> {
+    complexity::complexity(&parse(sql_str)?).try_into_py(py)
+}
 
-    let result = sql::SqlStatementParser::new().parse(&stripped_sql);
+/// Fold the constant expressions of a statement and drop the redundant parts of the others,
+/// returning the simplified statement as SQL
+#[pyfunction]
+pub fn simplify(sql_str: &str) -> PyResult<String> {
+    let mut statement = parse(sql_str)?;
+    simplify::simplify(&mut statement);
+
+    Ok(statement.to_string())
+}
 
-    match result {
-        Ok(r) => Ok(r),
-        Err(e) => Err(PyErr::new::<exceptions::ValueError, _>(format!("{}", e))),
+/// Convert a Python value into a literal: `None`, numbers and strings, or a literal as the
+/// dictionary returned by `parse()`
+fn literal(value: &PyAny) -> PyResult<ast::Literal> {
+    if value.is_none() {
+        Ok(ast::Literal::Null)
+    } else if <PyBool as PyTryFrom>::try_from(value).is_ok() {
+        Err(PyErr::new::<exceptions::ValueError, _>(
+            "booleans can't be bound to a parameter",
+        ))
+    } else if let Ok(value) = value.extract::<i64>() {
+        Ok(ast::Literal::Numeric(value.to_string()))
+    } else if let Ok(value) = value.extract::<f64>() {
+        if value.is_finite() {
+            Ok(ast::Literal::Numeric(value.to_string()))
+        } else {
+            Err(PyErr::new::<exceptions::ValueError, _>(format!(
+                "{} can't be bound to a parameter",
+                value
+            )))
+        }
+    } else if let Ok(value) = value.extract::<String>() {
+        Ok(ast::Literal::String(value))
+    } else {
+        value.extract()
     }
 }
 
+/// Convert a list of values with their `try_into_py()`, returning the first error
+fn try_list<T>(
+    py: Python,
+    values: Vec<T>,
+    convert: fn(T, Python) -> PyResult<PyObject>,
+) -> PyResult<PyObject> {
+    let items = values
+        .into_iter()
+        .map(|value| convert(value, py))
+        .collect::<PyResult<Vec<_>>>()?;
+
+    Ok(items.into_py(py))
+}
+
 #[pymodule]
 fn hephaestus(_py: Python, m: &PyModule) -> PyResult<()> {
-    m.add_wrapped(wrap_pyfunction!(parse))?;
+    /// Parse a statement into the dictionaries of its nodes
+    #[pyfn(m, "parse")]
+    fn parse_py(py: Python, sql_str: &str) -> PyResult<PyObject> {
+        parse(sql_str)?.try_into_py(py)
+    }
+
+    m.add_wrapped(wrap_pyfunction!(parse_lenient))?;
+    m.add_wrapped(wrap_pyfunction!(parse_many))?;
+    m.add_wrapped(wrap_pyfunction!(parse_tree))?;
+    m.add_wrapped(wrap_pyfunction!(to_sql))?;
+    m.add_wrapped(wrap_pyfunction!(format_sql))?;
+    m.add_wrapped(wrap_pyfunction!(fingerprint))?;
+    m.add_wrapped(wrap_pyfunction!(diff))?;
+    m.add_wrapped(wrap_pyfunction!(tables))?;
+    m.add_wrapped(wrap_pyfunction!(ctes))?;
+    m.add_wrapped(wrap_pyfunction!(columns))?;
+    m.add_wrapped(wrap_pyfunction!(functions))?;
+    m.add_wrapped(wrap_pyfunction!(literals))?;
+    m.add_wrapped(wrap_pyfunction!(lint))?;
+    m.add_wrapped(wrap_pyfunction!(parameters))?;
+    m.add_wrapped(wrap_pyfunction!(substitute))?;
+    m.add_wrapped(wrap_pyfunction!(simplify))?;
+    m.add_wrapped(wrap_pyfunction!(predicates))?;
+    m.add_wrapped(wrap_pyfunction!(join_graphs))?;
+    m.add_wrapped(wrap_pyfunction!(join_graphs_dot))?;
+    m.add_wrapped(wrap_pyfunction!(complexity))?;
+    m.add_wrapped(wrap_pyfunction!(classify))?;
+    m.add_wrapped(wrap_pyfunction!(inline_cte))?;
+    m.add_wrapped(wrap_pyfunction!(extract_cte))?;
+
+    tree::add_classes(m)?;
 
     Ok(())
 }
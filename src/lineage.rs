@@ -0,0 +1,647 @@
+// MIT License
+//
+// Copyright (c) 2019 Daniele Esposti
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::BTreeSet;
+
+use super::ast;
+use super::cte::references_itself;
+use super::symbols::Name;
+use super::visit::{self, Visitor};
+
+/// A column of a table the query reads from
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct ColumnReference {
+    /// the qualified name of the table; empty when the column cannot be attributed to a
+    /// single table, i.e. an unqualified column in a query reading from several tables
+    pub table: Vec<Name>,
+
+    /// the column name, `*` when all the columns of the table are selected
+    pub column: Name,
+}
+
+/// The lineage of a column returned by a query
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ColumnLineage {
+    /// the name of the column, if it has one
+    pub name: Option<Name>,
+
+    /// the table columns the value of the column derives from
+    pub sources: BTreeSet<ColumnReference>,
+}
+
+/// The lineage of a statement
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Lineage {
+    /// the columns returned by a query or written by an insert or update statement
+    pub columns: Vec<ColumnLineage>,
+
+    /// the tables the statement reads from, common table expressions excluded
+    pub reads: BTreeSet<Vec<Name>>,
+
+    /// the tables the statement writes into
+    pub writes: BTreeSet<Vec<Name>>,
+}
+
+#[derive(Debug, Clone)]
+enum Relation {
+    /// a table from the database
+    Table(Vec<Name>),
+
+    /// the result of a nested query or a common table expression
+    Derived(Vec<ColumnLineage>),
+}
+
+#[derive(Debug, Default)]
+struct Scope {
+    /// the row sets of the FROM clause with the name they can be referred to with
+    relations: Vec<(Option<Name>, Relation)>,
+
+    /// the columns of `JOIN ... USING` constraints with the relations they are joined from
+    using: Vec<(Name, Vec<usize>)>,
+}
+
+/// Resolves the columns returned by a statement to the columns of the tables they derive from.
+pub struct LineageAnalyzer {
+    default_schema: Option<Name>,
+}
+
+impl Default for LineageAnalyzer {
+    fn default() -> Self {
+        LineageAnalyzer::new()
+    }
+}
+
+impl LineageAnalyzer {
+    pub fn new() -> LineageAnalyzer {
+        LineageAnalyzer {
+            default_schema: None,
+        }
+    }
+
+    /// Qualify the tables referenced without a schema with the given one
+    pub fn with_default_schema(mut self, schema: Name) -> LineageAnalyzer {
+        self.default_schema = Some(schema);
+        self
+    }
+
+    pub fn analyze(&self, statement: &ast::SqlStatement) -> Lineage {
+        let mut state = State {
+            default_schema: self.default_schema.as_ref(),
+            ctes: Vec::new(),
+            scopes: Vec::new(),
+            reads: BTreeSet::new(),
+        };
+        let mut writes = BTreeSet::new();
+
+        let columns = match statement {
+            ast::SqlStatement::Statement(statement)
+            | ast::SqlStatement::ExplainQueryPlan(statement) => match statement {
+                ast::Statement::Select(select) => state.select_statement(select),
+                ast::Statement::Insert(insert) => {
+                    writes.insert(state.qualify(&insert.table_name));
+
+                    let mut columns = state.set_expression(&insert.source);
+
+                    if let Some(names) = &insert.columns {
+                        for (column, name) in columns.iter_mut().zip(names) {
+                            column.name = Some(name.clone());
+                        }
+                    }
+
+                    columns
+                }
+                ast::Statement::Update(update) => {
+                    let table = state.qualify(&update.table_name);
+                    writes.insert(table.clone());
+                    state.push_target(table);
+
+                    let columns = update
+                        .assignments
+                        .iter()
+                        .flat_map(|assignment| {
+                            let sources = state.expression_sources(&assignment.expr);
+
+                            assignment.columns.iter().map(move |name| ColumnLineage {
+                                name: Some(name.clone()),
+                                sources: sources.clone(),
+                            })
+                        })
+                        .collect::<Vec<_>>();
+
+                    if let Some(expr) = &update.where_expr {
+                        state.expression_sources(expr);
+                    }
+
+                    columns
+                }
+                ast::Statement::Delete(delete) => {
+                    let table = state.qualify(&delete.table_name);
+                    writes.insert(table.clone());
+                    state.push_target(table);
+
+                    if let Some(expr) = &delete.where_expr {
+                        state.expression_sources(expr);
+                    }
+
+                    Vec::new()
+                }
+            },
+            ast::SqlStatement::Attach(attach) => {
+                writes.insert(state.qualify(&attach.qualified_name));
+                Vec::new()
+            }
+            ast::SqlStatement::Describe(_) | ast::SqlStatement::Error(_) => Vec::new(),
+        };
+
+        Lineage {
+            columns,
+            reads: state.reads,
+            writes,
+        }
+    }
+}
+
+/// Analyse the lineage of a statement
+pub fn lineage(statement: &ast::SqlStatement) -> Lineage {
+    LineageAnalyzer::new().analyze(statement)
+}
+
+struct State<'a> {
+    default_schema: Option<&'a Name>,
+
+    /// the common table expressions visible from the current query, innermost last
+    ctes: Vec<Vec<(Name, Vec<ColumnLineage>)>>,
+
+    /// the scopes of the queries being analysed, innermost last
+    scopes: Vec<Scope>,
+
+    reads: BTreeSet<Vec<Name>>,
+}
+
+fn unresolved(column: &Name) -> ColumnReference {
+    ColumnReference {
+        table: Vec::new(),
+        column: column.clone(),
+    }
+}
+
+/// The query of a recursive common table expression without the queries combined with its
+/// first one, which doesn't reference it
+fn anchor(cte: &ast::CommonTableExpression) -> ast::SelectStatement {
+    let mut expr = cte.query.expr.as_ref();
+
+    while let ast::SetExpression::Op(op) = expr {
+        expr = op.left.as_ref();
+    }
+
+    ast::SelectStatement {
+        expr: Box::new(expr.clone()),
+        ..cte.query.clone()
+    }
+}
+
+fn star() -> Name {
+    Name::from("*")
+}
+
+impl<'a> State<'a> {
+    fn qualify(&self, name: &[Name]) -> Vec<Name> {
+        match (name.len(), self.default_schema) {
+            (1, Some(schema)) => vec![schema.clone(), name[0].clone()],
+            _ => name.to_vec(),
+        }
+    }
+
+    /// Make the target table of an update or delete statement visible to its expressions
+    fn push_target(&mut self, table: Vec<Name>) {
+        let alias = table.last().cloned();
+
+        self.scopes.push(Scope {
+            relations: vec![(alias, Relation::Table(table))],
+            using: Vec::new(),
+        });
+    }
+
+    fn lookup_cte(&self, name: &Name) -> Option<Vec<ColumnLineage>> {
+        self.ctes.iter().rev().find_map(|frame| {
+            frame
+                .iter()
+                .rev()
+                .find(|(identifier, _)| identifier == name)
+                .map(|(_, columns)| columns.clone())
+        })
+    }
+
+    fn select_statement(&mut self, select: &ast::SelectStatement) -> Vec<ColumnLineage> {
+        self.ctes.push(Vec::new());
+
+        for cte in &select.common {
+            // the queries of a recursive common table expression referencing it read the rows
+            // of its anchor, until the whole query is analysed
+            let recursive = select.recursive && references_itself(cte);
+
+            if recursive {
+                let columns = self.cte_columns(cte, &anchor(cte));
+                self.ctes
+                    .last_mut()
+                    .unwrap()
+                    .push((cte.identifier.clone(), columns));
+            }
+
+            let columns = self.cte_columns(cte, &cte.query);
+            let frame = self.ctes.last_mut().unwrap();
+
+            if recursive {
+                frame.pop();
+            }
+
+            frame.push((cte.identifier.clone(), columns));
+        }
+
+        let columns = self.set_expression(&select.expr);
+
+        self.ctes.pop();
+
+        columns
+    }
+
+    /// The columns of a query of a common table expression, with the names given after its one
+    fn cte_columns(
+        &mut self,
+        cte: &ast::CommonTableExpression,
+        query: &ast::SelectStatement,
+    ) -> Vec<ColumnLineage> {
+        let mut columns = self.select_statement(query);
+
+        if let Some(names) = &cte.column_names {
+            for (column, name) in columns.iter_mut().zip(names) {
+                column.name = Some(name.clone());
+            }
+        }
+
+        columns
+    }
+
+    fn set_expression(&mut self, expr: &ast::SetExpression) -> Vec<ColumnLineage> {
+        match expr {
+            ast::SetExpression::Values(values) => {
+                let mut columns: Vec<ColumnLineage> = Vec::new();
+
+                for row in &values.values {
+                    for (i, expr) in row.iter().enumerate() {
+                        let sources = self.expression_sources(expr);
+
+                        match columns.get_mut(i) {
+                            Some(column) => column.sources.extend(sources),
+                            None => columns.push(ColumnLineage {
+                                name: None,
+                                sources,
+                            }),
+                        }
+                    }
+                }
+
+                columns
+            }
+            ast::SetExpression::Query(query) => self.query_set_expression(query),
+            ast::SetExpression::Op(op) => {
+                let mut columns = self.set_expression(&op.left);
+                let right = self.set_expression(&op.right);
+
+                for (column, other) in columns.iter_mut().zip(right) {
+                    column.sources.extend(other.sources);
+                }
+
+                columns
+            }
+        }
+    }
+
+    fn query_set_expression(&mut self, query: &ast::QuerySetExpression) -> Vec<ColumnLineage> {
+        self.scopes.push(Scope::default());
+
+        for table in &query.from {
+            self.table_expression(table);
+        }
+
+        if let Some(expr) = &query.where_expr {
+            self.expression_sources(expr);
+        }
+
+        if let Some(group_by) = &query.group_by {
+            for expr in &group_by.groupings {
+                self.expression_sources(expr);
+            }
+
+            if let Some(expr) = &group_by.having {
+                self.expression_sources(expr);
+            }
+        }
+
+        let columns = match &query.columns {
+            ast::ResultColumns::All => {
+                let scope = self.scopes.last().unwrap();
+
+                scope
+                    .relations
+                    .iter()
+                    .flat_map(|(_, relation)| relation_columns(relation))
+                    .collect()
+            }
+            ast::ResultColumns::List(columns) => columns
+                .iter()
+                .flat_map(|column| self.result_column(column))
+                .collect(),
+        };
+
+        self.scopes.pop();
+
+        columns
+    }
+
+    fn result_column(&mut self, column: &ast::ResultColumn) -> Vec<ColumnLineage> {
+        match column {
+            ast::ResultColumn::AllFrom(name) => {
+                let scope = self.scopes.last().unwrap();
+
+                match find_relation(scope, name) {
+                    Some(relation) => relation_columns(relation),
+                    None => vec![ColumnLineage {
+                        name: Some(star()),
+                        sources: vec![ColumnReference {
+                            table: self.qualify(std::slice::from_ref(name)),
+                            column: star(),
+                        }]
+                        .into_iter()
+                        .collect(),
+                    }],
+                }
+            }
+            ast::ResultColumn::Expr(column) => {
+                let name = match (&column.rename, &column.expr) {
+                    (Some(rename), _) => Some(rename.clone()),
+                    (None, ast::Expression::QualifiedIdentifier(identifier)) => {
+                        identifier.identifiers.last().cloned()
+                    }
+                    _ => None,
+                };
+
+                vec![ColumnLineage {
+                    name,
+                    sources: self.expression_sources(&column.expr),
+                }]
+            }
+        }
+    }
+
+    fn table_expression(&mut self, table: &ast::TableExpression) {
+        match table {
+            ast::TableExpression::Named(named) => {
+                let cte = match named.name.len() {
+                    1 => self.lookup_cte(&named.name[0]),
+                    _ => None,
+                };
+
+                let relation = match cte {
+                    Some(columns) => Relation::Derived(columns),
+                    None => {
+                        let name = self.qualify(&named.name);
+                        self.reads.insert(name.clone());
+                        Relation::Table(name)
+                    }
+                };
+
+                let alias = named.alias.clone().or_else(|| named.name.last().cloned());
+
+                self.scopes
+                    .last_mut()
+                    .unwrap()
+                    .relations
+                    .push((alias, relation));
+            }
+            ast::TableExpression::Select(select) => {
+                // a derived table can't see the other tables of the FROM clause
+                let scope = self.scopes.pop().unwrap();
+                let columns = self.select_statement(&select.select);
+                self.scopes.push(scope);
+
+                self.scopes
+                    .last_mut()
+                    .unwrap()
+                    .relations
+                    .push((select.alias.clone(), Relation::Derived(columns)));
+            }
+            ast::TableExpression::Join(join) => {
+                let start = self.scopes.last().unwrap().relations.len();
+                self.table_expression(&join.left);
+                let middle = self.scopes.last().unwrap().relations.len();
+                self.table_expression(&join.right);
+                let end = self.scopes.last().unwrap().relations.len();
+
+                match &join.constraint {
+                    ast::JoinConstraint::Expr(expr) => {
+                        self.expression_sources(expr);
+                    }
+                    ast::JoinConstraint::Columns(columns) => {
+                        let scope = self.scopes.last_mut().unwrap();
+
+                        for column in &columns.columns {
+                            let left = (start..middle)
+                                .rev()
+                                .find(|i| relation_has_column(&scope.relations[*i].1, column));
+                            let right = (middle..end)
+                                .find(|i| relation_has_column(&scope.relations[*i].1, column));
+
+                            let relations = left.into_iter().chain(right).collect();
+                            scope.using.push((column.clone(), relations));
+                        }
+                    }
+                }
+            }
+            ast::TableExpression::Error(_) => {}
+        }
+    }
+
+    /// The table columns the value of the expression derives from
+    fn expression_sources(&mut self, expr: &ast::Expression) -> BTreeSet<ColumnReference> {
+        let mut collector = SourceCollector {
+            state: self,
+            sources: BTreeSet::new(),
+        };
+
+        collector.visit_expression(expr);
+        collector.sources
+    }
+
+    fn resolve(&self, identifiers: &[Name]) -> Vec<ColumnReference> {
+        match identifiers {
+            [column] => self.resolve_unqualified(column),
+            [qualifier, column] => {
+                for scope in self.scopes.iter().rev() {
+                    if let Some(relation) = find_relation(scope, qualifier) {
+                        return relation_column(relation, column);
+                    }
+                }
+
+                vec![ColumnReference {
+                    table: self.qualify(std::slice::from_ref(qualifier)),
+                    column: column.clone(),
+                }]
+            }
+            [schema, table, column] => vec![ColumnReference {
+                table: vec![schema.clone(), table.clone()],
+                column: column.clone(),
+            }],
+            _ => vec![unresolved(identifiers.last().unwrap())],
+        }
+    }
+
+    fn resolve_unqualified(&self, column: &Name) -> Vec<ColumnReference> {
+        for scope in self.scopes.iter().rev() {
+            if let Some((_, relations)) = scope.using.iter().find(|(name, _)| name == column) {
+                if !relations.is_empty() {
+                    return relations
+                        .iter()
+                        .flat_map(|i| relation_column(&scope.relations[*i].1, column))
+                        .collect();
+                }
+            }
+
+            let derived = scope
+                .relations
+                .iter()
+                .filter(|(_, relation)| match relation {
+                    Relation::Derived(columns) => {
+                        columns.iter().any(|c| c.name.as_ref() == Some(column))
+                    }
+                    Relation::Table(_) => false,
+                })
+                .collect::<Vec<_>>();
+            let tables = scope
+                .relations
+                .iter()
+                .filter(|(_, relation)| match relation {
+                    Relation::Table(_) => true,
+                    Relation::Derived(_) => false,
+                })
+                .collect::<Vec<_>>();
+
+            match (derived.len(), tables.len()) {
+                (0, 0) if scope.relations.is_empty() => continue,
+                (1, 0) => return relation_column(&derived[0].1, column),
+                (0, 1) => return relation_column(&tables[0].1, column),
+                _ => return vec![unresolved(column)],
+            }
+        }
+
+        vec![unresolved(column)]
+    }
+}
+
+fn find_relation<'s>(scope: &'s Scope, name: &Name) -> Option<&'s Relation> {
+    scope
+        .relations
+        .iter()
+        .rev()
+        .find(|(alias, _)| alias.as_ref() == Some(name))
+        .map(|(_, relation)| relation)
+}
+
+fn relation_has_column(relation: &Relation, column: &Name) -> bool {
+    match relation {
+        Relation::Table(_) => true,
+        Relation::Derived(columns) => columns.iter().any(|c| c.name.as_ref() == Some(column)),
+    }
+}
+
+/// The columns of a relation, as returned by `SELECT *`
+fn relation_columns(relation: &Relation) -> Vec<ColumnLineage> {
+    match relation {
+        Relation::Table(table) => vec![ColumnLineage {
+            name: Some(star()),
+            sources: vec![ColumnReference {
+                table: table.clone(),
+                column: star(),
+            }]
+            .into_iter()
+            .collect(),
+        }],
+        Relation::Derived(columns) => columns.clone(),
+    }
+}
+
+fn relation_column(relation: &Relation, column: &Name) -> Vec<ColumnReference> {
+    match relation {
+        Relation::Table(table) => vec![ColumnReference {
+            table: table.clone(),
+            column: column.clone(),
+        }],
+        Relation::Derived(columns) => {
+            if let Some(found) = columns.iter().find(|c| c.name.as_ref() == Some(column)) {
+                return found.sources.iter().cloned().collect();
+            }
+
+            // the column may come from a table selected with `*` in the nested query
+            let from_star = columns
+                .iter()
+                .filter(|c| c.name == Some(star()))
+                .flat_map(|c| c.sources.iter())
+                .map(|source| ColumnReference {
+                    table: source.table.clone(),
+                    column: column.clone(),
+                })
+                .collect::<Vec<_>>();
+
+            match from_star.is_empty() {
+                true => vec![unresolved(column)],
+                false => from_star,
+            }
+        }
+    }
+}
+
+struct SourceCollector<'s, 'a> {
+    state: &'s mut State<'a>,
+    sources: BTreeSet<ColumnReference>,
+}
+
+impl<'ast, 's, 'a> Visitor<'ast> for SourceCollector<'s, 'a> {
+    fn visit_qualified_identifier(&mut self, node: &'ast ast::QualifiedIdentifierExpression) {
+        self.sources.extend(self.state.resolve(&node.identifiers));
+    }
+
+    fn visit_select_statement(&mut self, node: &'ast ast::SelectStatement) {
+        for column in self.state.select_statement(node) {
+            self.sources.extend(column.sources);
+        }
+    }
+
+    fn visit_set_specification(&mut self, node: &'ast ast::SetSpecification) {
+        match node {
+            ast::SetSpecification::Name(name) => {
+                let name = self.state.qualify(name);
+                self.state.reads.insert(name);
+            }
+            _ => visit::walk_set_specification(self, node),
+        }
+    }
+}
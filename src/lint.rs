@@ -0,0 +1,525 @@
+// MIT License
+//
+// Copyright (c) 2019 Daniele Esposti
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::fmt;
+
+use dict_derive::IntoPyObject;
+use pyo3::{IntoPy, PyObject, Python};
+
+use super::ast;
+use super::symbols::{Name, Span};
+use super::visit::{self, Visitor};
+
+/// How serious a problem reported by a rule is
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum Severity {
+    /// something worth a second look, which may well be intended
+    Info,
+
+    /// a likely mistake or a practice to avoid
+    Warning,
+
+    /// the statement doesn't do what it says
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Severity::Info => write!(f, "info"),
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+impl IntoPy<PyObject> for Severity {
+    fn into_py(self, py: Python) -> PyObject {
+        IntoPy::<PyObject>::into_py(self.to_string(), py)
+    }
+}
+
+/// A problem found by a rule
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Violation {
+    pub message: String,
+
+    /// where the problem is in the statement, when it can be located
+    pub span: Option<Span>,
+}
+
+impl Violation {
+    pub fn new(message: &str, span: Option<Span>) -> Violation {
+        Violation {
+            message: message.to_string(),
+            span,
+        }
+    }
+}
+
+/// A problem found by the linter, tagged with the rule which reported it
+#[derive(IntoPyObject, Debug, PartialEq, Eq, Clone)]
+pub struct Diagnostic {
+    /// the name of the rule
+    pub rule: String,
+
+    pub severity: Severity,
+
+    pub message: String,
+
+    /// where the problem is in the statement, as byte offsets; `None` when it cannot be located
+    pub span: Option<Span>,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "{}: {} [{}]", self.severity, self.message, self.rule)
+    }
+}
+
+/// A check of a statement
+pub trait Rule {
+    /// the name identifying the rule, like `select-star`
+    fn name(&self) -> &'static str;
+
+    fn severity(&self) -> Severity;
+
+    /// the problems found in the statement
+    fn check(&self, statement: &ast::SqlStatement) -> Vec<Violation>;
+}
+
+/// Run a set of rules over statements, the built-in ones by default
+pub struct Linter {
+    rules: Vec<Box<dyn Rule>>,
+}
+
+impl Default for Linter {
+    fn default() -> Linter {
+        Linter::new()
+    }
+}
+
+impl Linter {
+    pub fn new() -> Linter {
+        Linter {
+            rules: vec![
+                Box::new(SelectStar),
+                Box::new(ImplicitCrossJoin),
+                Box::new(NotInSubquery),
+                Box::new(UnionDistinct),
+                Box::new(UnaliasedSubquery),
+                Box::new(LimitWithoutOrderBy),
+                Box::new(NullComparison),
+            ],
+        }
+    }
+
+    /// A linter without any rule
+    pub fn empty() -> Linter {
+        Linter { rules: Vec::new() }
+    }
+
+    pub fn with_rule<R: Rule + 'static>(mut self, rule: R) -> Linter {
+        self.rules.push(Box::new(rule));
+        self
+    }
+
+    /// Remove a rule by name; an error if there's no such rule
+    pub fn without_rule(mut self, name: &str) -> Result<Linter, String> {
+        let count = self.rules.len();
+        self.rules.retain(|rule| rule.name() != name);
+
+        if self.rules.len() == count {
+            return Err(format!("unknown rule `{}`", name));
+        }
+
+        Ok(self)
+    }
+
+    pub fn rule_names(&self) -> Vec<&'static str> {
+        self.rules.iter().map(|rule| rule.name()).collect()
+    }
+
+    /// The problems found by every rule, in the order they appear in the statement; the ones
+    /// which cannot be located come first
+    pub fn lint(&self, statement: &ast::SqlStatement) -> Vec<Diagnostic> {
+        let mut diagnostics: Vec<_> = self
+            .rules
+            .iter()
+            .flat_map(|rule| {
+                rule.check(statement)
+                    .into_iter()
+                    .map(move |violation| Diagnostic {
+                        rule: rule.name().to_string(),
+                        severity: rule.severity(),
+                        message: violation.message,
+                        span: violation.span,
+                    })
+            })
+            .collect();
+
+        diagnostics.sort_by_key(|diagnostic| diagnostic.span.map(|span| span.start));
+        diagnostics
+    }
+}
+
+/// Lint a statement with the built-in rules
+pub fn lint(statement: &ast::SqlStatement) -> Vec<Diagnostic> {
+    Linter::new().lint(statement)
+}
+
+/// `SELECT *` and `SELECT t.*`, whose result changes with the columns of the tables
+pub struct SelectStar;
+
+impl Rule for SelectStar {
+    fn name(&self) -> &'static str {
+        "select-star"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, statement: &ast::SqlStatement) -> Vec<Violation> {
+        let mut violations = Vec::new();
+
+        for node in nodes(statement) {
+            let query = match node {
+                Node::Set(ast::SetExpression::Query(query)) => query,
+                _ => continue,
+            };
+
+            match &query.columns {
+                ast::ResultColumns::All => violations.push(Violation::new(
+                    "SELECT * depends on the columns of the tables, list them instead",
+                    locate(|locator| locator.visit_query_set_expression(query)),
+                )),
+                ast::ResultColumns::List(columns) => {
+                    for column in columns {
+                        if let ast::ResultColumn::AllFrom(name) = column {
+                            let message = format!(
+                                "{}.* depends on the columns of the table, list them instead",
+                                name
+                            );
+                            violations.push(Violation::new(&message, name.span()));
+                        }
+                    }
+                }
+            }
+        }
+
+        violations
+    }
+}
+
+/// More than one table in a FROM clause, rather than an explicit join
+pub struct ImplicitCrossJoin;
+
+impl Rule for ImplicitCrossJoin {
+    fn name(&self) -> &'static str {
+        "implicit-cross-join"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, statement: &ast::SqlStatement) -> Vec<Violation> {
+        nodes(statement)
+            .into_iter()
+            .filter_map(|node| match node {
+                Node::Set(ast::SetExpression::Query(query)) if query.from.len() > 1 => {
+                    Some(Violation::new(
+                        "the tables separated by commas are cross joined, use JOIN instead",
+                        locate(|locator| locator.visit_table_expression(&query.from[1])),
+                    ))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// `NOT IN` a subquery, which is never true when the subquery returns a NULL
+pub struct NotInSubquery;
+
+impl Rule for NotInSubquery {
+    fn name(&self) -> &'static str {
+        "not-in-subquery"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, statement: &ast::SqlStatement) -> Vec<Violation> {
+        nodes(statement)
+            .into_iter()
+            .filter_map(|node| match node {
+                Node::Expression(ast::Expression::Unary(ast::UnaryExpression {
+                    op: ast::UnaryOperator::Not,
+                    expr,
+                })) => match expr.as_ref() {
+                    ast::Expression::In(ast::InExpression {
+                        expr,
+                        set: ast::SetSpecification::Select(_),
+                    }) => Some(Violation::new(
+                        "NOT IN matches no rows if the subquery returns a NULL, filter them out",
+                        locate(|locator| locator.visit_expression(expr)),
+                    )),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// `UNION`, which removes the duplicate rows, when `UNION ALL` may be intended
+pub struct UnionDistinct;
+
+impl Rule for UnionDistinct {
+    fn name(&self) -> &'static str {
+        "union-distinct"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Info
+    }
+
+    fn check(&self, statement: &ast::SqlStatement) -> Vec<Violation> {
+        nodes(statement)
+            .into_iter()
+            .filter_map(|node| match node {
+                Node::Set(ast::SetExpression::Op(op)) if op.op == ast::SetOperator::Union => {
+                    Some(Violation::new(
+                        "UNION removes the duplicate rows, use UNION ALL to keep them",
+                        locate(|locator| locator.visit_set_expression(&op.right)),
+                    ))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// A subquery in a FROM clause without an alias
+pub struct UnaliasedSubquery;
+
+impl Rule for UnaliasedSubquery {
+    fn name(&self) -> &'static str {
+        "unaliased-subquery"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, statement: &ast::SqlStatement) -> Vec<Violation> {
+        nodes(statement)
+            .into_iter()
+            .filter_map(|node| match node {
+                Node::Table(ast::TableExpression::Select(table)) if table.alias.is_none() => {
+                    Some(Violation::new(
+                        "the subquery has no alias to refer to its columns",
+                        locate(|locator| locator.visit_select_statement(&table.select)),
+                    ))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// `LIMIT` without `ORDER BY`, which returns any of the rows
+pub struct LimitWithoutOrderBy;
+
+impl Rule for LimitWithoutOrderBy {
+    fn name(&self) -> &'static str {
+        "limit-without-order-by"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, statement: &ast::SqlStatement) -> Vec<Violation> {
+        nodes(statement)
+            .into_iter()
+            .filter_map(|node| match node {
+                Node::Select(select) if select.limit.is_some() && select.order_by.is_empty() => {
+                    Some(Violation::new(
+                        "LIMIT without ORDER BY returns arbitrary rows",
+                        locate(|locator| locator.visit_select_statement(select)),
+                    ))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// `= NULL` and `!= NULL`, which are never true
+pub struct NullComparison;
+
+impl Rule for NullComparison {
+    fn name(&self) -> &'static str {
+        "null-comparison"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn check(&self, statement: &ast::SqlStatement) -> Vec<Violation> {
+        let is_null =
+            |expr: &ast::Expression| *expr == ast::Expression::Literal(ast::Literal::Null);
+
+        nodes(statement)
+            .into_iter()
+            .filter_map(|node| match node {
+                Node::Expression(ast::Expression::Comparison(comparison))
+                    if (comparison.op == ast::ComparisonOperator::Equal
+                        || comparison.op == ast::ComparisonOperator::NotEqual)
+                        && (is_null(&comparison.left) || is_null(&comparison.right)) =>
+                {
+                    let message = if comparison.op == ast::ComparisonOperator::Equal {
+                        "comparing with = NULL is never true, use IS NULL"
+                    } else {
+                        "comparing with != NULL is never true, use IS NOT NULL"
+                    };
+
+                    Some(Violation::new(
+                        message,
+                        locate(|locator| {
+                            locator.visit_expression(&comparison.left);
+                            locator.visit_expression(&comparison.right);
+                        }),
+                    ))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// A node the built-in rules look at
+enum Node<'ast> {
+    Select(&'ast ast::SelectStatement),
+    Set(&'ast ast::SetExpression),
+    Table(&'ast ast::TableExpression),
+    Expression(&'ast ast::Expression),
+}
+
+/// Every node of a statement the built-in rules look at, in tree order
+fn nodes<'ast>(statement: &'ast ast::SqlStatement) -> Vec<Node<'ast>> {
+    let mut collector = NodeCollector { nodes: Vec::new() };
+    collector.visit_sql_statement(statement);
+    collector.nodes
+}
+
+struct NodeCollector<'ast> {
+    nodes: Vec<Node<'ast>>,
+}
+
+impl<'ast> Visitor<'ast> for NodeCollector<'ast> {
+    fn visit_select_statement(&mut self, node: &'ast ast::SelectStatement) {
+        self.nodes.push(Node::Select(node));
+        visit::walk_select_statement(self, node);
+    }
+
+    fn visit_set_expression(&mut self, node: &'ast ast::SetExpression) {
+        self.nodes.push(Node::Set(node));
+        visit::walk_set_expression(self, node);
+    }
+
+    fn visit_table_expression(&mut self, node: &'ast ast::TableExpression) {
+        self.nodes.push(Node::Table(node));
+        visit::walk_table_expression(self, node);
+    }
+
+    fn visit_expression(&mut self, node: &'ast ast::Expression) {
+        self.nodes.push(Node::Expression(node));
+        visit::walk_expression(self, node);
+    }
+}
+
+/// The span covering the names of the visited nodes; the names are the only nodes which know
+/// where they are in the source
+fn locate<F: FnOnce(&mut Locator)>(visit: F) -> Option<Span> {
+    let mut locator = Locator { span: None };
+    visit(&mut locator);
+    locator.span
+}
+
+struct Locator {
+    span: Option<Span>,
+}
+
+impl Locator {
+    fn add<'a, I: IntoIterator<Item = &'a Name>>(&mut self, names: I) {
+        for span in names.into_iter().filter_map(Name::span) {
+            self.span = Some(match self.span {
+                Some(other) => other.merge(span),
+                None => span,
+            });
+        }
+    }
+}
+
+impl<'ast> Visitor<'ast> for Locator {
+    fn visit_common_table_expression(&mut self, node: &'ast ast::CommonTableExpression) {
+        self.add(Some(&node.identifier));
+        visit::walk_common_table_expression(self, node);
+    }
+
+    fn visit_named_table_expression(&mut self, node: &'ast ast::NamedTableExpression) {
+        self.add(&node.name);
+        self.add(&node.alias);
+    }
+
+    fn visit_select_table_expression(&mut self, node: &'ast ast::SelectTableExpression) {
+        self.add(&node.alias);
+        visit::walk_select_table_expression(self, node);
+    }
+
+    fn visit_result_column(&mut self, node: &'ast ast::ResultColumn) {
+        match node {
+            ast::ResultColumn::AllFrom(name) => self.add(Some(name)),
+            ast::ResultColumn::Expr(column) => self.add(&column.rename),
+        }
+
+        visit::walk_result_column(self, node);
+    }
+
+    fn visit_expression(&mut self, node: &'ast ast::Expression) {
+        if let ast::Expression::Unknown(unknown) = node {
+            self.add(&unknown.name);
+        }
+
+        visit::walk_expression(self, node);
+    }
+
+    fn visit_qualified_identifier(&mut self, node: &'ast ast::QualifiedIdentifierExpression) {
+        self.add(&node.identifiers);
+    }
+}
@@ -0,0 +1,395 @@
+// MIT License
+//
+// Copyright (c) 2019 Daniele Esposti
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::HashMap;
+
+use dict_derive::IntoPyObject;
+
+use super::ast;
+use super::symbols::Name;
+use super::typecheck::{common_type, TypeChecker};
+use super::types::DataType;
+use super::visit::{self, Visitor, VisitorMut};
+
+/// A parameter of a statement, with the type inferred from where it's used
+#[derive(IntoPyObject, Debug, PartialEq, Eq, Clone)]
+pub struct ParameterType {
+    pub parameter: ast::Parameter,
+
+    /// `Generic` when the parameter is used where any type fits
+    pub data_type: DataType,
+}
+
+/// Number the positional parameters from 1, in order of appearance
+pub(crate) fn number_positional(mut statement: ast::SqlStatement) -> ast::SqlStatement {
+    let mut numbering = Numbering { count: 0 };
+    numbering.visit_sql_statement_mut(&mut statement);
+    statement
+}
+
+struct Numbering {
+    count: usize,
+}
+
+impl VisitorMut for Numbering {
+    fn visit_parameter_mut(&mut self, node: &mut ast::Parameter) {
+        if let ast::Parameter::Positional(index) = node {
+            self.count += 1;
+            *index = self.count;
+        }
+    }
+}
+
+/// Replace the parameters of a statement with the given values: the positional parameters
+/// take the values in order, the named and variable ones the value of their name, compared
+/// case-insensitively. It's an error if a parameter has no value.
+pub fn substitute(
+    statement: &ast::SqlStatement,
+    positional: &[ast::Literal],
+    named: &HashMap<String, ast::Literal>,
+) -> Result<ast::SqlStatement, String> {
+    let mut substitution = Substitution {
+        positional,
+        named,
+        error: None,
+    };
+    let mut statement = statement.clone();
+
+    substitution.visit_sql_statement_mut(&mut statement);
+
+    match substitution.error {
+        Some(error) => Err(error),
+        None => Ok(statement),
+    }
+}
+
+struct Substitution<'v> {
+    positional: &'v [ast::Literal],
+    named: &'v HashMap<String, ast::Literal>,
+
+    /// the first parameter without a value
+    error: Option<String>,
+}
+
+impl<'v> Substitution<'v> {
+    fn value(&self, parameter: &ast::Parameter) -> Result<ast::Literal, String> {
+        let value = match parameter {
+            ast::Parameter::Positional(index) => self.positional.get(index - 1),
+            ast::Parameter::Named(name) | ast::Parameter::Variable(name) => self
+                .named
+                .iter()
+                .find(|(key, _)| *name == key[..])
+                .map(|(_, value)| value),
+        };
+
+        match (value, parameter) {
+            (Some(value), _) => Ok(value.clone()),
+            (None, ast::Parameter::Positional(index)) => {
+                Err(format!("no value for the positional parameter {}", index))
+            }
+            (None, _) => Err(format!("no value for the parameter {}", parameter)),
+        }
+    }
+}
+
+impl<'v> VisitorMut for Substitution<'v> {
+    fn visit_expression_mut(&mut self, node: &mut ast::Expression) {
+        if let ast::Expression::Parameter(parameter) = node {
+            match self.value(parameter) {
+                Ok(value) => *node = ast::Expression::Literal(value),
+                Err(error) => {
+                    self.error.get_or_insert(error);
+                }
+            }
+
+            return;
+        }
+
+        visit::walk_expression_mut(self, node);
+    }
+}
+
+/// List the parameters of a statement in order of appearance, each once, with the type
+/// inferred from the expressions they are used in
+pub fn parameters(statement: &ast::SqlStatement) -> Vec<ParameterType> {
+    Inference::new(TypeChecker::new()).infer(statement)
+}
+
+/// List the parameters like `parameters()`, using the given function to look up the type of
+/// the columns they are compared or assigned to
+pub fn parameters_with_column_types<F>(
+    statement: &ast::SqlStatement,
+    resolver: F,
+) -> Vec<ParameterType>
+where
+    F: Fn(&[Name]) -> Option<DataType>,
+{
+    Inference::new(TypeChecker::new().with_column_types(resolver)).infer(statement)
+}
+
+struct Inference<'r> {
+    checker: TypeChecker<'r>,
+    parameters: Vec<ParameterType>,
+}
+
+impl<'r> Inference<'r> {
+    fn new(checker: TypeChecker<'r>) -> Inference<'r> {
+        Inference {
+            checker,
+            parameters: Vec::new(),
+        }
+    }
+
+    fn infer(mut self, statement: &ast::SqlStatement) -> Vec<ParameterType> {
+        // the parameters are listed first, so that they keep their order whatever expression
+        // their type is inferred from
+        let mut collector = Collector {
+            parameters: &mut self.parameters,
+        };
+        collector.visit_sql_statement(statement);
+
+        self.visit_sql_statement(statement);
+        self.parameters
+    }
+
+    /// The type of an expression, ignoring the type errors
+    fn type_of(&mut self, expr: &ast::Expression) -> DataType {
+        self.checker.annotate(expr).data_type
+    }
+
+    /// The common type of the expressions which are not parameters
+    fn common_type_of(&mut self, exprs: &[&ast::Expression]) -> DataType {
+        exprs
+            .iter()
+            .filter(|expr| !is_parameter(expr))
+            .map(|expr| self.type_of(expr))
+            .fold(DataType::Generic, |result, data_type| {
+                common_type(result, data_type).unwrap_or(result)
+            })
+    }
+
+    /// Narrow the type of a parameter, if the expression is one; the first type inferred wins
+    /// when the uses of a parameter don't agree
+    fn expect(&mut self, expr: &ast::Expression, data_type: DataType) {
+        if let ast::Expression::Parameter(parameter) = expr {
+            for found in &mut self.parameters {
+                if found.parameter == *parameter {
+                    found.data_type =
+                        common_type(found.data_type, data_type).unwrap_or(found.data_type);
+                }
+            }
+        }
+    }
+
+    /// Expect each expression to be of the common type of the others
+    fn expect_common(&mut self, exprs: &[&ast::Expression]) {
+        let data_type = self.common_type_of(exprs);
+
+        for expr in exprs {
+            self.expect(expr, data_type);
+        }
+    }
+
+    fn expect_operands(&mut self, expr: &ast::Expression) {
+        use super::types::DataType::*;
+
+        match expr {
+            ast::Expression::Unary(unary) => match unary.op {
+                ast::UnaryOperator::Negate => self.expect(&unary.expr, Numeric),
+                ast::UnaryOperator::Not => self.expect(&unary.expr, Logical),
+                ast::UnaryOperator::IsNull => {}
+            },
+            ast::Expression::Binary(binary) => match binary.op {
+                ast::BinaryOperator::And | ast::BinaryOperator::Or => {
+                    self.expect(&binary.left, Logical);
+                    self.expect(&binary.right, Logical);
+                }
+                ast::BinaryOperator::Concat => {
+                    self.expect(&binary.left, Varchar);
+                    self.expect(&binary.right, Varchar);
+                }
+                ast::BinaryOperator::Multiply | ast::BinaryOperator::Divide => {
+                    self.expect(&binary.left, Numeric);
+                    self.expect(&binary.right, Numeric);
+                }
+                ast::BinaryOperator::Add | ast::BinaryOperator::Subtract => {
+                    // a number of days is added to or subtracted from dates and timestamps
+                    let left = self.type_of(&binary.left);
+                    let right = self.type_of(&binary.right);
+
+                    if right != Generic {
+                        self.expect(&binary.left, Numeric);
+                    }
+
+                    if left != Generic {
+                        self.expect(&binary.right, Numeric);
+                    }
+                }
+            },
+            ast::Expression::Comparison(comparison) => {
+                if comparison.op == ast::ComparisonOperator::Like {
+                    self.expect(&comparison.left, Varchar);
+                    self.expect(&comparison.right, Varchar);
+                } else {
+                    self.expect_common(&[&comparison.left, &comparison.right]);
+                }
+            }
+            ast::Expression::In(in_expr) => {
+                if let ast::SetSpecification::List(exprs) = &in_expr.set {
+                    let mut all = vec![in_expr.expr.as_ref()];
+                    all.extend(exprs);
+                    self.expect_common(&all);
+                }
+            }
+            ast::Expression::Between(between) => {
+                self.expect_common(&[&between.expr, &between.lower, &between.upper])
+            }
+            ast::Expression::Case(case) => {
+                match &case.expr {
+                    Some(expr) => {
+                        let mut operands = vec![expr.as_ref()];
+                        operands.extend(case.when_part.iter().map(|when| &when.guard));
+                        self.expect_common(&operands);
+                    }
+                    None => {
+                        for when in &case.when_part {
+                            self.expect(&when.guard, Logical);
+                        }
+                    }
+                }
+
+                let mut results: Vec<_> = case.when_part.iter().map(|when| &when.body).collect();
+                results.extend(case.else_part.as_deref());
+                self.expect_common(&results);
+            }
+            ast::Expression::Coalesce(coalesce) => {
+                self.expect_common(&coalesce.exprs.iter().collect::<Vec<_>>())
+            }
+            ast::Expression::Replace(replace) => {
+                self.expect(&replace.string, Varchar);
+                self.expect(&replace.search_string, Varchar);
+
+                if let Some(expr) = &replace.replace_string {
+                    self.expect(expr, Varchar);
+                }
+            }
+            ast::Expression::Substring(substring) => {
+                self.expect(&substring.string, Varchar);
+                self.expect(&substring.position, Numeric);
+
+                if let Some(expr) = &substring.length {
+                    self.expect(expr, Numeric);
+                }
+            }
+            ast::Expression::ToDate(to_date) => {
+                self.expect(&to_date.string, Varchar);
+
+                if let Some(expr) = &to_date.format {
+                    self.expect(expr, Varchar);
+                }
+            }
+            ast::Expression::Power(power) => {
+                self.expect(&power.base, Numeric);
+                self.expect(&power.exponent, Numeric);
+            }
+            ast::Expression::Concat(concat) => {
+                for expr in &concat.exprs {
+                    self.expect(expr, Varchar);
+                }
+            }
+            ast::Expression::Sum(sum) => self.expect(&sum.expr, Numeric),
+            ast::Expression::Right(right) => {
+                self.expect(&right.string, Varchar);
+                self.expect(&right.length, Numeric);
+            }
+            _ => {}
+        }
+    }
+
+    /// The type of a column of the statement's table
+    fn column_type(&mut self, column: &Name) -> DataType {
+        let identifier = ast::QualifiedIdentifierExpression {
+            identifiers: vec![column.clone()],
+        };
+
+        self.type_of(&ast::Expression::QualifiedIdentifier(identifier))
+    }
+}
+
+impl<'ast, 'r> Visitor<'ast> for Inference<'r> {
+    fn visit_expression(&mut self, node: &'ast ast::Expression) {
+        self.expect_operands(node);
+        visit::walk_expression(self, node);
+    }
+
+    fn visit_insert_statement(&mut self, node: &'ast ast::InsertStatement) {
+        if let (Some(columns), ast::SetExpression::Values(values)) = (&node.columns, &node.source) {
+            for row in &values.values {
+                for (column, expr) in columns.iter().zip(row) {
+                    let data_type = self.column_type(column);
+                    self.expect(expr, data_type);
+                }
+            }
+        }
+
+        visit::walk_insert_statement(self, node);
+    }
+
+    fn visit_assignment(&mut self, node: &'ast ast::Assignment) {
+        if let [column] = node.columns.as_slice() {
+            let data_type = self.column_type(column);
+            self.expect(&node.expr, data_type);
+        }
+
+        visit::walk_assignment(self, node);
+    }
+
+    fn visit_limit(&mut self, node: &'ast ast::Limit) {
+        self.expect(&node.number_rows, DataType::Numeric);
+
+        if let Some(offset) = &node.offset_value {
+            self.expect(offset, DataType::Numeric);
+        }
+
+        visit::walk_limit(self, node);
+    }
+}
+
+/// Collect the distinct parameters, with a `Generic` type
+struct Collector<'p> {
+    parameters: &'p mut Vec<ParameterType>,
+}
+
+impl<'ast, 'p> Visitor<'ast> for Collector<'p> {
+    fn visit_parameter(&mut self, node: &'ast ast::Parameter) {
+        if self.parameters.iter().all(|found| found.parameter != *node) {
+            self.parameters.push(ParameterType {
+                parameter: node.clone(),
+                data_type: DataType::Generic,
+            });
+        }
+    }
+}
+
+fn is_parameter(expr: &ast::Expression) -> bool {
+    matches!(expr, ast::Expression::Parameter(_))
+}
@@ -0,0 +1,485 @@
+// MIT License
+//
+// Copyright (c) 2019 Daniele Esposti
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::fmt;
+
+use dict_derive::IntoPyObject;
+use pyo3::{IntoPy, PyObject, Python};
+
+use super::ast;
+use super::symbols::Name;
+use super::visit::{self, Visitor};
+
+/// Past this many conjuncts a disjunction is kept as it is instead of being distributed over the
+/// conjunctions it contains
+const MAX_CONJUNCTS: usize = 64;
+
+/// The clause a predicate comes from
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Clause {
+    Where,
+    Join,
+    Having,
+}
+
+impl fmt::Display for Clause {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Clause::Where => write!(f, "where"),
+            Clause::Join => write!(f, "join"),
+            Clause::Having => write!(f, "having"),
+        }
+    }
+}
+
+impl IntoPy<PyObject> for Clause {
+    fn into_py(self, py: Python) -> PyObject {
+        IntoPy::<PyObject>::into_py(self.to_string(), py)
+    }
+}
+
+/// How a predicate tests a column
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Operator {
+    Equal,
+    NotEqual,
+    LessThan,
+    LessEqual,
+    GreaterThan,
+    GreaterEqual,
+    Like,
+    NotLike,
+    In,
+    NotIn,
+    Between,
+    NotBetween,
+    IsNull,
+    IsNotNull,
+}
+
+impl Operator {
+    fn comparison(op: &ast::ComparisonOperator) -> Operator {
+        match op {
+            ast::ComparisonOperator::Equal => Operator::Equal,
+            ast::ComparisonOperator::NotEqual => Operator::NotEqual,
+            ast::ComparisonOperator::LessThan => Operator::LessThan,
+            ast::ComparisonOperator::LessEqual => Operator::LessEqual,
+            ast::ComparisonOperator::GreaterThan => Operator::GreaterThan,
+            ast::ComparisonOperator::GreaterEqual => Operator::GreaterEqual,
+            ast::ComparisonOperator::Like => Operator::Like,
+        }
+    }
+
+    /// The operator testing the same with the operands swapped, `LIKE` has none
+    fn swapped(self) -> Option<Operator> {
+        match self {
+            Operator::LessThan => Some(Operator::GreaterThan),
+            Operator::LessEqual => Some(Operator::GreaterEqual),
+            Operator::GreaterThan => Some(Operator::LessThan),
+            Operator::GreaterEqual => Some(Operator::LessEqual),
+            Operator::Equal | Operator::NotEqual => Some(self),
+            _ => None,
+        }
+    }
+
+    fn negated(self) -> Operator {
+        match self {
+            Operator::Equal => Operator::NotEqual,
+            Operator::NotEqual => Operator::Equal,
+            Operator::LessThan => Operator::GreaterEqual,
+            Operator::LessEqual => Operator::GreaterThan,
+            Operator::GreaterThan => Operator::LessEqual,
+            Operator::GreaterEqual => Operator::LessThan,
+            Operator::Like => Operator::NotLike,
+            Operator::NotLike => Operator::Like,
+            Operator::In => Operator::NotIn,
+            Operator::NotIn => Operator::In,
+            Operator::Between => Operator::NotBetween,
+            Operator::NotBetween => Operator::Between,
+            Operator::IsNull => Operator::IsNotNull,
+            Operator::IsNotNull => Operator::IsNull,
+        }
+    }
+}
+
+impl fmt::Display for Operator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        let operator = match self {
+            Operator::Equal => "=",
+            Operator::NotEqual => "!=",
+            Operator::LessThan => "<",
+            Operator::LessEqual => "<=",
+            Operator::GreaterThan => ">",
+            Operator::GreaterEqual => ">=",
+            Operator::Like => "LIKE",
+            Operator::NotLike => "NOT LIKE",
+            Operator::In => "IN",
+            Operator::NotIn => "NOT IN",
+            Operator::Between => "BETWEEN",
+            Operator::NotBetween => "NOT BETWEEN",
+            Operator::IsNull => "IS NULL",
+            Operator::IsNotNull => "IS NOT NULL",
+        };
+
+        write!(f, "{}", operator)
+    }
+}
+
+impl IntoPy<PyObject> for Operator {
+    fn into_py(self, py: Python) -> PyObject {
+        IntoPy::<PyObject>::into_py(self.to_string(), py)
+    }
+}
+
+/// What a column is tested against: expressions which don't depend on the rows, like literals,
+/// parameters or `CURRENT_DATE - 1`
+#[derive(IntoPyObject, Debug, PartialEq, Eq, Clone)]
+#[dict(tag = "_type")]
+pub enum Value {
+    /// the operand of a comparison
+    #[dict(rename = "SingleValue")]
+    Single(#[dict(rename = "value")] ast::Expression),
+
+    /// the items of an `IN` list
+    #[dict(rename = "ListValue")]
+    List(#[dict(rename = "values")] Vec<ast::Expression>),
+
+    /// the bounds of a `BETWEEN`
+    #[dict(rename = "RangeValue")]
+    Range {
+        lower: ast::Expression,
+        upper: ast::Expression,
+    },
+}
+
+/// A filter on a column, one of the conjuncts of a condition
+#[derive(IntoPyObject, Debug, PartialEq, Eq, Clone)]
+pub struct Predicate {
+    /// the column as written in the condition
+    pub column: Vec<Name>,
+    pub operator: Operator,
+
+    /// `None` for `IS NULL` and `IS NOT NULL`
+    pub value: Option<Value>,
+    pub clause: Clause,
+}
+
+/// The filters on columns of the `WHERE`, `HAVING` and `JOIN ... ON` clauses of a statement and
+/// of the queries nested in it, in order of appearance. The conditions are split into their
+/// conjuncts, see `conjuncts`; a conjunct is a predicate if it tests a column against values
+/// which don't depend on the rows, in either operand order, or if it's a disjunction of
+/// equalities on the same column, which is the same as an `IN` list. The other conjuncts are
+/// left out.
+pub fn predicates(statement: &ast::SqlStatement) -> Vec<Predicate> {
+    let mut collector = Collector {
+        predicates: Vec::new(),
+    };
+
+    collector.visit_sql_statement(statement);
+    collector.predicates
+}
+
+/// Convert a condition into conjunctive normal form, returning its conjuncts: `NOT` is pushed
+/// down to the comparisons, which are negated, and `OR` is distributed over `AND`
+pub fn conjuncts(expr: &ast::Expression) -> Vec<ast::Expression> {
+    cnf(expr, false)
+        .into_iter()
+        .map(|disjuncts| {
+            let mut disjuncts = disjuncts.into_iter();
+            let first = disjuncts.next().unwrap();
+
+            disjuncts.fold(first, |left, right| {
+                ast::Expression::Binary(ast::BinaryExpression {
+                    op: ast::BinaryOperator::Or,
+                    left: Box::new(left),
+                    right: Box::new(right),
+                })
+            })
+        })
+        .collect()
+}
+
+/// The conjunction of disjunctions equivalent to a condition, negated if `negated` is set
+fn cnf(expr: &ast::Expression, negated: bool) -> Vec<Vec<ast::Expression>> {
+    let binary = match expr {
+        ast::Expression::Unary(ast::UnaryExpression {
+            op: ast::UnaryOperator::Not,
+            expr,
+        }) => return cnf(expr, !negated),
+        ast::Expression::Binary(binary) => binary,
+        _ => return vec![vec![negate(expr, negated)]],
+    };
+
+    // De Morgan's laws hold with NULL too
+    let conjunction = match (&binary.op, negated) {
+        (ast::BinaryOperator::And, false) | (ast::BinaryOperator::Or, true) => true,
+        (ast::BinaryOperator::Or, false) | (ast::BinaryOperator::And, true) => false,
+        _ => return vec![vec![negate(expr, negated)]],
+    };
+
+    let left = cnf(&binary.left, negated);
+    let right = cnf(&binary.right, negated);
+
+    if conjunction {
+        left.into_iter().chain(right).collect()
+    } else if left.len() * right.len() > MAX_CONJUNCTS {
+        vec![vec![negate(expr, negated)]]
+    } else {
+        left.iter()
+            .flat_map(|left| {
+                right
+                    .iter()
+                    .map(move |right| left.iter().chain(right).cloned().collect())
+            })
+            .collect()
+    }
+}
+
+/// A condition which isn't a conjunction or a disjunction, negated if `negated` is set
+fn negate(expr: &ast::Expression, negated: bool) -> ast::Expression {
+    match expr {
+        _ if !negated => expr.clone(),
+        ast::Expression::Comparison(comparison) => {
+            let op = match comparison.op {
+                ast::ComparisonOperator::Equal => ast::ComparisonOperator::NotEqual,
+                ast::ComparisonOperator::NotEqual => ast::ComparisonOperator::Equal,
+                ast::ComparisonOperator::LessThan => ast::ComparisonOperator::GreaterEqual,
+                ast::ComparisonOperator::LessEqual => ast::ComparisonOperator::GreaterThan,
+                ast::ComparisonOperator::GreaterThan => ast::ComparisonOperator::LessEqual,
+                ast::ComparisonOperator::GreaterEqual => ast::ComparisonOperator::LessThan,
+                ast::ComparisonOperator::Like => return not(expr),
+            };
+
+            ast::Expression::Comparison(ast::ComparisonExpression {
+                op,
+                left: comparison.left.clone(),
+                right: comparison.right.clone(),
+            })
+        }
+        _ => not(expr),
+    }
+}
+
+fn not(expr: &ast::Expression) -> ast::Expression {
+    ast::Expression::Unary(ast::UnaryExpression {
+        op: ast::UnaryOperator::Not,
+        expr: Box::new(expr.clone()),
+    })
+}
+
+/// The predicate of a conjunct, if it's one
+fn predicate(conjunct: &[ast::Expression], clause: Clause) -> Option<Predicate> {
+    let (column, operator, value) = match conjunct {
+        [expr] => test(expr, false)?,
+        _ => {
+            // `a = 1 OR a = 2` is `a IN (1, 2)`
+            let tests = conjunct
+                .iter()
+                .map(|expr| test(expr, false))
+                .collect::<Option<Vec<_>>>()?;
+            let column = tests[0].0.clone();
+            let mut values = Vec::new();
+
+            for (name, operator, value) in tests {
+                match (operator, value) {
+                    (Operator::Equal, Some(Value::Single(value))) if name == column => {
+                        values.push(value)
+                    }
+                    (Operator::In, Some(Value::List(list))) if name == column => {
+                        values.extend(list)
+                    }
+                    _ => return None,
+                }
+            }
+
+            (column, Operator::In, Some(Value::List(values)))
+        }
+    };
+
+    Some(Predicate {
+        column,
+        operator,
+        value,
+        clause,
+    })
+}
+
+/// The column, the operator and the value of a test, if it's one
+fn test(expr: &ast::Expression, negated: bool) -> Option<(Vec<Name>, Operator, Option<Value>)> {
+    let (column, operator, value) = match expr {
+        ast::Expression::Unary(ast::UnaryExpression {
+            op: ast::UnaryOperator::Not,
+            expr,
+        }) => return test(expr, !negated),
+        ast::Expression::Unary(ast::UnaryExpression {
+            op: ast::UnaryOperator::IsNull,
+            expr,
+        }) => (column(expr)?, Operator::IsNull, None),
+        ast::Expression::Comparison(comparison) => {
+            let operator = Operator::comparison(&comparison.op);
+
+            match (column(&comparison.left), column(&comparison.right)) {
+                (Some(column), None) if is_value(&comparison.right) => (
+                    column,
+                    operator,
+                    Some(Value::Single((*comparison.right).clone())),
+                ),
+                (None, Some(column)) if is_value(&comparison.left) => (
+                    column,
+                    operator.swapped()?,
+                    Some(Value::Single((*comparison.left).clone())),
+                ),
+                _ => return None,
+            }
+        }
+        ast::Expression::Between(between)
+            if is_value(&between.lower) && is_value(&between.upper) =>
+        {
+            (
+                column(&between.expr)?,
+                Operator::Between,
+                Some(Value::Range {
+                    lower: (*between.lower).clone(),
+                    upper: (*between.upper).clone(),
+                }),
+            )
+        }
+        ast::Expression::In(ast::InExpression {
+            expr,
+            set: ast::SetSpecification::List(exprs),
+        }) if exprs.iter().all(is_value) => (
+            column(expr)?,
+            Operator::In,
+            Some(Value::List(exprs.clone())),
+        ),
+        _ => return None,
+    };
+
+    let operator = if negated {
+        operator.negated()
+    } else {
+        operator
+    };
+
+    Some((column, operator, value))
+}
+
+fn column(expr: &ast::Expression) -> Option<Vec<Name>> {
+    match expr {
+        ast::Expression::QualifiedIdentifier(identifier) => Some(identifier.identifiers.clone()),
+        _ => None,
+    }
+}
+
+/// Whether an expression doesn't depend on the rows: it has no columns, aggregates or nested
+/// queries
+fn is_value(expr: &ast::Expression) -> bool {
+    let mut finder = RowDependency { found: false };
+
+    finder.visit_expression(expr);
+    !finder.found
+}
+
+struct RowDependency {
+    found: bool,
+}
+
+impl<'ast> Visitor<'ast> for RowDependency {
+    fn visit_expression(&mut self, node: &'ast ast::Expression) {
+        match node {
+            ast::Expression::QualifiedIdentifier(_)
+            | ast::Expression::Select(_)
+            | ast::Expression::Count(_)
+            | ast::Expression::Sum(_)
+            | ast::Expression::Max(_)
+            | ast::Expression::Min(_)
+            | ast::Expression::Error(_) => self.found = true,
+            _ => visit::walk_expression(self, node),
+        }
+    }
+}
+
+struct Collector {
+    predicates: Vec<Predicate>,
+}
+
+impl Collector {
+    fn collect(&mut self, condition: &ast::Expression, clause: Clause) {
+        self.predicates.extend(
+            cnf(condition, false)
+                .iter()
+                .filter_map(|conjunct| predicate(conjunct, clause)),
+        );
+    }
+}
+
+impl<'ast> Visitor<'ast> for Collector {
+    fn visit_query_set_expression(&mut self, node: &'ast ast::QuerySetExpression) {
+        // as the walk does, collecting the predicates in order of appearance
+        self.visit_result_columns(&node.columns);
+
+        for table in &node.from {
+            self.visit_table_expression(table);
+        }
+
+        if let Some(where_expr) = &node.where_expr {
+            self.collect(where_expr, Clause::Where);
+            self.visit_expression(where_expr);
+        }
+
+        if let Some(group_by) = &node.group_by {
+            self.visit_group_by(group_by);
+        }
+    }
+
+    fn visit_group_by(&mut self, node: &'ast ast::GroupBy) {
+        if let Some(having) = &node.having {
+            self.collect(having, Clause::Having);
+        }
+
+        visit::walk_group_by(self, node);
+    }
+
+    fn visit_join_constraint(&mut self, node: &'ast ast::JoinConstraint) {
+        if let ast::JoinConstraint::Expr(expr) = node {
+            self.collect(expr, Clause::Join);
+        }
+
+        visit::walk_join_constraint(self, node);
+    }
+
+    fn visit_delete_statement(&mut self, node: &'ast ast::DeleteStatement) {
+        if let Some(where_expr) = &node.where_expr {
+            self.collect(where_expr, Clause::Where);
+        }
+
+        visit::walk_delete_statement(self, node);
+    }
+
+    fn visit_update_statement(&mut self, node: &'ast ast::UpdateStatement) {
+        if let Some(where_expr) = &node.where_expr {
+            self.collect(where_expr, Clause::Where);
+        }
+
+        visit::walk_update_statement(self, node);
+    }
+}
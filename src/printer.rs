@@ -0,0 +1,785 @@
+// MIT License
+//
+// Copyright (c) 2019 Daniele Esposti
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::fmt;
+
+use super::ast::*;
+use super::symbols::Name;
+
+/// Write the items separated by commas
+fn comma_separated<T: fmt::Display>(f: &mut fmt::Formatter, items: &[T]) -> fmt::Result {
+    for (i, item) in items.iter().enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+
+        write!(f, "{}", item)?;
+    }
+
+    Ok(())
+}
+
+/// Write a qualified name like `schema.table`
+fn qualified(f: &mut fmt::Formatter, names: &[Name]) -> fmt::Result {
+    for (i, name) in names.iter().enumerate() {
+        if i > 0 {
+            write!(f, ".")?;
+        }
+
+        write!(f, "{}", name)?;
+    }
+
+    Ok(())
+}
+
+impl fmt::Display for SqlStatement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SqlStatement::Statement(statement) => write!(f, "{}", statement),
+            SqlStatement::ExplainQueryPlan(statement) => {
+                write!(f, "EXPLAIN QUERY PLAN {}", statement)
+            }
+            SqlStatement::Attach(attach) => write!(f, "{}", attach),
+            SqlStatement::Describe(describe) => write!(f, "{}", describe),
+            SqlStatement::Error(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl fmt::Display for Statement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Statement::Select(select) => write!(f, "{}", select),
+            Statement::Insert(insert) => write!(f, "{}", insert),
+            Statement::Delete(delete) => write!(f, "{}", delete),
+            Statement::Update(update) => write!(f, "{}", update),
+        }
+    }
+}
+
+impl fmt::Display for InsertStatement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "INSERT INTO ")?;
+        qualified(f, &self.table_name)?;
+
+        if let Some(columns) = &self.columns {
+            write!(f, " (")?;
+            comma_separated(f, columns)?;
+            write!(f, ")")?;
+        }
+
+        write!(f, " {}", self.source)
+    }
+}
+
+impl fmt::Display for DeleteStatement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "DELETE FROM ")?;
+        qualified(f, &self.table_name)?;
+
+        if let Some(expr) = &self.where_expr {
+            write!(f, " WHERE {}", expr)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for UpdateStatement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "UPDATE ")?;
+        qualified(f, &self.table_name)?;
+        write!(f, " SET ")?;
+        comma_separated(f, &self.assignments)?;
+
+        if let Some(expr) = &self.where_expr {
+            write!(f, " WHERE {}", expr)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Assignment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        comma_separated(f, &self.columns)?;
+        write!(f, " = {}", self.expr)
+    }
+}
+
+impl fmt::Display for AttachStatement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ATTACH FILE {} AS ", Literal::String(self.path.clone()))?;
+        qualified(f, &self.qualified_name)
+    }
+}
+
+impl fmt::Display for DescribeStatement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "DESCRIBE ")?;
+        qualified(f, &self.qualified_name)
+    }
+}
+
+impl fmt::Display for SelectStatement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if !self.common.is_empty() {
+            write!(f, "WITH ")?;
+
+            if self.recursive {
+                write!(f, "RECURSIVE ")?;
+            }
+
+            comma_separated(f, &self.common)?;
+            write!(f, " ")?;
+        }
+
+        write!(f, "{}", self.expr)?;
+
+        if !self.order_by.is_empty() {
+            write!(f, " ORDER BY ")?;
+            comma_separated(f, &self.order_by)?;
+        }
+
+        if let Some(limit) = &self.limit {
+            write!(f, " {}", limit)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for CommonTableExpression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.identifier)?;
+
+        if let Some(columns) = &self.column_names {
+            write!(f, " (")?;
+            comma_separated(f, columns)?;
+            write!(f, ")")?;
+        }
+
+        write!(f, " AS ({})", self.query)
+    }
+}
+
+impl fmt::Display for SetExpression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SetExpression::Values(values) => {
+                write!(f, "VALUES ")?;
+
+                for (i, row) in values.values.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+
+                    write!(f, "(")?;
+                    comma_separated(f, row)?;
+                    write!(f, ")")?;
+                }
+
+                Ok(())
+            }
+            SetExpression::Query(query) => write!(f, "{}", query),
+            SetExpression::Op(op) => write!(f, "{} {} {}", op.left, op.op, op.right),
+        }
+    }
+}
+
+impl fmt::Display for SetOperator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SetOperator::Intersect => write!(f, "INTERSECT"),
+            SetOperator::Except => write!(f, "EXCEPT"),
+            SetOperator::Union => write!(f, "UNION"),
+            SetOperator::UnionAll => write!(f, "UNION ALL"),
+        }
+    }
+}
+
+impl fmt::Display for QuerySetExpression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SELECT {}{}", self.mode, self.columns)?;
+
+        if !self.from.is_empty() {
+            write!(f, " FROM ")?;
+            comma_separated(f, &self.from)?;
+        }
+
+        if let Some(expr) = &self.where_expr {
+            write!(f, " WHERE {}", expr)?;
+        }
+
+        if let Some(group_by) = &self.group_by {
+            write!(f, " {}", group_by)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The select mode, followed by a space unless it's the default
+impl fmt::Display for SelectMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SelectMode::All => Ok(()),
+            SelectMode::Distinct => write!(f, "DISTINCT "),
+        }
+    }
+}
+
+impl fmt::Display for ResultColumns {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ResultColumns::All => write!(f, "*"),
+            ResultColumns::List(columns) => comma_separated(f, columns),
+        }
+    }
+}
+
+impl fmt::Display for ResultColumn {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ResultColumn::AllFrom(name) => write!(f, "{}.*", name),
+            ResultColumn::Expr(column) => {
+                write!(f, "{}", column.expr)?;
+
+                match &column.rename {
+                    Some(rename) => write!(f, " AS {}", rename),
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for TableExpression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let alias = match self {
+            TableExpression::Named(named) => {
+                qualified(f, &named.name)?;
+                &named.alias
+            }
+            TableExpression::Select(select) => {
+                write!(f, "({})", select.select)?;
+                &select.alias
+            }
+            TableExpression::Join(join) => {
+                write!(f, "{} {} ", join.left, join.op)?;
+
+                // the right side of a join can only be a join when it's parenthesised
+                match join.right.as_ref() {
+                    TableExpression::Join(_) => write!(f, "({})", join.right)?,
+                    _ => write!(f, "{}", join.right)?,
+                }
+
+                return write!(f, " {}", join.constraint);
+            }
+            TableExpression::Error(error) => return write!(f, "{}", error),
+        };
+
+        match alias {
+            Some(alias) => write!(f, " AS {}", alias),
+            None => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for JoinOperator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            JoinOperator::Join(join_type) => write!(f, "{}JOIN", join_type),
+            JoinOperator::Natural(join_type) => write!(f, "NATURAL {}JOIN", join_type),
+            JoinOperator::Cross => write!(f, "CROSS JOIN"),
+        }
+    }
+}
+
+/// The join type, followed by a space unless it's the default
+impl fmt::Display for JoinType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            JoinType::Inner => Ok(()),
+            JoinType::Left => write!(f, "LEFT "),
+            JoinType::Right => write!(f, "RIGHT "),
+            JoinType::Full => write!(f, "FULL "),
+        }
+    }
+}
+
+impl fmt::Display for JoinConstraint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            JoinConstraint::Expr(expr) => write!(f, "ON {}", expr),
+            JoinConstraint::Columns(columns) => write!(f, "{}", columns),
+        }
+    }
+}
+
+impl fmt::Display for ColumnsJoinConstraint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "USING (")?;
+        comma_separated(f, &self.columns)?;
+        write!(f, ")")
+    }
+}
+
+impl fmt::Display for GroupBy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "GROUP BY ")?;
+        comma_separated(f, &self.groupings)?;
+
+        match &self.having {
+            Some(having) => write!(f, " HAVING {}", having),
+            None => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for Ordering {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.expr)?;
+
+        if let Some(collation) = &self.collation {
+            write!(f, " COLLATE {}", collation)?;
+        }
+
+        match self.direction {
+            OrderingDirection::Ascending => Ok(()),
+            OrderingDirection::Descending => write!(f, " DESC"),
+        }
+    }
+}
+
+impl fmt::Display for OrderingDirection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OrderingDirection::Ascending => write!(f, "ASC"),
+            OrderingDirection::Descending => write!(f, "DESC"),
+        }
+    }
+}
+
+impl fmt::Display for Limit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "LIMIT {}", self.number_rows)?;
+
+        match &self.offset_value {
+            Some(offset) => write!(f, " OFFSET {}", offset),
+            None => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for Literal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let quoted =
+            |f: &mut fmt::Formatter, value: &str| write!(f, "'{}'", value.replace('\'', "''"));
+
+        match self {
+            Literal::String(value) => quoted(f, value),
+            Literal::Numeric(value) => write!(f, "{}", value),
+            Literal::Null => write!(f, "NULL"),
+            Literal::Boolean(true) => write!(f, "TRUE"),
+            Literal::Boolean(false) => write!(f, "FALSE"),
+            Literal::CurrentTime => write!(f, "CURRENT_TIME"),
+            Literal::CurrentDate => write!(f, "CURRENT_DATE"),
+            Literal::CurrentTimestamp => write!(f, "CURRENT_TIMESTAMP"),
+            Literal::Date(value) => {
+                write!(f, "DATE ")?;
+                quoted(f, value)
+            }
+            Literal::Time(value) => {
+                write!(f, "TIME ")?;
+                quoted(f, value)
+            }
+            Literal::Timestamp(value) => {
+                write!(f, "TIMESTAMP ")?;
+                quoted(f, value)
+            }
+            Literal::Placeholder => write!(f, "?"),
+        }
+    }
+}
+
+impl fmt::Display for Parameter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Parameter::Positional(_) => write!(f, "?"),
+            Parameter::Named(name) => write!(f, ":{}", name.as_str()),
+            Parameter::Variable(name) => write!(f, "${{{}}}", name.as_str()),
+        }
+    }
+}
+
+impl fmt::Display for ErrorNode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+impl fmt::Display for DataType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DataType::Boolean => write!(f, "BOOLEAN"),
+            DataType::Char(length) => write!(f, "CHAR({})", length),
+            DataType::Date => write!(f, "DATE"),
+            DataType::Decimal { p, s } => write!(f, "DECIMAL({}, {})", p, s),
+            DataType::DoublePrecision => write!(f, "DOUBLE PRECISION"),
+            DataType::Timestamp => write!(f, "TIMESTAMP"),
+            DataType::LocalTimestamp => write!(f, "TIMESTAMP WITH LOCAL TIME ZONE"),
+            DataType::Varchar(length) => write!(f, "VARCHAR({})", length),
+        }
+    }
+}
+
+impl fmt::Display for ComparisonOperator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ComparisonOperator::Equal => write!(f, "="),
+            ComparisonOperator::NotEqual => write!(f, "!="),
+            ComparisonOperator::LessThan => write!(f, "<"),
+            ComparisonOperator::LessEqual => write!(f, "<="),
+            ComparisonOperator::GreaterThan => write!(f, ">"),
+            ComparisonOperator::GreaterEqual => write!(f, ">="),
+            ComparisonOperator::Like => write!(f, "LIKE"),
+        }
+    }
+}
+
+impl fmt::Display for UnaryOperator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            UnaryOperator::Negate => write!(f, "-"),
+            UnaryOperator::Not => write!(f, "NOT"),
+            UnaryOperator::IsNull => write!(f, "IS NULL"),
+        }
+    }
+}
+
+impl fmt::Display for BinaryOperator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BinaryOperator::Multiply => write!(f, "*"),
+            BinaryOperator::Divide => write!(f, "/"),
+            BinaryOperator::Add => write!(f, "+"),
+            BinaryOperator::Subtract => write!(f, "-"),
+            BinaryOperator::Concat => write!(f, "||"),
+            BinaryOperator::And => write!(f, "AND"),
+            BinaryOperator::Or => write!(f, "OR"),
+        }
+    }
+}
+
+/// How tightly an expression binds its operands, following the levels of the grammar
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+enum Precedence {
+    Or,
+    And,
+    Not,
+    Comparison,
+    Additive,
+    Multiplicative,
+    Unary,
+    Primary,
+}
+
+/// The expressions the grammar accepts in an operand position
+#[derive(Debug, Clone, Copy)]
+struct Operand {
+    /// the loosest binding expression accepted without parentheses
+    min: Precedence,
+
+    /// whether function calls are accepted without parentheses
+    function: bool,
+
+    /// whether CASE expressions are accepted without parentheses
+    case: bool,
+}
+
+impl Operand {
+    fn new(min: Precedence, function: bool, case: bool) -> Operand {
+        Operand {
+            min,
+            function,
+            case,
+        }
+    }
+}
+
+fn is_function(expr: &Expression) -> bool {
+    matches!(
+        expr,
+        Expression::Coalesce(_)
+            | Expression::Replace(_)
+            | Expression::Substring(_)
+            | Expression::ToDate(_)
+            | Expression::Power(_)
+            | Expression::Concat(_)
+            | Expression::Sum(_)
+            | Expression::Max(_)
+            | Expression::Min(_)
+            | Expression::Cast(_)
+            | Expression::Right(_)
+            | Expression::Count(_)
+            | Expression::Unknown(_)
+    )
+}
+
+fn precedence(expr: &Expression) -> Precedence {
+    match expr {
+        Expression::Unary(unary) => match (&unary.op, unary.expr.as_ref()) {
+            (UnaryOperator::Negate, _) => Precedence::Unary,
+            (UnaryOperator::IsNull, _) => Precedence::Comparison,
+            (UnaryOperator::Not, Expression::Unary(inner)) if inner.op == UnaryOperator::IsNull => {
+                Precedence::Comparison
+            }
+            (UnaryOperator::Not, Expression::In(_))
+            | (UnaryOperator::Not, Expression::Between(_)) => Precedence::Comparison,
+            (UnaryOperator::Not, _) => Precedence::Not,
+        },
+        Expression::Binary(binary) => match binary.op {
+            BinaryOperator::Or => Precedence::Or,
+            BinaryOperator::And => Precedence::And,
+            BinaryOperator::Add | BinaryOperator::Subtract | BinaryOperator::Concat => {
+                Precedence::Additive
+            }
+            BinaryOperator::Multiply | BinaryOperator::Divide => Precedence::Multiplicative,
+        },
+        Expression::Comparison(_) | Expression::In(_) | Expression::Between(_) => {
+            Precedence::Comparison
+        }
+        // functions and CASE expressions are handled by the operand rules
+        _ => Precedence::Primary,
+    }
+}
+
+/// Write an expression, parenthesised if the grammar wouldn't accept it in that position
+fn operand(f: &mut fmt::Formatter, expr: &Expression, position: Operand) -> fmt::Result {
+    let parenthesise = match expr {
+        Expression::Case(_) => !position.case,
+        _ if is_function(expr) => !position.function,
+        _ => precedence(expr) < position.min,
+    };
+
+    match parenthesise {
+        true => write!(f, "({})", expr),
+        false => write!(f, "{}", expr),
+    }
+}
+
+/// Write the comparison-level expressions that apply to a single value, e.g. `x IN (...)`
+fn predicate(f: &mut fmt::Formatter, expr: &Expression, negated: bool) -> fmt::Result {
+    let not = match negated {
+        true => "NOT ",
+        false => "",
+    };
+    let value = Operand::new(Precedence::Comparison, false, false);
+    let bound = Operand::new(Precedence::Additive, false, false);
+
+    match expr {
+        Expression::Unary(unary) => {
+            operand(f, &unary.expr, value)?;
+            write!(f, " IS {}NULL", not)
+        }
+        Expression::In(in_expr) => {
+            operand(f, &in_expr.expr, value)?;
+            write!(f, " {}IN {}", not, in_expr.set)
+        }
+        Expression::Between(between) => {
+            operand(f, &between.expr, value)?;
+            write!(f, " {}BETWEEN ", not)?;
+            operand(f, &between.lower, bound)?;
+            write!(f, " AND ")?;
+            operand(f, &between.upper, bound)
+        }
+        _ => unreachable!(),
+    }
+}
+
+impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expression::Literal(literal) => write!(f, "{}", literal),
+            Expression::Parameter(parameter) => write!(f, "{}", parameter),
+            Expression::Error(error) => write!(f, "{}", error),
+            Expression::QualifiedIdentifier(identifier) => qualified(f, &identifier.identifiers),
+            Expression::MakeTuple(tuple) => {
+                write!(f, "(")?;
+                comma_separated(f, &tuple.exprs)?;
+                write!(f, ")")
+            }
+            Expression::Select(select) => write!(f, "({})", select),
+            Expression::Unary(unary) => match (&unary.op, unary.expr.as_ref()) {
+                (UnaryOperator::Negate, expr) => {
+                    write!(f, "-")?;
+                    // a nested negation is parenthesised, `--` would start a comment
+                    operand(f, expr, Operand::new(Precedence::Primary, false, false))
+                }
+                (UnaryOperator::IsNull, _) => predicate(f, self, false),
+                (UnaryOperator::Not, expr) => match expr {
+                    Expression::Unary(inner) if inner.op == UnaryOperator::IsNull => {
+                        predicate(f, expr, true)
+                    }
+                    Expression::In(_) | Expression::Between(_) => predicate(f, expr, true),
+                    _ => {
+                        write!(f, "NOT ")?;
+                        operand(f, expr, Operand::new(Precedence::Comparison, true, true))
+                    }
+                },
+            },
+            Expression::Binary(binary) => {
+                let (left, right) = match binary.op {
+                    BinaryOperator::Or => (
+                        Operand::new(Precedence::Or, true, true),
+                        Operand::new(Precedence::And, true, true),
+                    ),
+                    BinaryOperator::And => (
+                        Operand::new(Precedence::And, true, true),
+                        Operand::new(Precedence::Not, true, true),
+                    ),
+                    BinaryOperator::Add | BinaryOperator::Subtract | BinaryOperator::Concat => (
+                        Operand::new(Precedence::Additive, true, false),
+                        Operand::new(Precedence::Multiplicative, true, false),
+                    ),
+                    BinaryOperator::Multiply | BinaryOperator::Divide => (
+                        Operand::new(Precedence::Multiplicative, true, false),
+                        Operand::new(Precedence::Unary, true, false),
+                    ),
+                };
+
+                operand(f, &binary.left, left)?;
+                write!(f, " {} ", binary.op)?;
+                operand(f, &binary.right, right)
+            }
+            Expression::Comparison(comparison) => {
+                let equal = comparison.op == ComparisonOperator::Equal;
+                let not_equal = comparison.op == ComparisonOperator::NotEqual;
+
+                operand(
+                    f,
+                    &comparison.left,
+                    Operand::new(Precedence::Comparison, not_equal, false),
+                )?;
+                write!(f, " {} ", comparison.op)?;
+                operand(
+                    f,
+                    &comparison.right,
+                    Operand::new(Precedence::Additive, equal || not_equal, equal),
+                )
+            }
+            Expression::In(_) | Expression::Between(_) => predicate(f, self, false),
+            Expression::Case(case) => {
+                write!(f, "CASE")?;
+
+                if let Some(expr) = &case.expr {
+                    write!(f, " {}", expr)?;
+                }
+
+                for when in &case.when_part {
+                    write!(f, " {}", when)?;
+                }
+
+                if let Some(expr) = &case.else_part {
+                    write!(f, " ELSE {}", expr)?;
+                }
+
+                write!(f, " END")
+            }
+            Expression::Coalesce(coalesce) => {
+                write!(f, "COALESCE(")?;
+                comma_separated(f, &coalesce.exprs)?;
+                write!(f, ")")
+            }
+            Expression::Replace(replace) => {
+                write!(f, "REPLACE({}, {}", replace.string, replace.search_string)?;
+
+                if let Some(expr) = &replace.replace_string {
+                    write!(f, ", {}", expr)?;
+                }
+
+                write!(f, ")")
+            }
+            Expression::Substring(substring) => {
+                write!(
+                    f,
+                    "SUBSTRING({} FROM {}",
+                    substring.string, substring.position
+                )?;
+
+                if let Some(expr) = &substring.length {
+                    write!(f, " FOR {}", expr)?;
+                }
+
+                write!(f, ")")
+            }
+            Expression::ToDate(to_date) => {
+                write!(f, "TO_DATE({}", to_date.string)?;
+
+                if let Some(expr) = &to_date.format {
+                    write!(f, ", {}", expr)?;
+                }
+
+                write!(f, ")")
+            }
+            Expression::Power(power) => write!(f, "POWER({}, {})", power.base, power.exponent),
+            Expression::Concat(concat) => {
+                write!(f, "CONCAT(")?;
+                comma_separated(f, &concat.exprs)?;
+                write!(f, ")")
+            }
+            Expression::Sum(sum) => write!(f, "SUM({}{})", sum.mode, sum.expr),
+            Expression::Max(max) => write!(f, "MAX({}{})", max.mode, max.expr),
+            Expression::Min(min) => write!(f, "MIN({}{})", min.mode, min.expr),
+            Expression::Cast(cast) => write!(f, "CAST({} AS {})", cast.expr, cast.data_type),
+            Expression::Right(right) => write!(f, "RIGHT({}, {})", right.string, right.length),
+            Expression::Count(count) => write!(f, "COUNT({}{})", count.mode, count.columns),
+            Expression::Unknown(unknown) => {
+                qualified(f, &unknown.name)?;
+                write!(f, "(")?;
+                comma_separated(f, &unknown.exprs)?;
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+impl fmt::Display for SetSpecification {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SetSpecification::Select(select) => write!(f, "({})", select),
+            SetSpecification::List(exprs) => {
+                write!(f, "(")?;
+                comma_separated(f, exprs)?;
+                write!(f, ")")
+            }
+            SetSpecification::Name(name) => qualified(f, name),
+        }
+    }
+}
+
+impl fmt::Display for WhenClause {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "WHEN {} THEN {}", self.guard, self.body)
+    }
+}
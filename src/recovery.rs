@@ -0,0 +1,157 @@
+// MIT License
+//
+// Copyright (c) 2019 Daniele Esposti
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use dict_derive::IntoPyObject;
+use lalrpop_util::lexer::Token;
+use lalrpop_util::{ErrorRecovery, ParseError};
+
+use super::ast;
+use super::sql;
+use super::strip_comments;
+use super::symbols::Span;
+use super::visit::Visitor;
+
+/// The outcome of parsing a statement leniently: the statement, with placeholders for the parts
+/// which cannot be parsed, and all the syntax errors found; the statement is `None` if the
+/// parser cannot recover from an error
+#[derive(IntoPyObject, Debug, PartialEq, Eq, Clone)]
+pub struct LenientParse {
+    pub statement: Option<ast::SqlStatement>,
+    pub errors: Vec<ast::ErrorNode>,
+}
+
+/// Parse a statement, replacing the select list items, the `FROM` entries, the operands of
+/// `AND` and `OR`, or else the whole statement, which cannot be parsed with placeholders
+pub fn parse_lenient(sql_str: &str) -> LenientParse {
+    let stripped_sql = strip_comments(sql_str);
+
+    match sql::SqlStatementParser::new().parse(&stripped_sql) {
+        Ok(statement) => LenientParse {
+            errors: errors(&statement),
+            statement: Some(statement),
+        },
+        Err(error) => LenientParse {
+            statement: None,
+            errors: vec![unrecovered(&stripped_sql, error)],
+        },
+    }
+}
+
+/// Parse a statement, failing with the message of its first syntax error, even if the parser
+/// can recover from it
+pub fn parse_strict(sql_str: &str) -> Result<ast::SqlStatement, String> {
+    let stripped_sql = strip_comments(sql_str);
+    let statement = sql::SqlStatementParser::new()
+        .parse(&stripped_sql)
+        .map_err(|e| format!("{}", e))?;
+
+    match errors(&statement).into_iter().next() {
+        Some(error) => Err(error.message),
+        None => Ok(statement),
+    }
+}
+
+/// The placeholders of a statement, in order of appearance
+pub fn errors(statement: &ast::SqlStatement) -> Vec<ast::ErrorNode> {
+    let mut collector = Collector { errors: Vec::new() };
+
+    collector.visit_sql_statement(statement);
+    collector.errors.sort_by_key(|error| error.span.start);
+    collector.errors
+}
+
+struct Collector {
+    errors: Vec<ast::ErrorNode>,
+}
+
+impl<'ast> Visitor<'ast> for Collector {
+    fn visit_error_node(&mut self, node: &'ast ast::ErrorNode) {
+        self.errors.push(node.clone());
+    }
+}
+
+/// Build the placeholder of the SQL from `start` skipped by the parser to recover from an error
+pub(crate) fn recovered<'input>(
+    sql_str: &'input str,
+    recovery: ErrorRecovery<usize, Token<'input>, &'static str>,
+    start: usize,
+) -> ast::ErrorNode {
+    // the parser may go on from the unexpected token, which then isn't skipped
+    let end = match recovery.dropped_tokens.last() {
+        Some((_, _, end)) => *end,
+        None => location(&recovery.error),
+    };
+
+    error_node(sql_str, recovery.error, Span::new(start.min(end), end))
+}
+
+/// The placeholder of the rest of a statement after an error the parser cannot recover from
+fn unrecovered(sql_str: &str, error: ParseError<usize, Token, &'static str>) -> ast::ErrorNode {
+    let start = location(&error);
+
+    error_node(sql_str, error, Span::new(start, sql_str.len()))
+}
+
+/// Where the parser found the error
+fn location(error: &ParseError<usize, Token, &'static str>) -> usize {
+    match error {
+        ParseError::InvalidToken { location } | ParseError::UnrecognizedEOF { location, .. } => {
+            *location
+        }
+        ParseError::UnrecognizedToken {
+            token: (start, _, _),
+            ..
+        }
+        | ParseError::ExtraToken {
+            token: (start, _, _),
+        } => *start,
+        ParseError::User { .. } => 0,
+    }
+}
+
+fn error_node(
+    sql_str: &str,
+    error: ParseError<usize, Token, &'static str>,
+    skipped: Span,
+) -> ast::ErrorNode {
+    let token = match &error {
+        ParseError::UnrecognizedToken {
+            token: (_, token, _),
+            ..
+        }
+        | ParseError::ExtraToken {
+            token: (_, token, _),
+        } => Some(token.1.to_string()),
+        _ => None,
+    };
+
+    let text = &sql_str[skipped.start..skipped.end];
+    let start = skipped.start + text.len() - text.trim_start().len();
+
+    ast::ErrorNode {
+        message: format!("{}", error),
+        token,
+        location: location(&error),
+        text: text.trim().to_string(),
+        span: Span::new(start, start + text.trim().len()),
+    }
+}
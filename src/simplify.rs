@@ -0,0 +1,472 @@
+// MIT License
+//
+// Copyright (c) 2019 Daniele Esposti
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::cmp::Ordering;
+use std::fmt;
+
+use super::ast;
+use super::visit::{self, VisitorMut};
+
+/// Exasol's maximum precision of a decimal number
+const MAX_DIGITS: u32 = 36;
+
+/// Fold the constant expressions of a statement and drop the redundant parts of the others, in
+/// place. The simplified expressions give the same results, NULL included: arithmetic is exact,
+/// nothing is folded when the result would not fit in a decimal, and the empty string is NULL,
+/// as in Exasol.
+pub fn simplify(statement: &mut ast::SqlStatement) {
+    Simplifier.visit_sql_statement_mut(statement);
+}
+
+/// Simplify an expression, see `simplify`
+pub fn simplify_expression(mut expr: ast::Expression) -> ast::Expression {
+    Simplifier.visit_expression_mut(&mut expr);
+    expr
+}
+
+struct Simplifier;
+
+impl VisitorMut for Simplifier {
+    fn visit_expression_mut(&mut self, node: &mut ast::Expression) {
+        // the operands first, so that the folded ones can be folded further
+        visit::walk_expression_mut(self, node);
+
+        let expr = std::mem::replace(node, ast::Expression::Literal(ast::Literal::Null));
+        *node = fold(expr);
+    }
+}
+
+fn fold(expr: ast::Expression) -> ast::Expression {
+    match expr {
+        ast::Expression::Unary(node) => unary(node),
+        ast::Expression::Binary(node) => binary(node),
+        ast::Expression::Comparison(node) => match compare(&node.op, &node.left, &node.right) {
+            Some(value) => boolean(value),
+            None => ast::Expression::Comparison(node),
+        },
+        ast::Expression::Between(node) => between(node),
+        ast::Expression::Case(node) => case(node),
+        ast::Expression::Coalesce(node) => coalesce(node),
+        ast::Expression::Concat(node) => {
+            let strings = node
+                .exprs
+                .iter()
+                .map(|expr| match constant(expr) {
+                    Some(Constant::String(value)) => Some(value),
+                    _ => None,
+                })
+                .collect::<Option<Vec<_>>>();
+
+            match strings {
+                Some(strings) => expression(Constant::String(strings.concat())),
+                None => ast::Expression::Concat(node),
+            }
+        }
+        expr => expr,
+    }
+}
+
+fn unary(node: ast::UnaryExpression) -> ast::Expression {
+    let (op, expr) = match (node.op, *node.expr) {
+        (
+            ast::UnaryOperator::Not,
+            ast::Expression::Unary(ast::UnaryExpression {
+                op: ast::UnaryOperator::Not,
+                expr,
+            }),
+        ) => return *expr,
+        operation => operation,
+    };
+
+    let value = match (&op, &expr, constant(&expr)) {
+        (ast::UnaryOperator::Not, _, _) => {
+            truth(&expr).map(|value| value.map(|value| !value).into())
+        }
+        // a negative number is a negated literal already
+        (ast::UnaryOperator::Negate, ast::Expression::Literal(_), _) => None,
+        (ast::UnaryOperator::Negate, _, Some(Constant::Null)) => Some(Constant::Null),
+        (ast::UnaryOperator::Negate, _, Some(Constant::Number(number))) => {
+            Some(Constant::Number(number.negate()))
+        }
+        (ast::UnaryOperator::IsNull, _, Some(value)) => Some(Constant::Boolean(value.is_null())),
+        _ => None,
+    };
+
+    match value {
+        Some(value) => expression(value),
+        None => ast::Expression::Unary(ast::UnaryExpression {
+            op,
+            expr: Box::new(expr),
+        }),
+    }
+}
+
+fn binary(node: ast::BinaryExpression) -> ast::Expression {
+    match node.op {
+        ast::BinaryOperator::And => {
+            return match (truth(&node.left), truth(&node.right)) {
+                (Some(Some(false)), _) | (_, Some(Some(false))) => boolean(Some(false)),
+                (Some(Some(true)), _) => *node.right,
+                (_, Some(Some(true))) => *node.left,
+                (Some(None), Some(None)) => boolean(None),
+                _ => ast::Expression::Binary(node),
+            }
+        }
+        ast::BinaryOperator::Or => {
+            return match (truth(&node.left), truth(&node.right)) {
+                (Some(Some(true)), _) | (_, Some(Some(true))) => boolean(Some(true)),
+                (Some(Some(false)), _) => *node.right,
+                (_, Some(Some(false))) => *node.left,
+                (Some(None), Some(None)) => boolean(None),
+                _ => ast::Expression::Binary(node),
+            }
+        }
+        _ => (),
+    }
+
+    let value = match (&node.op, constant(&node.left), constant(&node.right)) {
+        (
+            ast::BinaryOperator::Concat,
+            Some(Constant::String(left)),
+            Some(Constant::String(right)),
+        ) => Some(Constant::String(left + &right)),
+        // NULL is an empty string when concatenating in Exasol, better not to fold it
+        (ast::BinaryOperator::Concat, _, _) => None,
+        (_, Some(Constant::Null), Some(Constant::Null))
+        | (_, Some(Constant::Null), Some(Constant::Number(_)))
+        | (_, Some(Constant::Number(_)), Some(Constant::Null)) => Some(Constant::Null),
+        (op, Some(Constant::Number(left)), Some(Constant::Number(right))) => match op {
+            ast::BinaryOperator::Add => left.add(right),
+            ast::BinaryOperator::Subtract => left.add(right.negate()),
+            ast::BinaryOperator::Multiply => left.multiply(right),
+            ast::BinaryOperator::Divide => left.divide(right),
+            _ => None,
+        }
+        .map(Constant::Number),
+        _ => None,
+    };
+
+    match value {
+        Some(value) => expression(value),
+        None => ast::Expression::Binary(node),
+    }
+}
+
+/// `x BETWEEN a AND b` is `x >= a AND x <= b`, or `x = a` when `a` and `b` are equal
+fn between(node: ast::BetweenExpression) -> ast::Expression {
+    let lower = compare(
+        &ast::ComparisonOperator::GreaterEqual,
+        &node.expr,
+        &node.lower,
+    );
+    let upper = compare(&ast::ComparisonOperator::LessEqual, &node.expr, &node.upper);
+
+    match (lower, upper) {
+        (Some(Some(false)), _) | (_, Some(Some(false))) => return boolean(Some(false)),
+        (Some(lower), Some(upper)) => return boolean(lower.and(upper)),
+        _ => (),
+    }
+
+    let same_bounds = compare(&ast::ComparisonOperator::Equal, &node.lower, &node.upper);
+
+    if same_bounds == Some(Some(true)) {
+        ast::Expression::Comparison(ast::ComparisonExpression {
+            op: ast::ComparisonOperator::Equal,
+            left: node.expr,
+            right: node.lower,
+        })
+    } else {
+        ast::Expression::Between(node)
+    }
+}
+
+/// Drop the `WHEN` clauses which never apply, and the ones after a clause which always applies
+fn case(node: ast::CaseExpression) -> ast::Expression {
+    let ast::CaseExpression {
+        expr,
+        when_part,
+        mut else_part,
+    } = node;
+    let mut clauses = Vec::new();
+
+    for clause in when_part {
+        let applies = match &expr {
+            Some(expr) => compare(&ast::ComparisonOperator::Equal, expr, &clause.guard),
+            None => truth(&clause.guard),
+        };
+
+        match applies {
+            // a NULL guard never applies
+            Some(Some(false)) | Some(None) => (),
+            Some(Some(true)) => {
+                else_part = Some(Box::new(clause.body));
+                break;
+            }
+            None => clauses.push(clause),
+        }
+    }
+
+    if clauses.is_empty() {
+        else_part.map_or_else(|| boolean(None), |body| *body)
+    } else {
+        ast::Expression::Case(ast::CaseExpression {
+            expr,
+            when_part: clauses,
+            else_part,
+        })
+    }
+}
+
+/// Drop the NULL arguments and the ones after an argument which is never NULL
+fn coalesce(node: ast::CoalesceExpression) -> ast::Expression {
+    let mut exprs = Vec::new();
+
+    for expr in node.exprs {
+        match constant(&expr) {
+            Some(Constant::Null) => (),
+            Some(_) => {
+                exprs.push(expr);
+                break;
+            }
+            None => exprs.push(expr),
+        }
+    }
+
+    match exprs.len() {
+        0 => boolean(None),
+        1 => exprs.remove(0),
+        _ => ast::Expression::Coalesce(ast::CoalesceExpression { exprs }),
+    }
+}
+
+/// The result of comparing two constants, `Some(None)` if it's NULL; `None` if the operands
+/// aren't constants or can't be compared without converting them
+fn compare(
+    op: &ast::ComparisonOperator,
+    left: &ast::Expression,
+    right: &ast::Expression,
+) -> Option<Option<bool>> {
+    let ordering = match (constant(left)?, constant(right)?) {
+        (Constant::Null, _) | (_, Constant::Null) => return Some(None),
+        (Constant::Number(left), Constant::Number(right)) => left.compare(right)?,
+        (Constant::String(left), Constant::String(right)) => left.cmp(&right),
+        _ => return None,
+    };
+
+    let value = match op {
+        ast::ComparisonOperator::Equal => ordering == Ordering::Equal,
+        ast::ComparisonOperator::NotEqual => ordering != Ordering::Equal,
+        ast::ComparisonOperator::LessThan => ordering == Ordering::Less,
+        ast::ComparisonOperator::LessEqual => ordering != Ordering::Greater,
+        ast::ComparisonOperator::GreaterThan => ordering == Ordering::Greater,
+        ast::ComparisonOperator::GreaterEqual => ordering != Ordering::Less,
+        ast::ComparisonOperator::Like => return None,
+    };
+
+    Some(Some(value))
+}
+
+/// The value of a constant boolean expression, `Some(None)` if it's NULL
+fn truth(expr: &ast::Expression) -> Option<Option<bool>> {
+    match constant(expr)? {
+        Constant::Null => Some(None),
+        Constant::Boolean(value) => Some(Some(value)),
+        _ => None,
+    }
+}
+
+fn boolean(value: Option<bool>) -> ast::Expression {
+    expression(Constant::from(value))
+}
+
+/// The values the simplifier computes with
+#[derive(Debug, PartialEq, Eq, Clone)]
+enum Constant {
+    Null,
+    Boolean(bool),
+    Number(Decimal),
+    String(String),
+}
+
+impl Constant {
+    fn is_null(&self) -> bool {
+        *self == Constant::Null
+    }
+}
+
+impl From<Option<bool>> for Constant {
+    fn from(value: Option<bool>) -> Constant {
+        value.map_or(Constant::Null, Constant::Boolean)
+    }
+}
+
+fn constant(expr: &ast::Expression) -> Option<Constant> {
+    match expr {
+        ast::Expression::Literal(ast::Literal::Null) => Some(Constant::Null),
+        ast::Expression::Literal(ast::Literal::Boolean(value)) => Some(Constant::Boolean(*value)),
+        ast::Expression::Literal(ast::Literal::Numeric(value)) => {
+            Decimal::parse(value).map(Constant::Number)
+        }
+        ast::Expression::Literal(ast::Literal::String(value)) if value.is_empty() => {
+            Some(Constant::Null)
+        }
+        ast::Expression::Literal(ast::Literal::String(value)) => {
+            Some(Constant::String(value.clone()))
+        }
+        // negative numbers are parsed as negated literals
+        ast::Expression::Unary(ast::UnaryExpression {
+            op: ast::UnaryOperator::Negate,
+            expr,
+        }) => match constant(expr)? {
+            Constant::Number(number) => Some(Constant::Number(number.negate())),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn expression(value: Constant) -> ast::Expression {
+    let literal = match value {
+        Constant::Null => ast::Literal::Null,
+        Constant::Boolean(value) => ast::Literal::Boolean(value),
+        Constant::String(value) => ast::Literal::String(value),
+        Constant::Number(number) if number.mantissa < 0 => {
+            return ast::Expression::Unary(ast::UnaryExpression {
+                op: ast::UnaryOperator::Negate,
+                expr: Box::new(expression(Constant::Number(number.negate()))),
+            })
+        }
+        Constant::Number(number) => ast::Literal::Numeric(number.to_string()),
+    };
+
+    ast::Expression::Literal(literal)
+}
+
+/// An exact decimal number, `mantissa / 10^scale`
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+struct Decimal {
+    mantissa: i128,
+    scale: u32,
+}
+
+impl Decimal {
+    /// Parse a decimal literal; the ones with an exponent are floating point numbers and the
+    /// hexadecimal ones are left alone
+    fn parse(value: &str) -> Option<Decimal> {
+        let (integer, fraction) = match value.find('.') {
+            Some(point) => (&value[..point], &value[point + 1..]),
+            None => (value, ""),
+        };
+
+        if !integer
+            .chars()
+            .chain(fraction.chars())
+            .all(|c| c.is_ascii_digit())
+        {
+            return None;
+        }
+
+        let digits = format!("{}{}", integer, fraction);
+        Decimal::new(digits.parse().ok()?, fraction.len() as u32)
+    }
+
+    /// The number, if it fits in a decimal
+    fn new(mantissa: i128, scale: u32) -> Option<Decimal> {
+        let digits = mantissa.checked_abs()?.to_string().len() as u32;
+
+        if digits <= MAX_DIGITS && scale <= MAX_DIGITS {
+            Some(Decimal { mantissa, scale })
+        } else {
+            None
+        }
+    }
+
+    fn negate(self) -> Decimal {
+        Decimal {
+            mantissa: -self.mantissa,
+            scale: self.scale,
+        }
+    }
+
+    /// The mantissa of the number with a larger scale
+    fn rescale(self, scale: u32) -> Option<i128> {
+        self.mantissa
+            .checked_mul(10i128.checked_pow(scale - self.scale)?)
+    }
+
+    fn add(self, other: Decimal) -> Option<Decimal> {
+        let scale = self.scale.max(other.scale);
+        Decimal::new(
+            self.rescale(scale)?.checked_add(other.rescale(scale)?)?,
+            scale,
+        )
+    }
+
+    fn multiply(self, other: Decimal) -> Option<Decimal> {
+        Decimal::new(
+            self.mantissa.checked_mul(other.mantissa)?,
+            self.scale + other.scale,
+        )
+    }
+
+    /// The quotient, if it has a finite number of decimal digits; a division by zero is an
+    /// error when the statement is run, so it isn't folded
+    fn divide(self, other: Decimal) -> Option<Decimal> {
+        if other.mantissa == 0 {
+            return None;
+        }
+
+        let scale = self.scale.max(other.scale);
+        let dividend = self.rescale(scale)?;
+        let divisor = other.rescale(scale)?;
+
+        (0..=MAX_DIGITS)
+            .map(|scale| Some((dividend.checked_mul(10i128.checked_pow(scale)?)?, scale)))
+            .take_while(Option::is_some)
+            .flatten()
+            .find(|(dividend, _)| dividend % divisor == 0)
+            .and_then(|(dividend, scale)| Decimal::new(dividend / divisor, scale))
+    }
+
+    fn compare(self, other: Decimal) -> Option<Ordering> {
+        let scale = self.scale.max(other.scale);
+        Some(self.rescale(scale)?.cmp(&other.rescale(scale)?))
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let digits = format!(
+            "{:0>width$}",
+            self.mantissa.abs(),
+            width = self.scale as usize + 1
+        );
+        let (integer, fraction) = digits.split_at(digits.len() - self.scale as usize);
+        let sign = if self.mantissa < 0 { "-" } else { "" };
+
+        if fraction.is_empty() {
+            write!(f, "{}{}", sign, integer)
+        } else {
+            write!(f, "{}{}.{}", sign, integer, fraction)
+        }
+    }
+}
@@ -24,26 +24,208 @@
 use std::cmp::{Eq, Ord, Ordering, PartialEq, PartialOrd};
 use std::fmt;
 
-use pyo3::{IntoPy, PyObject, Python};
+use pyo3::types::PyAny;
+use pyo3::{FromPyObject, IntoPy, PyObject, PyResult, Python};
+
+/// The keywords of the grammar; a word matching one of them is a keyword, unless the keyword is
+/// not reserved and the word is in the place of a name
+pub(crate) const KEYWORDS: &[&str] = &[
+    "ALL",
+    "AND",
+    "AS",
+    "ASC",
+    "ATTACH",
+    "BETWEEN",
+    "BY",
+    "BOOLEAN",
+    "CASE",
+    "CAST",
+    "CHAR",
+    "COALESCE",
+    "COLLATE",
+    "CONCAT",
+    "COUNT",
+    "CROSS",
+    "CURRENT_DATE",
+    "CURRENT_TIME",
+    "CURRENT_TIMESTAMP",
+    "DATE",
+    "DECIMAL",
+    "DELETE",
+    "DESC",
+    "DESCRIBE",
+    "DISTINCT",
+    "DOUBLE",
+    "ELSE",
+    "END",
+    "EXPLAIN",
+    "EXCEPT",
+    "FALSE",
+    "FILE",
+    "FOR",
+    "FROM",
+    "FULL",
+    "GROUP",
+    "HAVING",
+    "IN",
+    "INNER",
+    "INSERT",
+    "INTERSECT",
+    "INTO",
+    "IS",
+    "ISNULL",
+    "JOIN",
+    "LEFT",
+    "LIMIT",
+    "LIKE",
+    "LOCAL",
+    "MAX",
+    "MIN",
+    "NATURAL",
+    "NOT",
+    "NOTNULL",
+    "NULL",
+    "OFFSET",
+    "ON",
+    "OR",
+    "ORDER",
+    "OUTER",
+    "PLAN",
+    "POWER",
+    "PRECISION",
+    "QUERY",
+    "RECURSIVE",
+    "REPLACE",
+    "RIGHT",
+    "SELECT",
+    "SET",
+    "SUBSTR",
+    "SUBSTRING",
+    "SUM",
+    "TABLE",
+    "THEN",
+    "TIME",
+    "TIMESTAMP",
+    "TO_DATE",
+    "TRUE",
+    "UPDATE",
+    "UNION",
+    "USING",
+    "VALUES",
+    "VARCHAR",
+    "WHEN",
+    "WHERE",
+    "WITH",
+    "ZONE",
+];
+
+/// The keywords which are not reserved, as in the `NonReservedKeyword` rule of the grammar; they
+/// are the keywords of the grammar which Exasol doesn't reserve
+pub(crate) const NON_RESERVED_KEYWORDS: &[&str] = &[
+    "ATTACH",
+    "CONCAT",
+    "COUNT",
+    "EXPLAIN",
+    "ISNULL",
+    "MAX",
+    "MIN",
+    "NOTNULL",
+    "OFFSET",
+    "PLAN",
+    "POWER",
+    "PRECISION",
+    "QUERY",
+    "SUBSTR",
+    "SUM",
+    "TO_DATE",
+];
+
+/// A range of byte offsets in the parsed SQL string
+#[derive(Serialize, Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Span {
+        Span { start, end }
+    }
+
+    /// The smallest span covering both spans
+    pub fn merge(self, other: Span) -> Span {
+        Span {
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+        }
+    }
+}
+
+impl IntoPy<PyObject> for Span {
+    fn into_py(self, py: Python) -> PyObject {
+        IntoPy::<PyObject>::into_py((self.start, self.end), py)
+    }
+}
+
+impl<'source> FromPyObject<'source> for Span {
+    fn extract(obj: &'source PyAny) -> PyResult<Self> {
+        let (start, end) = obj.extract()?;
+        Ok(Span::new(start, end))
+    }
+}
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(transparent)]
 pub struct Name {
     string: String,
+
+    /// where the name appears in the source, if it comes from the parser; it's ignored when
+    /// comparing names
+    #[serde(skip)]
+    span: Option<Span>,
 }
 
 impl Name {
     pub fn new(string: String) -> Name {
-        Name { string }
+        Name { string, span: None }
+    }
+
+    pub fn with_span(string: String, span: Span) -> Name {
+        Name {
+            string,
+            span: Some(span),
+        }
     }
 
     pub fn as_str(&self) -> &str {
         self.string.as_str()
     }
+
+    pub fn span(&self) -> Option<Span> {
+        self.span
+    }
+
+    /// Whether the name can be written without quotes: it's made of letters, digits and
+    /// underscores, and it's not a reserved keyword
+    fn is_plain(&self) -> bool {
+        let mut chars = self.string.chars();
+        let word = chars
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+        let upper = self.string.to_uppercase();
+
+        word && (!KEYWORDS.contains(&upper.as_str())
+            || NON_RESERVED_KEYWORDS.contains(&upper.as_str()))
+    }
 }
 
 impl fmt::Display for Name {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        self.string.fmt(f)
+        match self.is_plain() {
+            true => self.string.fmt(f),
+            false => write!(f, "\"{}\"", self.string.replace('"', "\"\"")),
+        }
     }
 }
 
@@ -106,3 +288,9 @@ impl IntoPy<PyObject> for Name {
         IntoPy::<PyObject>::into_py(self.string, py)
     }
 }
+
+impl<'source> FromPyObject<'source> for Name {
+    fn extract(obj: &'source PyAny) -> PyResult<Self> {
+        Ok(Name::new(obj.extract()?))
+    }
+}
@@ -0,0 +1,905 @@
+// MIT License
+//
+// Copyright (c) 2019 Daniele Esposti
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use pyo3::class::basic::CompareOp;
+use pyo3::prelude::*;
+use pyo3::PyObjectProtocol;
+
+use super::ast;
+use super::symbols::{Name, Span};
+
+/// Conversion of the AST into the node classes
+trait IntoNode {
+    fn into_node(self, py: Python) -> PyObject;
+
+    /// Collect the node objects, skipping names and operators
+    fn collect_nodes(self, py: Python, nodes: &mut Vec<PyObject>)
+    where
+        Self: Sized,
+    {
+        nodes.push(self.into_node(py));
+    }
+}
+
+/// Convert a statement into a tree of node objects
+pub fn into_tree(statement: ast::SqlStatement, py: Python) -> PyObject {
+    statement.into_node(py)
+}
+
+impl<T: IntoNode> IntoNode for Box<T> {
+    fn into_node(self, py: Python) -> PyObject {
+        (*self).into_node(py)
+    }
+
+    fn collect_nodes(self, py: Python, nodes: &mut Vec<PyObject>) {
+        (*self).collect_nodes(py, nodes)
+    }
+}
+
+impl<T: IntoNode> IntoNode for Option<T> {
+    fn into_node(self, py: Python) -> PyObject {
+        match self {
+            Some(node) => node.into_node(py),
+            None => py.None(),
+        }
+    }
+
+    fn collect_nodes(self, py: Python, nodes: &mut Vec<PyObject>) {
+        if let Some(node) = self {
+            node.collect_nodes(py, nodes)
+        }
+    }
+}
+
+impl<T: IntoNode> IntoNode for Vec<T> {
+    fn into_node(self, py: Python) -> PyObject {
+        let items: Vec<PyObject> = self.into_iter().map(|item| item.into_node(py)).collect();
+        items.into_py(py)
+    }
+
+    fn collect_nodes(self, py: Python, nodes: &mut Vec<PyObject>) {
+        for item in self {
+            item.collect_nodes(py, nodes)
+        }
+    }
+}
+
+/// Values converted the same way as in the dictionaries, which are not nodes
+macro_rules! leaves {
+    ($($type:ty),*) => {
+        $(
+            impl IntoNode for $type {
+                fn into_node(self, py: Python) -> PyObject {
+                    self.into_py(py)
+                }
+
+                fn collect_nodes(self, _py: Python, _nodes: &mut Vec<PyObject>) {}
+            }
+        )*
+    };
+}
+
+leaves!(
+    String,
+    usize,
+    bool,
+    Name,
+    Span,
+    ast::JoinType,
+    ast::SetOperator,
+    ast::UnaryOperator,
+    ast::BinaryOperator,
+    ast::ComparisonOperator,
+    ast::OrderingDirection
+);
+
+/// Enums whose variants hold a single node
+macro_rules! variants {
+    ($type:ident { $($variant:ident),* }) => {
+        impl IntoNode for ast::$type {
+            fn into_node(self, py: Python) -> PyObject {
+                match self {
+                    $(ast::$type::$variant(v) => v.into_node(py),)*
+                }
+            }
+
+            fn collect_nodes(self, py: Python, nodes: &mut Vec<PyObject>) {
+                match self {
+                    $(ast::$type::$variant(v) => v.collect_nodes(py, nodes),)*
+                }
+            }
+        }
+    };
+}
+
+variants!(SqlStatement {
+    Statement,
+    ExplainQueryPlan,
+    Attach,
+    Describe,
+    Error
+});
+variants!(Statement {
+    Select,
+    Insert,
+    Delete,
+    Update
+});
+variants!(SetExpression { Values, Query, Op });
+variants!(TableExpression {
+    Named,
+    Select,
+    Join,
+    Error
+});
+variants!(JoinConstraint { Expr, Columns });
+variants!(ResultColumn { AllFrom, Expr });
+variants!(Expression {
+    Literal,
+    QualifiedIdentifier,
+    MakeTuple,
+    Select,
+    Unary,
+    Binary,
+    Comparison,
+    In,
+    Between,
+    Case,
+    Coalesce,
+    Replace,
+    Substring,
+    ToDate,
+    Power,
+    Concat,
+    Sum,
+    Max,
+    Min,
+    Cast,
+    Right,
+    Count,
+    Unknown,
+    Parameter,
+    Error
+});
+
+/// The payloads of the enum variants exposed as node classes of their own
+mod payload {
+    use super::ast::{Expression, JoinType, Literal, ResultColumn};
+    use super::Name;
+
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    pub struct SelectAll {}
+
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    pub struct SelectDistinct {}
+
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    pub struct RegularJoin {
+        pub join: JoinType,
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    pub struct NaturalJoin {
+        pub join: JoinType,
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    pub struct CrossJoin {}
+
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    pub struct AllResultColumn {}
+
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    pub struct ListResultColumn {
+        pub values: Vec<ResultColumn>,
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    pub struct ListSetSpecification {
+        pub exprs: Vec<Expression>,
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    pub struct StringLiteral {
+        pub str: String,
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    pub struct NumericLiteral {
+        pub num: String,
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    pub struct NullLiteral {}
+
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    pub struct BooleanLiteral {
+        pub value: bool,
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    pub struct CurrentTimeLiteral {}
+
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    pub struct CurrentDateLiteral {}
+
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    pub struct CurrentTimestampLiteral {}
+
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    pub struct PlaceholderLiteral {}
+
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    pub struct TimeLiteral {
+        pub time: String,
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    pub struct DateLiteral {
+        pub date: String,
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    pub struct TimestampLiteral {
+        pub ts: String,
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    pub struct PositionalParameter {
+        pub index: usize,
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    pub struct NamedParameter {
+        pub name: Name,
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    pub struct VariableParameter {
+        pub name: Name,
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    pub struct BooleanDataType {}
+
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    pub struct CharDataType {
+        pub s: Literal,
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    pub struct DecimalDataType {
+        pub p: Literal,
+        pub s: Literal,
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    pub struct DateDataType {}
+
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    pub struct DoublePrecisionDataType {}
+
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    pub struct TimestampDataType {}
+
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    pub struct LocalTimestampDataType {}
+
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    pub struct VarcharDataType {
+        pub s: Literal,
+    }
+}
+
+impl IntoNode for ast::SelectMode {
+    fn into_node(self, py: Python) -> PyObject {
+        match self {
+            ast::SelectMode::All => payload::SelectAll {}.into_node(py),
+            ast::SelectMode::Distinct => payload::SelectDistinct {}.into_node(py),
+        }
+    }
+}
+
+impl IntoNode for ast::JoinOperator {
+    fn into_node(self, py: Python) -> PyObject {
+        match self {
+            ast::JoinOperator::Join(join) => payload::RegularJoin { join }.into_node(py),
+            ast::JoinOperator::Natural(join) => payload::NaturalJoin { join }.into_node(py),
+            ast::JoinOperator::Cross => payload::CrossJoin {}.into_node(py),
+        }
+    }
+}
+
+impl IntoNode for ast::ResultColumns {
+    fn into_node(self, py: Python) -> PyObject {
+        match self {
+            ast::ResultColumns::All => payload::AllResultColumn {}.into_node(py),
+            ast::ResultColumns::List(values) => payload::ListResultColumn { values }.into_node(py),
+        }
+    }
+}
+
+impl IntoNode for ast::SetSpecification {
+    fn into_node(self, py: Python) -> PyObject {
+        match self {
+            ast::SetSpecification::Select(v) => v.into_node(py),
+            ast::SetSpecification::List(exprs) => {
+                payload::ListSetSpecification { exprs }.into_node(py)
+            }
+            ast::SetSpecification::Name(v) => v.into_node(py),
+        }
+    }
+
+    fn collect_nodes(self, py: Python, nodes: &mut Vec<PyObject>) {
+        match self {
+            ast::SetSpecification::Name(_) => (),
+            set => nodes.push(set.into_node(py)),
+        }
+    }
+}
+
+impl IntoNode for ast::Literal {
+    fn into_node(self, py: Python) -> PyObject {
+        match self {
+            ast::Literal::String(str) => payload::StringLiteral { str }.into_node(py),
+            ast::Literal::Numeric(num) => payload::NumericLiteral { num }.into_node(py),
+            ast::Literal::Null => payload::NullLiteral {}.into_node(py),
+            ast::Literal::Boolean(value) => payload::BooleanLiteral { value }.into_node(py),
+            ast::Literal::CurrentTime => payload::CurrentTimeLiteral {}.into_node(py),
+            ast::Literal::CurrentDate => payload::CurrentDateLiteral {}.into_node(py),
+            ast::Literal::CurrentTimestamp => payload::CurrentTimestampLiteral {}.into_node(py),
+            ast::Literal::Time(time) => payload::TimeLiteral { time }.into_node(py),
+            ast::Literal::Date(date) => payload::DateLiteral { date }.into_node(py),
+            ast::Literal::Timestamp(ts) => payload::TimestampLiteral { ts }.into_node(py),
+            ast::Literal::Placeholder => payload::PlaceholderLiteral {}.into_node(py),
+        }
+    }
+}
+
+impl IntoNode for ast::Parameter {
+    fn into_node(self, py: Python) -> PyObject {
+        match self {
+            ast::Parameter::Positional(index) => {
+                payload::PositionalParameter { index }.into_node(py)
+            }
+            ast::Parameter::Named(name) => payload::NamedParameter { name }.into_node(py),
+            ast::Parameter::Variable(name) => payload::VariableParameter { name }.into_node(py),
+        }
+    }
+}
+
+impl IntoNode for ast::DataType {
+    fn into_node(self, py: Python) -> PyObject {
+        match self {
+            ast::DataType::Boolean => payload::BooleanDataType {}.into_node(py),
+            ast::DataType::Char(s) => payload::CharDataType { s }.into_node(py),
+            ast::DataType::Date => payload::DateDataType {}.into_node(py),
+            ast::DataType::Decimal { p, s } => payload::DecimalDataType { p, s }.into_node(py),
+            ast::DataType::DoublePrecision => payload::DoublePrecisionDataType {}.into_node(py),
+            ast::DataType::Timestamp => payload::TimestampDataType {}.into_node(py),
+            ast::DataType::LocalTimestamp => payload::LocalTimestampDataType {}.into_node(py),
+            ast::DataType::Varchar(s) => payload::VarcharDataType { s }.into_node(py),
+        }
+    }
+}
+
+/// The field of a node behind an attribute, when they are named differently
+macro_rules! field {
+    ($node:expr, $attr:ident) => {
+        $node.$attr
+    };
+    ($node:expr, $attr:ident = $field:ident) => {
+        $node.$field
+    };
+}
+
+/// Declare a node class for each AST struct, exposing its fields as read-only attributes. The
+/// Python type of each attribute is only used to generate the stub file.
+macro_rules! nodes {
+    ($($class:ident($node:ty) { $($attr:ident $(= $field:ident)?: $pytype:expr),* })*) => {
+        $(
+            #[pyclass]
+            pub struct $class {
+                node: $node,
+            }
+
+            impl IntoNode for $node {
+                fn into_node(self, py: Python) -> PyObject {
+                    $class { node: self }.into_py(py)
+                }
+            }
+
+            #[pymethods]
+            impl $class {
+                $(
+                    #[getter]
+                    fn $attr(&self, py: Python) -> PyObject {
+                        field!(self.node, $attr $(= $field)?).clone().into_node(py)
+                    }
+                )*
+
+                /// The child nodes, in the order of the attributes
+                #[allow(unused_mut, unused_variables)]
+                fn children(&self, py: Python) -> Vec<PyObject> {
+                    let mut nodes = Vec::new();
+                    $(field!(self.node, $attr $(= $field)?).clone().collect_nodes(py, &mut nodes);)*
+                    nodes
+                }
+            }
+
+            #[pyproto]
+            impl<'p> PyObjectProtocol<'p> for $class {
+                #[allow(unused_variables)]
+                fn __repr__(&'p self) -> PyResult<String> {
+                    let gil = Python::acquire_gil();
+                    let py = gil.python();
+                    let attrs: Vec<String> = vec![
+                        $(format!("{}={}", stringify!($attr), self.$attr(py).as_ref(py).repr()?),)*
+                    ];
+
+                    Ok(format!("{}({})", stringify!($class), attrs.join(", ")))
+                }
+
+                fn __richcmp__(&'p self, other: &'p PyAny, op: CompareOp) -> PyResult<PyObject> {
+                    let py = other.py();
+                    let other = match other.extract::<PyRef<$class>>() {
+                        Ok(other) => other,
+                        Err(_) => return Ok(py.NotImplemented()),
+                    };
+
+                    match op {
+                        CompareOp::Eq => Ok((self.node == other.node).into_py(py)),
+                        CompareOp::Ne => Ok((self.node != other.node).into_py(py)),
+                        _ => Ok(py.NotImplemented()),
+                    }
+                }
+            }
+        )*
+
+        const CLASSES: &[(&str, &[(&str, &str)])] = &[
+            $((stringify!($class), &[$((stringify!($attr), $pytype)),*]),)*
+        ];
+
+        /// Register the node classes into the module
+        pub fn add_classes(m: &PyModule) -> PyResult<()> {
+            $(m.add_class::<$class>()?;)*
+
+            Ok(())
+        }
+    };
+}
+
+nodes! {
+    SelectStatement(ast::SelectStatement) {
+        common: "List[CommonTableExpression]",
+        recursive: "bool",
+        expr: "SetExpression",
+        order_by: "List[Ordering]",
+        limit: "Optional[Limit]"
+    }
+    InsertStatement(ast::InsertStatement) {
+        table_name: "List[str]",
+        columns: "Optional[List[str]]",
+        source: "SetExpression"
+    }
+    DeleteStatement(ast::DeleteStatement) {
+        table_name: "List[str]",
+        where_expr: "Optional[Expression]"
+    }
+    UpdateStatement(ast::UpdateStatement) {
+        table_name: "List[str]",
+        assignments: "List[Assignment]",
+        where_expr: "Optional[Expression]"
+    }
+    AttachStatement(ast::AttachStatement) {
+        qualified_name: "List[str]",
+        path: "str"
+    }
+    DescribeStatement(ast::DescribeStatement) {
+        qualified_name: "List[str]"
+    }
+    CommonTableExpression(ast::CommonTableExpression) {
+        identifier: "str",
+        column_names: "Optional[List[str]]",
+        query: "SelectStatement"
+    }
+    Assignment(ast::Assignment) {
+        columns: "List[str]",
+        expr: "Expression"
+    }
+    SelectAll(payload::SelectAll) {}
+    SelectDistinct(payload::SelectDistinct) {}
+    ValuesSetExpression(ast::ValuesSetExpression) {
+        values: "List[List[Expression]]"
+    }
+    QuerySetExpression(ast::QuerySetExpression) {
+        mode: "SelectMode",
+        columns: "ResultColumns",
+        from_ = from: "List[TableExpression]",
+        where_expr: "Optional[Expression]",
+        group_by: "Optional[GroupBy]"
+    }
+    OpSetExpression(ast::OpSetExpression) {
+        op: "str",
+        left: "SetExpression",
+        right: "SetExpression"
+    }
+    NamedTableExpression(ast::NamedTableExpression) {
+        name: "List[str]",
+        alias: "Optional[str]"
+    }
+    SelectTableExpression(ast::SelectTableExpression) {
+        select: "SelectStatement",
+        alias: "Optional[str]"
+    }
+    JoinTableExpression(ast::JoinTableExpression) {
+        left: "TableExpression",
+        right: "TableExpression",
+        op: "JoinOperator",
+        constraint: "JoinConstraint"
+    }
+    RegularJoin(payload::RegularJoin) {
+        join: "str"
+    }
+    NaturalJoin(payload::NaturalJoin) {
+        join: "str"
+    }
+    CrossJoin(payload::CrossJoin) {}
+    ColumnsJoinConstraint(ast::ColumnsJoinConstraint) {
+        columns: "List[str]"
+    }
+    AllResultColumn(payload::AllResultColumn) {}
+    ListResultColumn(payload::ListResultColumn) {
+        values: "List[ResultColumn]"
+    }
+    ExprResultColumn(ast::ExprResultColumn) {
+        expr: "Expression",
+        rename: "Optional[str]"
+    }
+    GroupBy(ast::GroupBy) {
+        groupings: "List[Expression]",
+        having: "Optional[Expression]"
+    }
+    QualifiedIdentifierExpression(ast::QualifiedIdentifierExpression) {
+        identifiers: "List[str]"
+    }
+    MakeTupleExpression(ast::MakeTupleExpression) {
+        exprs: "List[Expression]"
+    }
+    UnaryExpression(ast::UnaryExpression) {
+        op: "str",
+        expr: "Expression"
+    }
+    BinaryExpression(ast::BinaryExpression) {
+        op: "str",
+        left: "Expression",
+        right: "Expression"
+    }
+    ComparisonExpression(ast::ComparisonExpression) {
+        op: "str",
+        left: "Expression",
+        right: "Expression"
+    }
+    InExpression(ast::InExpression) {
+        expr: "Expression",
+        set: "SetSpecification"
+    }
+    ListSetSpecification(payload::ListSetSpecification) {
+        exprs: "List[Expression]"
+    }
+    BetweenExpression(ast::BetweenExpression) {
+        expr: "Expression",
+        lower: "Expression",
+        upper: "Expression"
+    }
+    CaseExpression(ast::CaseExpression) {
+        expr: "Optional[Expression]",
+        when_part: "List[WhenClause]",
+        else_part: "Optional[Expression]"
+    }
+    WhenClause(ast::WhenClause) {
+        guard: "Expression",
+        body: "Expression"
+    }
+    CoalesceExpression(ast::CoalesceExpression) {
+        exprs: "List[Expression]"
+    }
+    ReplaceExpression(ast::ReplaceExpression) {
+        string: "Expression",
+        search_string: "Expression",
+        replace_string: "Optional[Expression]"
+    }
+    SubstringExpression(ast::SubstringExpression) {
+        string: "Expression",
+        position: "Expression",
+        length: "Optional[Expression]"
+    }
+    ToDateExpression(ast::ToDateExpression) {
+        string: "Expression",
+        format: "Optional[Expression]"
+    }
+    PowerExpression(ast::PowerExpression) {
+        base: "Expression",
+        exponent: "Expression"
+    }
+    ConcatExpression(ast::ConcatExpression) {
+        exprs: "List[Expression]"
+    }
+    MaxExpression(ast::MaxExpression) {
+        mode: "SelectMode",
+        expr: "Expression"
+    }
+    MinExpression(ast::MinExpression) {
+        mode: "SelectMode",
+        expr: "Expression"
+    }
+    SumExpression(ast::SumExpression) {
+        mode: "SelectMode",
+        expr: "Expression"
+    }
+    CastExpression(ast::CastExpression) {
+        expr: "Expression",
+        data_type: "DataType"
+    }
+    RightExpression(ast::RightExpression) {
+        string: "Expression",
+        length: "Expression"
+    }
+    CountExpression(ast::CountExpression) {
+        columns: "ResultColumns",
+        mode: "SelectMode"
+    }
+    UnknownExpression(ast::UnknownExpression) {
+        name: "List[str]",
+        exprs: "List[Expression]"
+    }
+    ErrorNode(ast::ErrorNode) {
+        message: "str",
+        token: "Optional[str]",
+        location: "int",
+        text: "str",
+        span: "Tuple[int, int]"
+    }
+    StringLiteral(payload::StringLiteral) {
+        str: "str"
+    }
+    NumericLiteral(payload::NumericLiteral) {
+        num: "str"
+    }
+    NullLiteral(payload::NullLiteral) {}
+    BooleanLiteral(payload::BooleanLiteral) {
+        value: "bool"
+    }
+    CurrentTimeLiteral(payload::CurrentTimeLiteral) {}
+    CurrentDateLiteral(payload::CurrentDateLiteral) {}
+    CurrentTimestampLiteral(payload::CurrentTimestampLiteral) {}
+    PlaceholderLiteral(payload::PlaceholderLiteral) {}
+    TimeLiteral(payload::TimeLiteral) {
+        time: "str"
+    }
+    DateLiteral(payload::DateLiteral) {
+        date: "str"
+    }
+    TimestampLiteral(payload::TimestampLiteral) {
+        ts: "str"
+    }
+    PositionalParameter(payload::PositionalParameter) {
+        index: "int"
+    }
+    NamedParameter(payload::NamedParameter) {
+        name: "str"
+    }
+    VariableParameter(payload::VariableParameter) {
+        name: "str"
+    }
+    Ordering(ast::Ordering) {
+        expr: "Expression",
+        collation: "Optional[str]",
+        direction: "str"
+    }
+    Limit(ast::Limit) {
+        number_rows: "Expression",
+        offset_value: "Optional[Expression]"
+    }
+    BooleanDataType(payload::BooleanDataType) {}
+    CharDataType(payload::CharDataType) {
+        s: "Literal"
+    }
+    DateDataType(payload::DateDataType) {}
+    DecimalDataType(payload::DecimalDataType) {
+        p: "Literal",
+        s: "Literal"
+    }
+    DoublePrecisionDataType(payload::DoublePrecisionDataType) {}
+    TimestampDataType(payload::TimestampDataType) {}
+    LocalTimestampDataType(payload::LocalTimestampDataType) {}
+    VarcharDataType(payload::VarcharDataType) {
+        s: "Literal"
+    }
+}
+
+/// The node types standing for the AST enums
+const ALIASES: &[(&str, &[&str])] = &[
+    (
+        "Statement",
+        &[
+            "SelectStatement",
+            "InsertStatement",
+            "DeleteStatement",
+            "UpdateStatement",
+            "AttachStatement",
+            "DescribeStatement",
+            "ErrorNode",
+        ],
+    ),
+    ("SelectMode", &["SelectAll", "SelectDistinct"]),
+    (
+        "SetExpression",
+        &[
+            "ValuesSetExpression",
+            "QuerySetExpression",
+            "OpSetExpression",
+        ],
+    ),
+    (
+        "TableExpression",
+        &[
+            "NamedTableExpression",
+            "SelectTableExpression",
+            "JoinTableExpression",
+            "ErrorNode",
+        ],
+    ),
+    ("JoinOperator", &["RegularJoin", "NaturalJoin", "CrossJoin"]),
+    ("JoinConstraint", &["Expression", "ColumnsJoinConstraint"]),
+    ("ResultColumns", &["AllResultColumn", "ListResultColumn"]),
+    ("ResultColumn", &["str", "ExprResultColumn"]),
+    (
+        "SetSpecification",
+        &["SelectStatement", "ListSetSpecification", "List[str]"],
+    ),
+    (
+        "Literal",
+        &[
+            "StringLiteral",
+            "NumericLiteral",
+            "NullLiteral",
+            "BooleanLiteral",
+            "CurrentTimeLiteral",
+            "CurrentDateLiteral",
+            "CurrentTimestampLiteral",
+            "PlaceholderLiteral",
+            "TimeLiteral",
+            "DateLiteral",
+            "TimestampLiteral",
+        ],
+    ),
+    (
+        "Parameter",
+        &["PositionalParameter", "NamedParameter", "VariableParameter"],
+    ),
+    (
+        "Expression",
+        &[
+            "Literal",
+            "QualifiedIdentifierExpression",
+            "MakeTupleExpression",
+            "SelectStatement",
+            "UnaryExpression",
+            "BinaryExpression",
+            "ComparisonExpression",
+            "InExpression",
+            "BetweenExpression",
+            "CaseExpression",
+            "CoalesceExpression",
+            "ReplaceExpression",
+            "SubstringExpression",
+            "ToDateExpression",
+            "PowerExpression",
+            "ConcatExpression",
+            "SumExpression",
+            "MaxExpression",
+            "MinExpression",
+            "CastExpression",
+            "RightExpression",
+            "CountExpression",
+            "UnknownExpression",
+            "Parameter",
+            "ErrorNode",
+        ],
+    ),
+    (
+        "DataType",
+        &[
+            "BooleanDataType",
+            "CharDataType",
+            "DateDataType",
+            "DecimalDataType",
+            "DoublePrecisionDataType",
+            "TimestampDataType",
+            "LocalTimestampDataType",
+            "VarcharDataType",
+        ],
+    ),
+];
+
+/// The functions of the extension module
+const FUNCTIONS: &[&str] = &[
+    "def parse(sql_str: str) -> Dict[str, Any]: ...",
+    "def parse_lenient(sql_str: str) -> Dict[str, Any]: ...",
+    "def parse_many(sql_strs: List[str], threads: Optional[int] = None) -> List[Dict[str, Any]]: ...",
+    "def parse_tree(sql_str: str) -> Statement: ...",
+    "def to_sql(statement: Dict[str, Any]) -> str: ...",
+    "def format_sql(sql_str: str, **options: Any) -> str: ...",
+    "def fingerprint(sql_str: str) -> Tuple[str, str]: ...",
+    "def diff(sql_a: str, sql_b: str) -> List[Dict[str, Any]]: ...",
+    "def tables(sql_str: str) -> List[str]: ...",
+    "def ctes(sql_str: str) -> List[str]: ...",
+    "def columns(sql_str: str) -> List[str]: ...",
+    "def functions(sql_str: str) -> List[str]: ...",
+    "def literals(sql_str: str) -> List[str]: ...",
+    "def lint(sql_str: str, disable: Optional[List[str]] = None) -> List[Dict[str, Any]]: ...",
+    "def parameters(sql_str: str) -> List[Dict[str, Any]]: ...",
+    "def substitute(sql_str: str, *values: Any, **named: Any) -> str: ...",
+    "def simplify(sql_str: str) -> str: ...",
+    "def predicates(sql_str: str) -> List[Dict[str, Any]]: ...",
+    "def join_graphs(sql_str: str) -> List[Dict[str, Any]]: ...",
+    "def join_graphs_dot(sql_str: str) -> str: ...",
+    "def complexity(sql_str: str) -> Dict[str, Any]: ...",
+    "def classify(sql_str: str) -> Dict[str, Any]: ...",
+    "def inline_cte(sql_str: str, name: str) -> str: ...",
+    "def extract_cte(sql_str: str, name: str) -> str: ...",
+];
+
+fn union(name: &str, types: &[&str]) -> String {
+    let types: Vec<String> = types.iter().map(|t| format!("    {},\n", t)).collect();
+    format!("{} = Union[\n{}]\n", name, types.concat())
+}
+
+/// Generate the content of the `.pyi` stub file of the extension module
+pub fn stub() -> String {
+    let mut stub = String::from("# Generated by `hephaestus::tree::stub()`, do not edit.\n\n");
+    stub.push_str("from typing import Any, Dict, List, Optional, Tuple, Union\n\n");
+
+    for function in FUNCTIONS {
+        stub.push_str(&format!("{}\n", function));
+    }
+
+    for (name, types) in ALIASES {
+        stub.push_str(&format!("\n{}", union(name, types)));
+    }
+
+    let classes: Vec<&str> = CLASSES.iter().map(|(class, _)| *class).collect();
+    stub.push_str(&format!("\n{}", union("Node", &classes)));
+
+    for (class, attrs) in CLASSES {
+        stub.push_str(&format!("\nclass {}:\n", class));
+
+        for (attr, pytype) in attrs.iter() {
+            stub.push_str(&format!("    {}: {}\n", attr, pytype));
+        }
+
+        stub.push_str("    def children(self) -> List[Node]: ...\n");
+    }
+
+    stub
+}
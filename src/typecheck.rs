@@ -0,0 +1,697 @@
+// MIT License
+//
+// Copyright (c) 2019 Daniele Esposti
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::fmt;
+
+use super::ast;
+use super::symbols;
+use super::types::DataType;
+use super::visit::{self, Visitor};
+
+/// A type error found while checking an expression
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct TypeError {
+    pub message: String,
+}
+
+impl TypeError {
+    fn new(message: String) -> TypeError {
+        TypeError { message }
+    }
+}
+
+impl fmt::Display for TypeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "{}", self.message)
+    }
+}
+
+/// An expression annotated with its inferred type
+#[derive(Debug, PartialEq, Clone)]
+pub struct TypedExpression<'a> {
+    /// the annotated expression
+    pub expr: &'a ast::Expression,
+
+    /// the type inferred for the expression; `Generic` when it cannot be determined
+    pub data_type: DataType,
+
+    /// the annotated sub-expressions, in source order
+    pub children: Vec<TypedExpression<'a>>,
+}
+
+/// How the return type of a function in the catalogue is determined
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum ReturnType {
+    /// the function always returns the same type
+    Fixed(DataType),
+
+    /// the function returns the type of its first argument
+    FirstArgument,
+
+    /// the function returns the common type of all its arguments
+    Common,
+}
+
+/// Catalogue of the Exasol functions which are not part of the grammar, keyed by upper case name
+const FUNCTIONS: &[(&str, ReturnType)] = &[
+    ("ABS", ReturnType::Fixed(DataType::Numeric)),
+    ("ACOS", ReturnType::Fixed(DataType::Numeric)),
+    ("ADD_DAYS", ReturnType::FirstArgument),
+    ("ADD_HOURS", ReturnType::Fixed(DataType::Timestamp)),
+    ("ADD_MINUTES", ReturnType::Fixed(DataType::Timestamp)),
+    ("ADD_MONTHS", ReturnType::FirstArgument),
+    ("ADD_SECONDS", ReturnType::Fixed(DataType::Timestamp)),
+    ("ADD_WEEKS", ReturnType::FirstArgument),
+    ("ADD_YEARS", ReturnType::FirstArgument),
+    ("ASIN", ReturnType::Fixed(DataType::Numeric)),
+    ("ATAN", ReturnType::Fixed(DataType::Numeric)),
+    ("AVG", ReturnType::Fixed(DataType::Numeric)),
+    ("CEIL", ReturnType::Fixed(DataType::Numeric)),
+    ("CEILING", ReturnType::Fixed(DataType::Numeric)),
+    ("CONVERT_TZ", ReturnType::Fixed(DataType::Timestamp)),
+    ("COS", ReturnType::Fixed(DataType::Numeric)),
+    ("CURDATE", ReturnType::Fixed(DataType::Date)),
+    ("DAY", ReturnType::Fixed(DataType::Numeric)),
+    ("DAYS_BETWEEN", ReturnType::Fixed(DataType::Numeric)),
+    ("EXP", ReturnType::Fixed(DataType::Numeric)),
+    ("FLOOR", ReturnType::Fixed(DataType::Numeric)),
+    ("GREATEST", ReturnType::Common),
+    ("GROUP_CONCAT", ReturnType::Fixed(DataType::Varchar)),
+    ("HASH_MD5", ReturnType::Fixed(DataType::Varchar)),
+    ("HASH_SHA1", ReturnType::Fixed(DataType::Varchar)),
+    ("HOUR", ReturnType::Fixed(DataType::Numeric)),
+    ("IFNULL", ReturnType::Common),
+    ("INITCAP", ReturnType::Fixed(DataType::Varchar)),
+    ("INSTR", ReturnType::Fixed(DataType::Numeric)),
+    ("LAST_DAY", ReturnType::Fixed(DataType::Date)),
+    ("LCASE", ReturnType::Fixed(DataType::Varchar)),
+    ("LEAST", ReturnType::Common),
+    ("LENGTH", ReturnType::Fixed(DataType::Numeric)),
+    ("LISTAGG", ReturnType::Fixed(DataType::Varchar)),
+    ("LN", ReturnType::Fixed(DataType::Numeric)),
+    ("LOCATE", ReturnType::Fixed(DataType::Numeric)),
+    ("LOG", ReturnType::Fixed(DataType::Numeric)),
+    ("LOWER", ReturnType::Fixed(DataType::Varchar)),
+    ("LPAD", ReturnType::Fixed(DataType::Varchar)),
+    ("LTRIM", ReturnType::Fixed(DataType::Varchar)),
+    ("MEDIAN", ReturnType::Fixed(DataType::Numeric)),
+    ("MINUTE", ReturnType::Fixed(DataType::Numeric)),
+    ("MOD", ReturnType::Fixed(DataType::Numeric)),
+    ("MONTH", ReturnType::Fixed(DataType::Numeric)),
+    ("MONTHS_BETWEEN", ReturnType::Fixed(DataType::Numeric)),
+    ("NOW", ReturnType::Fixed(DataType::Timestamp)),
+    ("NVL", ReturnType::Common),
+    ("OCTET_LENGTH", ReturnType::Fixed(DataType::Numeric)),
+    ("REGEXP_REPLACE", ReturnType::Fixed(DataType::Varchar)),
+    ("REGEXP_SUBSTR", ReturnType::Fixed(DataType::Varchar)),
+    ("REPEAT", ReturnType::Fixed(DataType::Varchar)),
+    ("REVERSE", ReturnType::Fixed(DataType::Varchar)),
+    ("ROUND", ReturnType::FirstArgument),
+    ("RPAD", ReturnType::Fixed(DataType::Varchar)),
+    ("RTRIM", ReturnType::Fixed(DataType::Varchar)),
+    ("SECOND", ReturnType::Fixed(DataType::Numeric)),
+    ("SIGN", ReturnType::Fixed(DataType::Numeric)),
+    ("SIN", ReturnType::Fixed(DataType::Numeric)),
+    ("SOUNDEX", ReturnType::Fixed(DataType::Varchar)),
+    ("SQRT", ReturnType::Fixed(DataType::Numeric)),
+    ("STDDEV", ReturnType::Fixed(DataType::Numeric)),
+    ("SYSDATE", ReturnType::Fixed(DataType::Date)),
+    ("SYSTIMESTAMP", ReturnType::Fixed(DataType::Timestamp)),
+    ("TAN", ReturnType::Fixed(DataType::Numeric)),
+    ("TO_CHAR", ReturnType::Fixed(DataType::Varchar)),
+    ("TO_NUMBER", ReturnType::Fixed(DataType::Numeric)),
+    ("TO_TIMESTAMP", ReturnType::Fixed(DataType::Timestamp)),
+    ("TRANSLATE", ReturnType::Fixed(DataType::Varchar)),
+    ("TRIM", ReturnType::Fixed(DataType::Varchar)),
+    ("TRUNC", ReturnType::FirstArgument),
+    ("UCASE", ReturnType::Fixed(DataType::Varchar)),
+    ("UPPER", ReturnType::Fixed(DataType::Varchar)),
+    ("VARIANCE", ReturnType::Fixed(DataType::Numeric)),
+    ("WEEK", ReturnType::Fixed(DataType::Numeric)),
+    ("YEAR", ReturnType::Fixed(DataType::Numeric)),
+];
+
+fn lookup_function(name: &[symbols::Name]) -> Option<ReturnType> {
+    let name = name.last()?;
+
+    FUNCTIONS
+        .iter()
+        .find(|(function, _)| name == *function)
+        .map(|(_, return_type)| *return_type)
+}
+
+/// Return the type both values can be converted to for comparisons and conditional
+/// expressions, if there is one
+pub fn common_type(left: DataType, right: DataType) -> Option<DataType> {
+    match (left, right) {
+        (l, r) if l == r => Some(l),
+        (DataType::Generic, other) | (other, DataType::Generic) => Some(other),
+        (DataType::Date, DataType::Timestamp) | (DataType::Timestamp, DataType::Date) => {
+            Some(DataType::Timestamp)
+        }
+        // string literals are implicitly converted to date and time values
+        (DataType::Varchar, other) | (other, DataType::Varchar)
+            if other == DataType::Date
+                || other == DataType::Time
+                || other == DataType::Timestamp =>
+        {
+            Some(other)
+        }
+        _ => None,
+    }
+}
+
+/// Function resolving the type of a (qualified) column reference
+pub type ColumnTypes<'r> = Box<dyn Fn(&[symbols::Name]) -> Option<DataType> + 'r>;
+
+/// Infers the types of expressions and collects the type errors found on the way.
+///
+/// Column references are `Generic` unless a resolver for their types is provided with
+/// `with_column_types`.
+pub struct TypeChecker<'r> {
+    column_types: Option<ColumnTypes<'r>>,
+    errors: Vec<TypeError>,
+}
+
+impl<'r> Default for TypeChecker<'r> {
+    fn default() -> Self {
+        TypeChecker::new()
+    }
+}
+
+impl<'r> TypeChecker<'r> {
+    pub fn new() -> TypeChecker<'r> {
+        TypeChecker {
+            column_types: None,
+            errors: Vec::new(),
+        }
+    }
+
+    /// Use the given function to look up the type of a (qualified) column reference
+    pub fn with_column_types<F>(mut self, resolver: F) -> TypeChecker<'r>
+    where
+        F: Fn(&[symbols::Name]) -> Option<DataType> + 'r,
+    {
+        self.column_types = Some(Box::new(resolver));
+        self
+    }
+
+    /// The errors found so far
+    pub fn errors(&self) -> &[TypeError] {
+        &self.errors
+    }
+
+    pub fn into_errors(self) -> Vec<TypeError> {
+        self.errors
+    }
+
+    fn error(&mut self, message: String) {
+        self.errors.push(TypeError::new(message));
+    }
+
+    /// Check every expression of the statement, including the nested queries
+    pub fn check_statement(&mut self, statement: &ast::SqlStatement) {
+        self.visit_sql_statement(statement);
+    }
+
+    fn expect_logical(&mut self, expr: &ast::Expression, context: &str) {
+        let data_type = self.annotate(expr).data_type;
+
+        if data_type != DataType::Logical && data_type != DataType::Generic {
+            self.error(format!(
+                "{} must be of type LOGICAL, found {}",
+                context, data_type
+            ));
+        }
+    }
+
+    fn expect_type(&mut self, data_type: DataType, expected: DataType, context: &str) {
+        if data_type != expected && data_type != DataType::Generic {
+            self.error(format!(
+                "{} expects {}, found {}",
+                context, expected, data_type
+            ));
+        }
+    }
+
+    fn unify(&mut self, types: &[DataType], context: &str) -> DataType {
+        let mut result = DataType::Generic;
+
+        for data_type in types {
+            match common_type(result, *data_type) {
+                Some(t) => result = t,
+                None => {
+                    self.error(format!(
+                        "{} mixes incompatible types {} and {}",
+                        context, result, data_type
+                    ));
+                    return DataType::Generic;
+                }
+            }
+        }
+
+        result
+    }
+
+    fn compare(&mut self, op: &str, left: DataType, right: DataType, ordered: bool) {
+        match common_type(left, right) {
+            None => self.error(format!("cannot compare {} with {}", left, right)),
+            Some(t) if ordered && t != DataType::Generic && !t.is_ordered() => self.error(format!(
+                "cannot use {} on {} values, they are not ordered",
+                op, t
+            )),
+            Some(DataType::Geometry) => self.error(format!("cannot use {} on GEOMETRY values", op)),
+            Some(_) => {}
+        }
+    }
+
+    fn arithmetic(
+        &mut self,
+        op: &ast::BinaryOperator,
+        left: DataType,
+        right: DataType,
+    ) -> DataType {
+        use super::types::DataType::*;
+
+        let (symbol, result) = match op {
+            ast::BinaryOperator::Add => (
+                "+",
+                match (left, right) {
+                    (Numeric, Numeric) => Some(Numeric),
+                    (Date, Numeric) | (Numeric, Date) => Some(Date),
+                    (Timestamp, Numeric) | (Numeric, Timestamp) => Some(Timestamp),
+                    (Generic, Numeric) | (Numeric, Generic) | (Generic, Generic) => Some(Generic),
+                    (Generic, Date) | (Date, Generic) => Some(Date),
+                    (Generic, Timestamp) | (Timestamp, Generic) => Some(Timestamp),
+                    _ => None,
+                },
+            ),
+            ast::BinaryOperator::Subtract => (
+                "-",
+                match (left, right) {
+                    (Numeric, Numeric) => Some(Numeric),
+                    (Date, Numeric) => Some(Date),
+                    (Timestamp, Numeric) => Some(Timestamp),
+                    (Date, Date) => Some(Numeric),
+                    (Generic, Numeric) | (Numeric, Generic) | (Generic, Generic) => Some(Generic),
+                    (Date, Generic) | (Generic, Date) => Some(Generic),
+                    (Timestamp, Generic) | (Generic, Timestamp) | (Timestamp, Timestamp) => {
+                        Some(Generic)
+                    }
+                    _ => None,
+                },
+            ),
+            ast::BinaryOperator::Multiply | ast::BinaryOperator::Divide => (
+                if *op == ast::BinaryOperator::Multiply {
+                    "*"
+                } else {
+                    "/"
+                },
+                match (left, right) {
+                    (Numeric, Numeric) => Some(Numeric),
+                    (Generic, Numeric) | (Numeric, Generic) | (Generic, Generic) => Some(Numeric),
+                    _ => None,
+                },
+            ),
+            _ => unreachable!(),
+        };
+
+        match result {
+            Some(t) => t,
+            None => {
+                self.error(format!("cannot apply {} to {} and {}", symbol, left, right));
+                Generic
+            }
+        }
+    }
+
+    /// Infer the type of an expression and of all its sub-expressions
+    pub fn annotate<'e>(&mut self, expr: &'e ast::Expression) -> TypedExpression<'e> {
+        let mut children = Vec::new();
+        let data_type = self.infer(expr, &mut children);
+
+        TypedExpression {
+            expr,
+            data_type,
+            children,
+        }
+    }
+
+    fn child<'e>(
+        &mut self,
+        expr: &'e ast::Expression,
+        children: &mut Vec<TypedExpression<'e>>,
+    ) -> DataType {
+        let typed = self.annotate(expr);
+        let data_type = typed.data_type;
+        children.push(typed);
+        data_type
+    }
+
+    fn infer<'e>(
+        &mut self,
+        expr: &'e ast::Expression,
+        children: &mut Vec<TypedExpression<'e>>,
+    ) -> DataType {
+        match expr {
+            ast::Expression::Literal(literal) => literal_type(literal),
+            // the type of a parameter depends on the value given to it
+            ast::Expression::Parameter(_) => DataType::Generic,
+            ast::Expression::Error(_) => DataType::Generic,
+            ast::Expression::QualifiedIdentifier(identifier) => match &self.column_types {
+                Some(resolver) => resolver(&identifier.identifiers).unwrap_or(DataType::Generic),
+                None => DataType::Generic,
+            },
+            ast::Expression::MakeTuple(tuple) => {
+                for expr in &tuple.exprs {
+                    self.child(expr, children);
+                }
+                DataType::Generic
+            }
+            ast::Expression::Select(select) => {
+                self.visit_select_statement(select);
+                DataType::Generic
+            }
+            ast::Expression::Unary(unary) => {
+                let operand = self.child(&unary.expr, children);
+
+                match unary.op {
+                    ast::UnaryOperator::Negate => {
+                        self.expect_type(operand, DataType::Numeric, "unary -");
+                        DataType::Numeric
+                    }
+                    ast::UnaryOperator::Not => {
+                        self.expect_type(operand, DataType::Logical, "NOT");
+                        DataType::Logical
+                    }
+                    ast::UnaryOperator::IsNull => DataType::Logical,
+                }
+            }
+            ast::Expression::Binary(binary) => {
+                let left = self.child(&binary.left, children);
+                let right = self.child(&binary.right, children);
+
+                match binary.op {
+                    ast::BinaryOperator::Concat => DataType::Varchar,
+                    ast::BinaryOperator::And | ast::BinaryOperator::Or => {
+                        let op = if binary.op == ast::BinaryOperator::And {
+                            "AND"
+                        } else {
+                            "OR"
+                        };
+                        self.expect_type(left, DataType::Logical, op);
+                        self.expect_type(right, DataType::Logical, op);
+                        DataType::Logical
+                    }
+                    ref op => self.arithmetic(op, left, right),
+                }
+            }
+            ast::Expression::Comparison(comparison) => {
+                let left = self.child(&comparison.left, children);
+                let right = self.child(&comparison.right, children);
+
+                match comparison.op {
+                    ast::ComparisonOperator::Equal => self.compare("=", left, right, false),
+                    ast::ComparisonOperator::NotEqual => self.compare("!=", left, right, false),
+                    ast::ComparisonOperator::LessThan => self.compare("<", left, right, true),
+                    ast::ComparisonOperator::LessEqual => self.compare("<=", left, right, true),
+                    ast::ComparisonOperator::GreaterThan => self.compare(">", left, right, true),
+                    ast::ComparisonOperator::GreaterEqual => self.compare(">=", left, right, true),
+                    ast::ComparisonOperator::Like => {
+                        self.expect_type(left, DataType::Varchar, "LIKE");
+                        self.expect_type(right, DataType::Varchar, "LIKE");
+                    }
+                }
+
+                DataType::Logical
+            }
+            ast::Expression::In(in_expr) => {
+                let data_type = self.child(&in_expr.expr, children);
+
+                match &in_expr.set {
+                    ast::SetSpecification::List(exprs) => {
+                        for expr in exprs {
+                            let element = self.child(expr, children);
+                            self.compare("IN", data_type, element, false);
+                        }
+                    }
+                    ast::SetSpecification::Select(select) => self.visit_select_statement(select),
+                    ast::SetSpecification::Name(_) => {}
+                }
+
+                DataType::Logical
+            }
+            ast::Expression::Between(between) => {
+                let data_type = self.child(&between.expr, children);
+                let lower = self.child(&between.lower, children);
+                let upper = self.child(&between.upper, children);
+
+                self.compare("BETWEEN", data_type, lower, true);
+                self.compare("BETWEEN", data_type, upper, true);
+
+                DataType::Logical
+            }
+            ast::Expression::Case(case) => {
+                let operand = case.expr.as_ref().map(|expr| self.child(expr, children));
+                let mut results = Vec::new();
+
+                for when in &case.when_part {
+                    let guard = self.child(&when.guard, children);
+
+                    match operand {
+                        Some(operand) => self.compare("CASE", operand, guard, false),
+                        None => self.expect_type(guard, DataType::Logical, "WHEN"),
+                    }
+
+                    results.push(self.child(&when.body, children));
+                }
+
+                if let Some(else_part) = &case.else_part {
+                    results.push(self.child(else_part, children));
+                }
+
+                self.unify(&results, "CASE")
+            }
+            ast::Expression::Coalesce(coalesce) => {
+                let types = coalesce
+                    .exprs
+                    .iter()
+                    .map(|expr| self.child(expr, children))
+                    .collect::<Vec<_>>();
+
+                self.unify(&types, "COALESCE")
+            }
+            ast::Expression::Replace(replace) => {
+                self.child(&replace.string, children);
+                self.child(&replace.search_string, children);
+
+                if let Some(expr) = &replace.replace_string {
+                    self.child(expr, children);
+                }
+
+                DataType::Varchar
+            }
+            ast::Expression::Substring(substring) => {
+                self.child(&substring.string, children);
+
+                let position = self.child(&substring.position, children);
+                self.expect_type(position, DataType::Numeric, "SUBSTRING position");
+
+                if let Some(expr) = &substring.length {
+                    let length = self.child(expr, children);
+                    self.expect_type(length, DataType::Numeric, "SUBSTRING length");
+                }
+
+                DataType::Varchar
+            }
+            ast::Expression::ToDate(to_date) => {
+                self.child(&to_date.string, children);
+
+                if let Some(expr) = &to_date.format {
+                    let format = self.child(expr, children);
+                    self.expect_type(format, DataType::Varchar, "TO_DATE format");
+                }
+
+                DataType::Date
+            }
+            ast::Expression::Power(power) => {
+                let base = self.child(&power.base, children);
+                let exponent = self.child(&power.exponent, children);
+
+                self.expect_type(base, DataType::Numeric, "POWER");
+                self.expect_type(exponent, DataType::Numeric, "POWER");
+
+                DataType::Numeric
+            }
+            ast::Expression::Concat(concat) => {
+                for expr in &concat.exprs {
+                    self.child(expr, children);
+                }
+
+                DataType::Varchar
+            }
+            ast::Expression::Sum(sum) => {
+                let data_type = self.child(&sum.expr, children);
+                self.expect_type(data_type, DataType::Numeric, "SUM");
+
+                DataType::Numeric
+            }
+            ast::Expression::Max(max) => {
+                let data_type = self.child(&max.expr, children);
+                self.compare("MAX", data_type, data_type, true);
+
+                data_type
+            }
+            ast::Expression::Min(min) => {
+                let data_type = self.child(&min.expr, children);
+                self.compare("MIN", data_type, data_type, true);
+
+                data_type
+            }
+            ast::Expression::Cast(cast) => {
+                self.child(&cast.expr, children);
+
+                DataType::from(&cast.data_type)
+            }
+            ast::Expression::Right(right) => {
+                self.child(&right.string, children);
+
+                let length = self.child(&right.length, children);
+                self.expect_type(length, DataType::Numeric, "RIGHT length");
+
+                DataType::Varchar
+            }
+            ast::Expression::Count(count) => {
+                if let ast::ResultColumns::List(columns) = &count.columns {
+                    for column in columns {
+                        if let ast::ResultColumn::Expr(column) = column {
+                            self.child(&column.expr, children);
+                        }
+                    }
+                }
+
+                DataType::Numeric
+            }
+            ast::Expression::Unknown(unknown) => {
+                let types = unknown
+                    .exprs
+                    .iter()
+                    .map(|expr| self.child(expr, children))
+                    .collect::<Vec<_>>();
+
+                match lookup_function(&unknown.name) {
+                    Some(ReturnType::Fixed(data_type)) => data_type,
+                    Some(ReturnType::FirstArgument) => {
+                        types.first().cloned().unwrap_or(DataType::Generic)
+                    }
+                    Some(ReturnType::Common) => {
+                        let name = unknown.name.last().unwrap().to_string().to_uppercase();
+                        self.unify(&types, &name)
+                    }
+                    None => DataType::Generic,
+                }
+            }
+        }
+    }
+}
+
+impl<'ast, 'r> Visitor<'ast> for TypeChecker<'r> {
+    fn visit_expression(&mut self, expr: &'ast ast::Expression) {
+        // annotating the expression already descends into its sub-expressions
+        self.annotate(expr);
+    }
+
+    fn visit_query_set_expression(&mut self, node: &'ast ast::QuerySetExpression) {
+        self.visit_result_columns(&node.columns);
+
+        for table in &node.from {
+            self.visit_table_expression(table);
+        }
+
+        if let Some(expr) = &node.where_expr {
+            self.expect_logical(expr, "WHERE condition");
+        }
+
+        if let Some(group_by) = &node.group_by {
+            for expr in &group_by.groupings {
+                self.visit_expression(expr);
+            }
+
+            if let Some(expr) = &group_by.having {
+                self.expect_logical(expr, "HAVING condition");
+            }
+        }
+    }
+
+    fn visit_join_constraint(&mut self, node: &'ast ast::JoinConstraint) {
+        if let ast::JoinConstraint::Expr(expr) = node {
+            self.expect_logical(expr, "JOIN condition");
+        }
+    }
+
+    fn visit_delete_statement(&mut self, node: &'ast ast::DeleteStatement) {
+        if let Some(expr) = &node.where_expr {
+            self.expect_logical(expr, "WHERE condition");
+        }
+    }
+
+    fn visit_update_statement(&mut self, node: &'ast ast::UpdateStatement) {
+        for assignment in &node.assignments {
+            visit::walk_assignment(self, assignment);
+        }
+
+        if let Some(expr) = &node.where_expr {
+            self.expect_logical(expr, "WHERE condition");
+        }
+    }
+}
+
+/// The type of a literal value
+pub fn literal_type(literal: &ast::Literal) -> DataType {
+    match literal {
+        ast::Literal::String(_) => DataType::Varchar,
+        ast::Literal::Numeric(_) => DataType::Numeric,
+        ast::Literal::Boolean(_) => DataType::Logical,
+        ast::Literal::Null | ast::Literal::Placeholder => DataType::Generic,
+        ast::Literal::CurrentTime | ast::Literal::Time(_) => DataType::Time,
+        ast::Literal::CurrentDate | ast::Literal::Date(_) => DataType::Date,
+        ast::Literal::CurrentTimestamp | ast::Literal::Timestamp(_) => DataType::Timestamp,
+    }
+}
+
+/// Infer the type of a standalone expression
+pub fn infer(expr: &ast::Expression) -> Result<DataType, Vec<TypeError>> {
+    let mut checker = TypeChecker::new();
+    let data_type = checker.annotate(expr).data_type;
+
+    match checker.errors.is_empty() {
+        true => Ok(data_type),
+        false => Err(checker.errors),
+    }
+}
+
+/// Type check a statement, returning all the errors found
+pub fn check(statement: &ast::SqlStatement) -> Vec<TypeError> {
+    let mut checker = TypeChecker::new();
+    checker.check_statement(statement);
+    checker.into_errors()
+}
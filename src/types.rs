@@ -23,8 +23,12 @@
 
 use std::fmt;
 
+use pyo3::{IntoPy, PyObject, Python};
+
+use super::ast;
+
 /// The types supported by this engine
-#[derive(Serialize, Deserialize, PartialEq, Debug, Copy, Clone)]
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Debug, Copy, Clone)]
 pub enum DataType {
     /// Generic describes values without specific type constraints; will be parsed and converted on demand
     Generic,
@@ -66,6 +70,12 @@ impl fmt::Display for DataType {
     }
 }
 
+impl IntoPy<PyObject> for DataType {
+    fn into_py(self, py: Python) -> PyObject {
+        IntoPy::<PyObject>::into_py(self.to_string(), py)
+    }
+}
+
 impl DataType {
     /// Can values of this type be ordered?
     pub fn is_ordered(self) -> bool {
@@ -79,3 +89,15 @@ impl DataType {
         }
     }
 }
+
+impl<'a> From<&'a ast::DataType> for DataType {
+    fn from(data_type: &'a ast::DataType) -> DataType {
+        match data_type {
+            ast::DataType::Boolean => DataType::Logical,
+            ast::DataType::Char(_) | ast::DataType::Varchar(_) => DataType::Varchar,
+            ast::DataType::Date => DataType::Date,
+            ast::DataType::Decimal { .. } | ast::DataType::DoublePrecision => DataType::Numeric,
+            ast::DataType::Timestamp | ast::DataType::LocalTimestamp => DataType::Timestamp,
+        }
+    }
+}
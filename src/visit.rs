@@ -0,0 +1,824 @@
+// MIT License
+//
+// Copyright (c) 2019 Daniele Esposti
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use super::ast::*;
+
+/// Read-only traversal of the AST.
+///
+/// Every `visit_*` method defaults to the matching `walk_*` function, which visits the
+/// node's children; an overriding method should call it too to keep descending the tree.
+pub trait Visitor<'ast> {
+    fn visit_sql_statement(&mut self, node: &'ast SqlStatement) {
+        walk_sql_statement(self, node)
+    }
+
+    fn visit_statement(&mut self, node: &'ast Statement) {
+        walk_statement(self, node)
+    }
+
+    fn visit_insert_statement(&mut self, node: &'ast InsertStatement) {
+        walk_insert_statement(self, node)
+    }
+
+    fn visit_delete_statement(&mut self, node: &'ast DeleteStatement) {
+        walk_delete_statement(self, node)
+    }
+
+    fn visit_update_statement(&mut self, node: &'ast UpdateStatement) {
+        walk_update_statement(self, node)
+    }
+
+    fn visit_assignment(&mut self, node: &'ast Assignment) {
+        walk_assignment(self, node)
+    }
+
+    fn visit_select_statement(&mut self, node: &'ast SelectStatement) {
+        walk_select_statement(self, node)
+    }
+
+    fn visit_common_table_expression(&mut self, node: &'ast CommonTableExpression) {
+        walk_common_table_expression(self, node)
+    }
+
+    fn visit_set_expression(&mut self, node: &'ast SetExpression) {
+        walk_set_expression(self, node)
+    }
+
+    fn visit_query_set_expression(&mut self, node: &'ast QuerySetExpression) {
+        walk_query_set_expression(self, node)
+    }
+
+    fn visit_table_expression(&mut self, node: &'ast TableExpression) {
+        walk_table_expression(self, node)
+    }
+
+    fn visit_named_table_expression(&mut self, _node: &'ast NamedTableExpression) {}
+
+    fn visit_select_table_expression(&mut self, node: &'ast SelectTableExpression) {
+        walk_select_table_expression(self, node)
+    }
+
+    fn visit_join_table_expression(&mut self, node: &'ast JoinTableExpression) {
+        walk_join_table_expression(self, node)
+    }
+
+    fn visit_join_constraint(&mut self, node: &'ast JoinConstraint) {
+        walk_join_constraint(self, node)
+    }
+
+    fn visit_result_columns(&mut self, node: &'ast ResultColumns) {
+        walk_result_columns(self, node)
+    }
+
+    fn visit_result_column(&mut self, node: &'ast ResultColumn) {
+        walk_result_column(self, node)
+    }
+
+    fn visit_group_by(&mut self, node: &'ast GroupBy) {
+        walk_group_by(self, node)
+    }
+
+    fn visit_ordering(&mut self, node: &'ast Ordering) {
+        walk_ordering(self, node)
+    }
+
+    fn visit_limit(&mut self, node: &'ast Limit) {
+        walk_limit(self, node)
+    }
+
+    fn visit_expression(&mut self, node: &'ast Expression) {
+        walk_expression(self, node)
+    }
+
+    fn visit_literal(&mut self, _node: &'ast Literal) {}
+
+    fn visit_parameter(&mut self, _node: &'ast Parameter) {}
+
+    fn visit_error_node(&mut self, _node: &'ast ErrorNode) {}
+
+    fn visit_qualified_identifier(&mut self, _node: &'ast QualifiedIdentifierExpression) {}
+
+    fn visit_set_specification(&mut self, node: &'ast SetSpecification) {
+        walk_set_specification(self, node)
+    }
+
+    fn visit_when_clause(&mut self, node: &'ast WhenClause) {
+        walk_when_clause(self, node)
+    }
+}
+
+pub fn walk_sql_statement<'ast, V: Visitor<'ast> + ?Sized>(v: &mut V, node: &'ast SqlStatement) {
+    match node {
+        SqlStatement::Statement(s) | SqlStatement::ExplainQueryPlan(s) => v.visit_statement(s),
+        SqlStatement::Attach(_) | SqlStatement::Describe(_) => {}
+        SqlStatement::Error(error) => v.visit_error_node(error),
+    }
+}
+
+pub fn walk_statement<'ast, V: Visitor<'ast> + ?Sized>(v: &mut V, node: &'ast Statement) {
+    match node {
+        Statement::Select(s) => v.visit_select_statement(s),
+        Statement::Insert(s) => v.visit_insert_statement(s),
+        Statement::Delete(s) => v.visit_delete_statement(s),
+        Statement::Update(s) => v.visit_update_statement(s),
+    }
+}
+
+pub fn walk_insert_statement<'ast, V: Visitor<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast InsertStatement,
+) {
+    v.visit_set_expression(&node.source);
+}
+
+pub fn walk_delete_statement<'ast, V: Visitor<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast DeleteStatement,
+) {
+    if let Some(expr) = &node.where_expr {
+        v.visit_expression(expr);
+    }
+}
+
+pub fn walk_update_statement<'ast, V: Visitor<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast UpdateStatement,
+) {
+    for assignment in &node.assignments {
+        v.visit_assignment(assignment);
+    }
+
+    if let Some(expr) = &node.where_expr {
+        v.visit_expression(expr);
+    }
+}
+
+pub fn walk_assignment<'ast, V: Visitor<'ast> + ?Sized>(v: &mut V, node: &'ast Assignment) {
+    v.visit_expression(&node.expr);
+}
+
+pub fn walk_select_statement<'ast, V: Visitor<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast SelectStatement,
+) {
+    for cte in &node.common {
+        v.visit_common_table_expression(cte);
+    }
+
+    v.visit_set_expression(&node.expr);
+
+    for ordering in &node.order_by {
+        v.visit_ordering(ordering);
+    }
+
+    if let Some(limit) = &node.limit {
+        v.visit_limit(limit);
+    }
+}
+
+pub fn walk_common_table_expression<'ast, V: Visitor<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast CommonTableExpression,
+) {
+    v.visit_select_statement(&node.query);
+}
+
+pub fn walk_set_expression<'ast, V: Visitor<'ast> + ?Sized>(v: &mut V, node: &'ast SetExpression) {
+    match node {
+        SetExpression::Values(values) => {
+            for row in &values.values {
+                for expr in row {
+                    v.visit_expression(expr);
+                }
+            }
+        }
+        SetExpression::Query(query) => v.visit_query_set_expression(query),
+        SetExpression::Op(op) => {
+            v.visit_set_expression(&op.left);
+            v.visit_set_expression(&op.right);
+        }
+    }
+}
+
+pub fn walk_query_set_expression<'ast, V: Visitor<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast QuerySetExpression,
+) {
+    v.visit_result_columns(&node.columns);
+
+    for table in &node.from {
+        v.visit_table_expression(table);
+    }
+
+    if let Some(expr) = &node.where_expr {
+        v.visit_expression(expr);
+    }
+
+    if let Some(group_by) = &node.group_by {
+        v.visit_group_by(group_by);
+    }
+}
+
+pub fn walk_table_expression<'ast, V: Visitor<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast TableExpression,
+) {
+    match node {
+        TableExpression::Named(named) => v.visit_named_table_expression(named),
+        TableExpression::Select(select) => v.visit_select_table_expression(select),
+        TableExpression::Join(join) => v.visit_join_table_expression(join),
+        TableExpression::Error(error) => v.visit_error_node(error),
+    }
+}
+
+pub fn walk_select_table_expression<'ast, V: Visitor<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast SelectTableExpression,
+) {
+    v.visit_select_statement(&node.select);
+}
+
+pub fn walk_join_table_expression<'ast, V: Visitor<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast JoinTableExpression,
+) {
+    v.visit_table_expression(&node.left);
+    v.visit_table_expression(&node.right);
+    v.visit_join_constraint(&node.constraint);
+}
+
+pub fn walk_join_constraint<'ast, V: Visitor<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast JoinConstraint,
+) {
+    match node {
+        JoinConstraint::Expr(expr) => v.visit_expression(expr),
+        JoinConstraint::Columns(_) => {}
+    }
+}
+
+pub fn walk_result_columns<'ast, V: Visitor<'ast> + ?Sized>(v: &mut V, node: &'ast ResultColumns) {
+    match node {
+        ResultColumns::All => {}
+        ResultColumns::List(columns) => {
+            for column in columns {
+                v.visit_result_column(column);
+            }
+        }
+    }
+}
+
+pub fn walk_result_column<'ast, V: Visitor<'ast> + ?Sized>(v: &mut V, node: &'ast ResultColumn) {
+    match node {
+        ResultColumn::AllFrom(_) => {}
+        ResultColumn::Expr(column) => v.visit_expression(&column.expr),
+    }
+}
+
+pub fn walk_group_by<'ast, V: Visitor<'ast> + ?Sized>(v: &mut V, node: &'ast GroupBy) {
+    for expr in &node.groupings {
+        v.visit_expression(expr);
+    }
+
+    if let Some(having) = &node.having {
+        v.visit_expression(having);
+    }
+}
+
+pub fn walk_ordering<'ast, V: Visitor<'ast> + ?Sized>(v: &mut V, node: &'ast Ordering) {
+    v.visit_expression(&node.expr);
+}
+
+pub fn walk_limit<'ast, V: Visitor<'ast> + ?Sized>(v: &mut V, node: &'ast Limit) {
+    v.visit_expression(&node.number_rows);
+
+    if let Some(offset) = &node.offset_value {
+        v.visit_expression(offset);
+    }
+}
+
+pub fn walk_expression<'ast, V: Visitor<'ast> + ?Sized>(v: &mut V, node: &'ast Expression) {
+    match node {
+        Expression::Literal(literal) => v.visit_literal(literal),
+        Expression::QualifiedIdentifier(identifier) => v.visit_qualified_identifier(identifier),
+        Expression::MakeTuple(tuple) => {
+            for expr in &tuple.exprs {
+                v.visit_expression(expr);
+            }
+        }
+        Expression::Select(select) => v.visit_select_statement(select),
+        Expression::Unary(unary) => v.visit_expression(&unary.expr),
+        Expression::Binary(binary) => {
+            v.visit_expression(&binary.left);
+            v.visit_expression(&binary.right);
+        }
+        Expression::Comparison(comparison) => {
+            v.visit_expression(&comparison.left);
+            v.visit_expression(&comparison.right);
+        }
+        Expression::In(in_expr) => {
+            v.visit_expression(&in_expr.expr);
+            v.visit_set_specification(&in_expr.set);
+        }
+        Expression::Between(between) => {
+            v.visit_expression(&between.expr);
+            v.visit_expression(&between.lower);
+            v.visit_expression(&between.upper);
+        }
+        Expression::Case(case) => {
+            if let Some(expr) = &case.expr {
+                v.visit_expression(expr);
+            }
+
+            for when in &case.when_part {
+                v.visit_when_clause(when);
+            }
+
+            if let Some(expr) = &case.else_part {
+                v.visit_expression(expr);
+            }
+        }
+        Expression::Coalesce(coalesce) => {
+            for expr in &coalesce.exprs {
+                v.visit_expression(expr);
+            }
+        }
+        Expression::Replace(replace) => {
+            v.visit_expression(&replace.string);
+            v.visit_expression(&replace.search_string);
+
+            if let Some(expr) = &replace.replace_string {
+                v.visit_expression(expr);
+            }
+        }
+        Expression::Substring(substring) => {
+            v.visit_expression(&substring.string);
+            v.visit_expression(&substring.position);
+
+            if let Some(expr) = &substring.length {
+                v.visit_expression(expr);
+            }
+        }
+        Expression::ToDate(to_date) => {
+            v.visit_expression(&to_date.string);
+
+            if let Some(expr) = &to_date.format {
+                v.visit_expression(expr);
+            }
+        }
+        Expression::Power(power) => {
+            v.visit_expression(&power.base);
+            v.visit_expression(&power.exponent);
+        }
+        Expression::Concat(concat) => {
+            for expr in &concat.exprs {
+                v.visit_expression(expr);
+            }
+        }
+        Expression::Sum(sum) => v.visit_expression(&sum.expr),
+        Expression::Max(max) => v.visit_expression(&max.expr),
+        Expression::Min(min) => v.visit_expression(&min.expr),
+        Expression::Cast(cast) => v.visit_expression(&cast.expr),
+        Expression::Right(right) => {
+            v.visit_expression(&right.string);
+            v.visit_expression(&right.length);
+        }
+        Expression::Count(count) => v.visit_result_columns(&count.columns),
+        Expression::Unknown(unknown) => {
+            for expr in &unknown.exprs {
+                v.visit_expression(expr);
+            }
+        }
+        Expression::Parameter(parameter) => v.visit_parameter(parameter),
+        Expression::Error(error) => v.visit_error_node(error),
+    }
+}
+
+pub fn walk_set_specification<'ast, V: Visitor<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast SetSpecification,
+) {
+    match node {
+        SetSpecification::Select(select) => v.visit_select_statement(select),
+        SetSpecification::List(exprs) => {
+            for expr in exprs {
+                v.visit_expression(expr);
+            }
+        }
+        SetSpecification::Name(_) => {}
+    }
+}
+
+pub fn walk_when_clause<'ast, V: Visitor<'ast> + ?Sized>(v: &mut V, node: &'ast WhenClause) {
+    v.visit_expression(&node.guard);
+    v.visit_expression(&node.body);
+}
+
+/// Mutable traversal of the AST, to rewrite it in place.
+///
+/// It works like `Visitor`: every `visit_*_mut` method defaults to the matching `walk_*_mut`
+/// function, which visits the node's children.
+pub trait VisitorMut {
+    fn visit_sql_statement_mut(&mut self, node: &mut SqlStatement) {
+        walk_sql_statement_mut(self, node)
+    }
+
+    fn visit_statement_mut(&mut self, node: &mut Statement) {
+        walk_statement_mut(self, node)
+    }
+
+    fn visit_insert_statement_mut(&mut self, node: &mut InsertStatement) {
+        walk_insert_statement_mut(self, node)
+    }
+
+    fn visit_delete_statement_mut(&mut self, node: &mut DeleteStatement) {
+        walk_delete_statement_mut(self, node)
+    }
+
+    fn visit_update_statement_mut(&mut self, node: &mut UpdateStatement) {
+        walk_update_statement_mut(self, node)
+    }
+
+    fn visit_assignment_mut(&mut self, node: &mut Assignment) {
+        walk_assignment_mut(self, node)
+    }
+
+    fn visit_select_statement_mut(&mut self, node: &mut SelectStatement) {
+        walk_select_statement_mut(self, node)
+    }
+
+    fn visit_common_table_expression_mut(&mut self, node: &mut CommonTableExpression) {
+        walk_common_table_expression_mut(self, node)
+    }
+
+    fn visit_set_expression_mut(&mut self, node: &mut SetExpression) {
+        walk_set_expression_mut(self, node)
+    }
+
+    fn visit_query_set_expression_mut(&mut self, node: &mut QuerySetExpression) {
+        walk_query_set_expression_mut(self, node)
+    }
+
+    fn visit_table_expression_mut(&mut self, node: &mut TableExpression) {
+        walk_table_expression_mut(self, node)
+    }
+
+    fn visit_named_table_expression_mut(&mut self, _node: &mut NamedTableExpression) {}
+
+    fn visit_select_table_expression_mut(&mut self, node: &mut SelectTableExpression) {
+        walk_select_table_expression_mut(self, node)
+    }
+
+    fn visit_join_table_expression_mut(&mut self, node: &mut JoinTableExpression) {
+        walk_join_table_expression_mut(self, node)
+    }
+
+    fn visit_join_constraint_mut(&mut self, node: &mut JoinConstraint) {
+        walk_join_constraint_mut(self, node)
+    }
+
+    fn visit_result_columns_mut(&mut self, node: &mut ResultColumns) {
+        walk_result_columns_mut(self, node)
+    }
+
+    fn visit_result_column_mut(&mut self, node: &mut ResultColumn) {
+        walk_result_column_mut(self, node)
+    }
+
+    fn visit_group_by_mut(&mut self, node: &mut GroupBy) {
+        walk_group_by_mut(self, node)
+    }
+
+    fn visit_ordering_mut(&mut self, node: &mut Ordering) {
+        walk_ordering_mut(self, node)
+    }
+
+    fn visit_limit_mut(&mut self, node: &mut Limit) {
+        walk_limit_mut(self, node)
+    }
+
+    fn visit_expression_mut(&mut self, node: &mut Expression) {
+        walk_expression_mut(self, node)
+    }
+
+    fn visit_literal_mut(&mut self, _node: &mut Literal) {}
+
+    fn visit_parameter_mut(&mut self, _node: &mut Parameter) {}
+
+    fn visit_error_node_mut(&mut self, _node: &mut ErrorNode) {}
+
+    fn visit_qualified_identifier_mut(&mut self, _node: &mut QualifiedIdentifierExpression) {}
+
+    fn visit_set_specification_mut(&mut self, node: &mut SetSpecification) {
+        walk_set_specification_mut(self, node)
+    }
+
+    fn visit_when_clause_mut(&mut self, node: &mut WhenClause) {
+        walk_when_clause_mut(self, node)
+    }
+}
+
+pub fn walk_sql_statement_mut<V: VisitorMut + ?Sized>(v: &mut V, node: &mut SqlStatement) {
+    match node {
+        SqlStatement::Statement(s) | SqlStatement::ExplainQueryPlan(s) => v.visit_statement_mut(s),
+        SqlStatement::Attach(_) | SqlStatement::Describe(_) => {}
+        SqlStatement::Error(error) => v.visit_error_node_mut(error),
+    }
+}
+
+pub fn walk_statement_mut<V: VisitorMut + ?Sized>(v: &mut V, node: &mut Statement) {
+    match node {
+        Statement::Select(s) => v.visit_select_statement_mut(s),
+        Statement::Insert(s) => v.visit_insert_statement_mut(s),
+        Statement::Delete(s) => v.visit_delete_statement_mut(s),
+        Statement::Update(s) => v.visit_update_statement_mut(s),
+    }
+}
+
+pub fn walk_insert_statement_mut<V: VisitorMut + ?Sized>(v: &mut V, node: &mut InsertStatement) {
+    v.visit_set_expression_mut(&mut node.source);
+}
+
+pub fn walk_delete_statement_mut<V: VisitorMut + ?Sized>(v: &mut V, node: &mut DeleteStatement) {
+    if let Some(expr) = &mut node.where_expr {
+        v.visit_expression_mut(expr);
+    }
+}
+
+pub fn walk_update_statement_mut<V: VisitorMut + ?Sized>(v: &mut V, node: &mut UpdateStatement) {
+    for assignment in &mut node.assignments {
+        v.visit_assignment_mut(assignment);
+    }
+
+    if let Some(expr) = &mut node.where_expr {
+        v.visit_expression_mut(expr);
+    }
+}
+
+pub fn walk_assignment_mut<V: VisitorMut + ?Sized>(v: &mut V, node: &mut Assignment) {
+    v.visit_expression_mut(&mut node.expr);
+}
+
+pub fn walk_select_statement_mut<V: VisitorMut + ?Sized>(v: &mut V, node: &mut SelectStatement) {
+    for cte in &mut node.common {
+        v.visit_common_table_expression_mut(cte);
+    }
+
+    v.visit_set_expression_mut(&mut node.expr);
+
+    for ordering in &mut node.order_by {
+        v.visit_ordering_mut(ordering);
+    }
+
+    if let Some(limit) = &mut node.limit {
+        v.visit_limit_mut(limit);
+    }
+}
+
+pub fn walk_common_table_expression_mut<V: VisitorMut + ?Sized>(
+    v: &mut V,
+    node: &mut CommonTableExpression,
+) {
+    v.visit_select_statement_mut(&mut node.query);
+}
+
+pub fn walk_set_expression_mut<V: VisitorMut + ?Sized>(v: &mut V, node: &mut SetExpression) {
+    match node {
+        SetExpression::Values(values) => {
+            for row in &mut values.values {
+                for expr in row {
+                    v.visit_expression_mut(expr);
+                }
+            }
+        }
+        SetExpression::Query(query) => v.visit_query_set_expression_mut(query),
+        SetExpression::Op(op) => {
+            v.visit_set_expression_mut(&mut op.left);
+            v.visit_set_expression_mut(&mut op.right);
+        }
+    }
+}
+
+pub fn walk_query_set_expression_mut<V: VisitorMut + ?Sized>(
+    v: &mut V,
+    node: &mut QuerySetExpression,
+) {
+    v.visit_result_columns_mut(&mut node.columns);
+
+    for table in &mut node.from {
+        v.visit_table_expression_mut(table);
+    }
+
+    if let Some(expr) = &mut node.where_expr {
+        v.visit_expression_mut(expr);
+    }
+
+    if let Some(group_by) = &mut node.group_by {
+        v.visit_group_by_mut(group_by);
+    }
+}
+
+pub fn walk_table_expression_mut<V: VisitorMut + ?Sized>(v: &mut V, node: &mut TableExpression) {
+    match node {
+        TableExpression::Named(named) => v.visit_named_table_expression_mut(named),
+        TableExpression::Select(select) => v.visit_select_table_expression_mut(select),
+        TableExpression::Join(join) => v.visit_join_table_expression_mut(join),
+        TableExpression::Error(error) => v.visit_error_node_mut(error),
+    }
+}
+
+pub fn walk_select_table_expression_mut<V: VisitorMut + ?Sized>(
+    v: &mut V,
+    node: &mut SelectTableExpression,
+) {
+    v.visit_select_statement_mut(&mut node.select);
+}
+
+pub fn walk_join_table_expression_mut<V: VisitorMut + ?Sized>(
+    v: &mut V,
+    node: &mut JoinTableExpression,
+) {
+    v.visit_table_expression_mut(&mut node.left);
+    v.visit_table_expression_mut(&mut node.right);
+    v.visit_join_constraint_mut(&mut node.constraint);
+}
+
+pub fn walk_join_constraint_mut<V: VisitorMut + ?Sized>(v: &mut V, node: &mut JoinConstraint) {
+    match node {
+        JoinConstraint::Expr(expr) => v.visit_expression_mut(expr),
+        JoinConstraint::Columns(_) => {}
+    }
+}
+
+pub fn walk_result_columns_mut<V: VisitorMut + ?Sized>(v: &mut V, node: &mut ResultColumns) {
+    match node {
+        ResultColumns::All => {}
+        ResultColumns::List(columns) => {
+            for column in columns {
+                v.visit_result_column_mut(column);
+            }
+        }
+    }
+}
+
+pub fn walk_result_column_mut<V: VisitorMut + ?Sized>(v: &mut V, node: &mut ResultColumn) {
+    match node {
+        ResultColumn::AllFrom(_) => {}
+        ResultColumn::Expr(column) => v.visit_expression_mut(&mut column.expr),
+    }
+}
+
+pub fn walk_group_by_mut<V: VisitorMut + ?Sized>(v: &mut V, node: &mut GroupBy) {
+    for expr in &mut node.groupings {
+        v.visit_expression_mut(expr);
+    }
+
+    if let Some(having) = &mut node.having {
+        v.visit_expression_mut(having);
+    }
+}
+
+pub fn walk_ordering_mut<V: VisitorMut + ?Sized>(v: &mut V, node: &mut Ordering) {
+    v.visit_expression_mut(&mut node.expr);
+}
+
+pub fn walk_limit_mut<V: VisitorMut + ?Sized>(v: &mut V, node: &mut Limit) {
+    v.visit_expression_mut(&mut node.number_rows);
+
+    if let Some(offset) = &mut node.offset_value {
+        v.visit_expression_mut(offset);
+    }
+}
+
+pub fn walk_expression_mut<V: VisitorMut + ?Sized>(v: &mut V, node: &mut Expression) {
+    match node {
+        Expression::Literal(literal) => v.visit_literal_mut(literal),
+        Expression::QualifiedIdentifier(identifier) => v.visit_qualified_identifier_mut(identifier),
+        Expression::MakeTuple(tuple) => {
+            for expr in &mut tuple.exprs {
+                v.visit_expression_mut(expr);
+            }
+        }
+        Expression::Select(select) => v.visit_select_statement_mut(select),
+        Expression::Unary(unary) => v.visit_expression_mut(&mut unary.expr),
+        Expression::Binary(binary) => {
+            v.visit_expression_mut(&mut binary.left);
+            v.visit_expression_mut(&mut binary.right);
+        }
+        Expression::Comparison(comparison) => {
+            v.visit_expression_mut(&mut comparison.left);
+            v.visit_expression_mut(&mut comparison.right);
+        }
+        Expression::In(in_expr) => {
+            v.visit_expression_mut(&mut in_expr.expr);
+            v.visit_set_specification_mut(&mut in_expr.set);
+        }
+        Expression::Between(between) => {
+            v.visit_expression_mut(&mut between.expr);
+            v.visit_expression_mut(&mut between.lower);
+            v.visit_expression_mut(&mut between.upper);
+        }
+        Expression::Case(case) => {
+            if let Some(expr) = &mut case.expr {
+                v.visit_expression_mut(expr);
+            }
+
+            for when in &mut case.when_part {
+                v.visit_when_clause_mut(when);
+            }
+
+            if let Some(expr) = &mut case.else_part {
+                v.visit_expression_mut(expr);
+            }
+        }
+        Expression::Coalesce(coalesce) => {
+            for expr in &mut coalesce.exprs {
+                v.visit_expression_mut(expr);
+            }
+        }
+        Expression::Replace(replace) => {
+            v.visit_expression_mut(&mut replace.string);
+            v.visit_expression_mut(&mut replace.search_string);
+
+            if let Some(expr) = &mut replace.replace_string {
+                v.visit_expression_mut(expr);
+            }
+        }
+        Expression::Substring(substring) => {
+            v.visit_expression_mut(&mut substring.string);
+            v.visit_expression_mut(&mut substring.position);
+
+            if let Some(expr) = &mut substring.length {
+                v.visit_expression_mut(expr);
+            }
+        }
+        Expression::ToDate(to_date) => {
+            v.visit_expression_mut(&mut to_date.string);
+
+            if let Some(expr) = &mut to_date.format {
+                v.visit_expression_mut(expr);
+            }
+        }
+        Expression::Power(power) => {
+            v.visit_expression_mut(&mut power.base);
+            v.visit_expression_mut(&mut power.exponent);
+        }
+        Expression::Concat(concat) => {
+            for expr in &mut concat.exprs {
+                v.visit_expression_mut(expr);
+            }
+        }
+        Expression::Sum(sum) => v.visit_expression_mut(&mut sum.expr),
+        Expression::Max(max) => v.visit_expression_mut(&mut max.expr),
+        Expression::Min(min) => v.visit_expression_mut(&mut min.expr),
+        Expression::Cast(cast) => v.visit_expression_mut(&mut cast.expr),
+        Expression::Right(right) => {
+            v.visit_expression_mut(&mut right.string);
+            v.visit_expression_mut(&mut right.length);
+        }
+        Expression::Count(count) => v.visit_result_columns_mut(&mut count.columns),
+        Expression::Unknown(unknown) => {
+            for expr in &mut unknown.exprs {
+                v.visit_expression_mut(expr);
+            }
+        }
+        Expression::Parameter(parameter) => v.visit_parameter_mut(parameter),
+        Expression::Error(error) => v.visit_error_node_mut(error),
+    }
+}
+
+pub fn walk_set_specification_mut<V: VisitorMut + ?Sized>(v: &mut V, node: &mut SetSpecification) {
+    match node {
+        SetSpecification::Select(select) => v.visit_select_statement_mut(select),
+        SetSpecification::List(exprs) => {
+            for expr in exprs {
+                v.visit_expression_mut(expr);
+            }
+        }
+        SetSpecification::Name(_) => {}
+    }
+}
+
+pub fn walk_when_clause_mut<V: VisitorMut + ?Sized>(v: &mut V, node: &mut WhenClause) {
+    v.visit_expression_mut(&mut node.guard);
+    v.visit_expression_mut(&mut node.body);
+}
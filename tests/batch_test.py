@@ -0,0 +1,25 @@
+import pytest
+
+from hephaestus import parse, parse_many
+
+
+@pytest.mark.parametrize("threads", [None, 1, 3, 16])
+def test_parse_many(threads):
+    sqls = ["SELECT a{0} FROM t{0}".format(i) for i in range(10)]
+
+    results = parse_many(sqls, threads=threads)
+
+    assert [r["statement"] for r in results] == [parse(sql) for sql in sqls]
+    assert [r["error"] for r in results] == [None] * len(sqls)
+
+
+def test_parse_many_errors():
+    results = parse_many(["SELECT 1", "SELECT", "SELECT 2"])
+
+    assert [r["statement"] is None for r in results] == [False, True, False]
+    assert results[1]["error"].startswith("Unrecognized EOF")
+
+
+def test_parse_many_invalid_threads():
+    with pytest.raises(ValueError):
+        parse_many(["SELECT 1"], threads=0)
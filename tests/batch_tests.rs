@@ -0,0 +1,71 @@
+// MIT License
+//
+// Copyright (c) 2019 Daniele Esposti
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use hephaestus::batch::parse_many;
+use hephaestus::parse;
+
+fn statements(count: usize) -> Vec<String> {
+    (0..count)
+        .map(|i| format!("SELECT a{} FROM t{}", i, i))
+        .collect()
+}
+
+macro_rules! test_parse_many_builder {
+    ($name:ident, $count:expr, $threads:expr) => {
+        #[test]
+        fn $name() {
+            let sql_strs = statements($count);
+            let expected: Vec<_> = sql_strs.iter().map(|s| parse(s).unwrap()).collect();
+
+            let results: Vec<_> = parse_many(sql_strs, $threads)
+                .into_iter()
+                .map(|result| result.unwrap())
+                .collect();
+
+            assert_eq!(results, expected);
+        }
+    };
+}
+
+test_parse_many_builder!(parse_many_empty, 0, 4);
+test_parse_many_builder!(parse_many_single_thread, 10, 1);
+test_parse_many_builder!(parse_many_uneven_chunks, 10, 3);
+test_parse_many_builder!(parse_many_more_threads_than_statements, 3, 8);
+
+#[test]
+fn parse_many_errors() {
+    let sql_strs = vec![
+        "SELECT a FROM t".to_string(),
+        "SELECT FROM".to_string(),
+        "SELECT b FROM u".to_string(),
+    ];
+
+    let results = parse_many(sql_strs, 2);
+
+    assert_eq!(results.len(), 3);
+    assert!(results[0].is_ok());
+    assert!(results[1]
+        .as_ref()
+        .unwrap_err()
+        .starts_with("Unrecognized token"));
+    assert!(results[2].is_ok());
+}
@@ -0,0 +1,326 @@
+// MIT License
+//
+// Copyright (c) 2019 Daniele Esposti
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use hephaestus::binder::{bind, BindError, BindErrorKind, BoundColumn};
+use hephaestus::catalog::{Catalog, MemoryCatalog};
+use hephaestus::parse;
+use hephaestus::symbols::{Name, Span};
+use hephaestus::types::DataType;
+
+const CATALOG: &str = r#"{
+    "default_schema": "s",
+    "schemas": [
+        {
+            "name": "s",
+            "tables": [
+                {
+                    "name": "orders",
+                    "columns": [
+                        {"name": "id", "data_type": "Numeric"},
+                        {"name": "customer_id", "data_type": "Numeric"},
+                        {"name": "placed_at", "data_type": "Timestamp"}
+                    ]
+                },
+                {
+                    "name": "customers",
+                    "columns": [
+                        {"name": "customer_id", "data_type": "Numeric"},
+                        {"name": "name", "data_type": "Varchar"}
+                    ]
+                }
+            ]
+        },
+        {
+            "name": "other",
+            "tables": [
+                {
+                    "name": "orders",
+                    "columns": [{"name": "code", "data_type": "Varchar"}]
+                }
+            ]
+        }
+    ]
+}"#;
+
+fn catalog() -> MemoryCatalog {
+    MemoryCatalog::from_json(CATALOG).unwrap()
+}
+
+fn names(names: &[&str]) -> Vec<Name> {
+    names.iter().map(|name| Name::from(*name)).collect()
+}
+
+fn bound(identifiers: &[&str], table: Option<&[&str]>, data_type: DataType) -> BoundColumn {
+    BoundColumn {
+        identifiers: names(identifiers),
+        table: table.map(names),
+        column: Name::from(*identifiers.last().unwrap()),
+        data_type,
+    }
+}
+
+fn error(kind: BindErrorKind, name: &[&str], start: usize, end: usize) -> BindError {
+    BindError {
+        kind,
+        name: names(name),
+        span: Some(Span::new(start, end)),
+    }
+}
+
+macro_rules! test_bind_errors_builder {
+    ($name:ident, $sql:expr, $errors:expr) => {
+        #[test]
+        fn $name() {
+            let binding = bind(&catalog(), &parse($sql).unwrap());
+            let expected: Vec<BindError> = $errors;
+
+            assert_eq!(binding.errors, expected);
+        }
+    };
+}
+
+#[test]
+fn bind_columns() {
+    let binding = bind(
+        &catalog(),
+        &parse(
+            "SELECT o.id, name FROM orders o JOIN s.customers c ON o.customer_id = c.customer_id",
+        )
+        .unwrap(),
+    );
+
+    assert_eq!(binding.errors, vec![]);
+    assert_eq!(
+        binding.columns,
+        vec![
+            bound(
+                &["o", "customer_id"],
+                Some(&["s", "orders"]),
+                DataType::Numeric
+            ),
+            bound(
+                &["c", "customer_id"],
+                Some(&["s", "customers"]),
+                DataType::Numeric
+            ),
+            bound(&["o", "id"], Some(&["s", "orders"]), DataType::Numeric),
+            bound(&["name"], Some(&["s", "customers"]), DataType::Varchar),
+        ]
+    );
+}
+
+#[test]
+fn bind_schema_qualified_table() {
+    let binding = bind(
+        &catalog(),
+        &parse("SELECT code, other.orders.code FROM other.orders").unwrap(),
+    );
+
+    assert_eq!(binding.errors, vec![]);
+    assert_eq!(
+        binding.columns,
+        vec![
+            bound(&["code"], Some(&["other", "orders"]), DataType::Varchar),
+            bound(
+                &["other", "orders", "code"],
+                Some(&["other", "orders"]),
+                DataType::Varchar
+            ),
+        ]
+    );
+}
+
+#[test]
+fn bind_derived_table_columns() {
+    let binding = bind(
+        &catalog(),
+        &parse(
+            "WITH c (key) AS (SELECT customer_id FROM customers) \
+             SELECT d.total, key FROM (SELECT id + 1 AS total FROM orders) d, c",
+        )
+        .unwrap(),
+    );
+
+    assert_eq!(binding.errors, vec![]);
+    assert_eq!(
+        binding.columns,
+        vec![
+            bound(
+                &["customer_id"],
+                Some(&["s", "customers"]),
+                DataType::Numeric
+            ),
+            bound(&["id"], Some(&["s", "orders"]), DataType::Numeric),
+            bound(&["d", "total"], None, DataType::Numeric),
+            bound(&["key"], None, DataType::Numeric),
+        ]
+    );
+}
+
+test_bind_errors_builder!(bind_no_errors, "SELECT * FROM orders WHERE id > 1", vec![]);
+
+test_bind_errors_builder!(
+    bind_unknown_table,
+    "SELECT a FROM missing",
+    vec![error(BindErrorKind::UnknownTable, &["missing"], 14, 21)]
+);
+
+test_bind_errors_builder!(
+    bind_unknown_column,
+    "SELECT id, amount FROM orders",
+    vec![error(BindErrorKind::UnknownColumn, &["amount"], 11, 17)]
+);
+
+test_bind_errors_builder!(
+    bind_unknown_qualified_column,
+    "SELECT o.name FROM orders o",
+    vec![error(BindErrorKind::UnknownColumn, &["o", "name"], 7, 13)]
+);
+
+test_bind_errors_builder!(
+    bind_unknown_qualifier,
+    "SELECT x.id FROM orders o",
+    vec![error(BindErrorKind::UnknownTable, &["x"], 7, 8)]
+);
+
+test_bind_errors_builder!(
+    bind_ambiguous_column,
+    "SELECT customer_id FROM orders, customers",
+    vec![error(
+        BindErrorKind::AmbiguousColumn,
+        &["customer_id"],
+        7,
+        18
+    )]
+);
+
+test_bind_errors_builder!(
+    bind_using_columns_are_coalesced,
+    "SELECT customer_id FROM orders JOIN customers USING (customer_id)",
+    vec![]
+);
+
+test_bind_errors_builder!(
+    bind_unknown_using_column,
+    "SELECT 1 FROM orders JOIN customers USING (id)",
+    vec![error(BindErrorKind::UnknownColumn, &["id"], 43, 45)]
+);
+
+test_bind_errors_builder!(
+    bind_natural_join_columns_are_coalesced,
+    "SELECT customer_id FROM orders NATURAL JOIN customers ON 1 = 1",
+    vec![]
+);
+
+test_bind_errors_builder!(
+    bind_unknown_all_from,
+    "SELECT c.* FROM orders o",
+    vec![error(BindErrorKind::UnknownTable, &["c"], 7, 8)]
+);
+
+test_bind_errors_builder!(
+    bind_correlated_subquery,
+    "SELECT name FROM customers c WHERE customer_id IN \
+     (SELECT o.customer_id FROM orders o WHERE o.id = c.customer_id AND c.nope = 1)",
+    vec![error(
+        BindErrorKind::UnknownColumn,
+        &["c", "nope"],
+        117,
+        123
+    )]
+);
+
+test_bind_errors_builder!(
+    bind_derived_table_cannot_see_siblings,
+    "SELECT 1 FROM orders o, (SELECT o.id FROM customers) d",
+    vec![error(BindErrorKind::UnknownTable, &["o"], 32, 33)]
+);
+
+test_bind_errors_builder!(
+    bind_order_by_alias,
+    "SELECT id AS key FROM orders ORDER BY key, placed_at",
+    vec![]
+);
+
+test_bind_errors_builder!(
+    bind_union_order_by,
+    "SELECT id FROM orders UNION SELECT customer_id FROM customers ORDER BY name",
+    vec![error(BindErrorKind::UnknownColumn, &["name"], 71, 75)]
+);
+
+test_bind_errors_builder!(
+    bind_unknown_table_columns_are_not_checked,
+    "SELECT m.a, b FROM missing m",
+    vec![error(BindErrorKind::UnknownTable, &["missing"], 19, 26)]
+);
+
+test_bind_errors_builder!(
+    bind_insert,
+    "INSERT INTO orders (id, amount) SELECT customer_id, name FROM customers",
+    vec![error(BindErrorKind::UnknownColumn, &["amount"], 24, 30)]
+);
+
+test_bind_errors_builder!(
+    bind_update,
+    "UPDATE orders SET placed_at = NULL WHERE name = 'x'",
+    vec![error(BindErrorKind::UnknownColumn, &["name"], 41, 45)]
+);
+
+test_bind_errors_builder!(
+    bind_spans_skip_comments,
+    "-- find the orders\nSELECT nope -- not a column\nFROM orders",
+    vec![error(BindErrorKind::UnknownColumn, &["nope"], 26, 30)]
+);
+
+#[test]
+fn catalog_lookup() {
+    let catalog = catalog();
+
+    assert_eq!(
+        catalog.table(&names(&["orders"])).map(|(name, _)| name),
+        Some(names(&["s", "orders"]))
+    );
+    assert_eq!(
+        catalog
+            .table(&names(&["OTHER", "Orders"]))
+            .map(|(name, _)| name),
+        Some(names(&["other", "orders"]))
+    );
+    assert!(catalog.table(&names(&["s", "missing"])).is_none());
+}
+
+#[test]
+fn catalog_without_default_schema() {
+    let mut catalog = catalog();
+    catalog.default_schema = None;
+
+    assert!(catalog.table(&names(&["orders"])).is_none());
+    assert_eq!(
+        catalog.table(&names(&["customers"])).map(|(name, _)| name),
+        Some(names(&["s", "customers"]))
+    );
+}
+
+#[test]
+fn catalog_invalid_json() {
+    assert!(MemoryCatalog::from_json("{\"schemas\": 1}").is_err());
+}
@@ -0,0 +1,33 @@
+from hephaestus import classify
+
+
+def test_classify_select():
+    assert classify("SELECT a, COUNT(*) FROM t JOIN u ON t.a = u.a GROUP BY a") == {
+        "kind": "read",
+        "read_only": True,
+        "side_effects": False,
+        "written_tables": [],
+        "read_tables": ["t", "u"],
+        "uses_aggregation": True,
+        "uses_subquery": False,
+        "is_explain": False,
+    }
+
+
+def test_classify_dml():
+    result = classify("UPDATE s.t SET a = 1 WHERE b IN (SELECT b FROM u)")
+
+    assert result["kind"] == "dml"
+    assert not result["read_only"]
+    assert result["side_effects"]
+    assert result["written_tables"] == ["s.t"]
+    assert result["read_tables"] == ["u"]
+    assert result["uses_subquery"]
+
+
+def test_classify_explain():
+    result = classify("EXPLAIN DELETE FROM t")
+
+    assert result["kind"] == "dml"
+    assert result["read_only"]
+    assert result["is_explain"]
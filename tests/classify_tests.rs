@@ -0,0 +1,197 @@
+// MIT License
+//
+// Copyright (c) 2019 Daniele Esposti
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use hephaestus::classify::{classify, Classification, StatementKind};
+use hephaestus::parse;
+use hephaestus::recovery::parse_lenient;
+
+macro_rules! test_classify_builder {
+    ($name:ident, $sql:expr, $expected:expr) => {
+        #[test]
+        fn $name() {
+            let result = classify(&parse($sql).unwrap());
+            let expected = $expected;
+
+            assert_eq!(result, expected);
+        }
+    };
+}
+
+fn tables(tables: &[&str]) -> Vec<String> {
+    tables.iter().map(|table| table.to_string()).collect()
+}
+
+fn read(read_tables: &[&str]) -> Classification {
+    Classification {
+        kind: StatementKind::Read,
+        read_only: true,
+        side_effects: false,
+        written_tables: Vec::new(),
+        read_tables: tables(read_tables),
+        uses_aggregation: false,
+        uses_subquery: false,
+        is_explain: false,
+    }
+}
+
+fn dml(written_table: &str, read_tables: &[&str]) -> Classification {
+    Classification {
+        kind: StatementKind::Dml,
+        read_only: false,
+        side_effects: true,
+        written_tables: tables(&[written_table]),
+        ..read(read_tables)
+    }
+}
+
+test_classify_builder!(
+    select,
+    "SELECT a FROM s.t JOIN u ON t.a = u.a",
+    read(&["s.t", "u"])
+);
+
+test_classify_builder!(
+    select_from_cte,
+    "WITH c AS (SELECT a FROM t) SELECT a FROM c",
+    read(&["t"])
+);
+
+test_classify_builder!(
+    table_named_like_nested_cte,
+    "SELECT a FROM c WHERE a IN (WITH c AS (SELECT a FROM t) SELECT a FROM c)",
+    Classification {
+        uses_subquery: true,
+        ..read(&["c", "t"])
+    }
+);
+
+test_classify_builder!(
+    cte_reading_table_of_its_name,
+    "WITH t AS (SELECT a FROM t) SELECT a FROM t",
+    read(&["t"])
+);
+
+test_classify_builder!(
+    recursive_cte,
+    "WITH RECURSIVE n AS (SELECT a FROM t UNION ALL SELECT a FROM n) SELECT a FROM n",
+    read(&["t"])
+);
+
+test_classify_builder!(
+    subquery_of_cte,
+    "WITH c AS (SELECT a FROM (SELECT a FROM t) AS s) SELECT a FROM c",
+    Classification {
+        uses_subquery: true,
+        ..read(&["t"])
+    }
+);
+
+test_classify_builder!(
+    aggregation,
+    "SELECT a, COUNT(*) FROM t GROUP BY a",
+    Classification {
+        uses_aggregation: true,
+        ..read(&["t"])
+    }
+);
+
+test_classify_builder!(
+    aggregate_function,
+    "SELECT avg(a) FROM t",
+    Classification {
+        uses_aggregation: true,
+        ..read(&["t"])
+    }
+);
+
+test_classify_builder!(
+    subquery,
+    "SELECT a FROM t WHERE a IN (SELECT MAX(a) FROM u)",
+    Classification {
+        uses_aggregation: true,
+        uses_subquery: true,
+        ..read(&["t", "u"])
+    }
+);
+
+test_classify_builder!(
+    insert,
+    "INSERT INTO s.t SELECT a FROM u",
+    dml("s.t", &["u"])
+);
+
+test_classify_builder!(update, "UPDATE t SET a = 1 WHERE b = 2", dml("t", &[]));
+
+test_classify_builder!(
+    update_reading_itself,
+    "UPDATE t SET a = (SELECT MAX(a) FROM T)",
+    Classification {
+        uses_aggregation: true,
+        uses_subquery: true,
+        ..dml("t", &["T"])
+    }
+);
+
+test_classify_builder!(
+    delete,
+    "DELETE FROM t WHERE a IN (SELECT a FROM u)",
+    Classification {
+        uses_subquery: true,
+        ..dml("t", &["u"])
+    }
+);
+
+test_classify_builder!(
+    explain,
+    "EXPLAIN QUERY PLAN DELETE FROM t",
+    Classification {
+        read_only: true,
+        side_effects: false,
+        is_explain: true,
+        ..dml("t", &[])
+    }
+);
+
+test_classify_builder!(describe, "DESCRIBE s.t", read(&["s.t"]));
+
+test_classify_builder!(
+    attach,
+    "ATTACH FILE 'data.csv' AS s.t",
+    Classification {
+        kind: StatementKind::Session,
+        ..dml("s.t", &[])
+    }
+);
+
+#[test]
+fn unknown() {
+    let statement = parse_lenient("SELEC a FROM t").statement.unwrap();
+
+    assert_eq!(
+        classify(&statement),
+        Classification {
+            kind: StatementKind::Unknown,
+            written_tables: Vec::new(),
+            ..dml("t", &[])
+        }
+    );
+}
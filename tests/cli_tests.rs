@@ -0,0 +1,286 @@
+// MIT License
+//
+// Copyright (c) 2019 Daniele Esposti
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+const CATALOG: &str = r#"{
+    "default_schema": "s",
+    "schemas": [
+        {
+            "name": "s",
+            "tables": [
+                {"name": "t", "columns": [{"name": "a", "data_type": "Numeric"}]}
+            ]
+        }
+    ]
+}"#;
+
+struct Output {
+    code: i32,
+    stdout: String,
+    stderr: String,
+}
+
+/// Run the command line tool with the given standard input
+fn run(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_hephaestus"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+
+    let output = child.wait_with_output().unwrap();
+
+    Output {
+        code: output.status.code().unwrap(),
+        stdout: String::from_utf8(output.stdout).unwrap(),
+        stderr: String::from_utf8(output.stderr).unwrap(),
+    }
+}
+
+/// Write a script in a temporary file, unique for each test
+fn script(name: &str, sql: &str) -> PathBuf {
+    let path = env::temp_dir().join(format!("hephaestus_cli_{}.sql", name));
+    fs::write(&path, sql).unwrap();
+
+    path
+}
+
+#[test]
+fn validate_valid_statements() {
+    let output = run(&["validate"], "SELECT 1;\nSELECT a FROM t;\n");
+
+    assert_eq!(output.code, 0);
+    assert_eq!(output.stderr, "");
+}
+
+#[test]
+fn validate_reports_the_location_of_errors() {
+    let output = run(&["validate"], "SELECT 1;\n  SELECT FROM;\nSELECT");
+
+    assert_eq!(output.code, 1);
+    assert_eq!(
+        output.stderr,
+        "<stdin>:2:10: unexpected token `FROM`\n<stdin>:3:7: unexpected end of statement\n"
+    );
+}
+
+#[test]
+fn parse_as_json() {
+    let output = run(&["parse"], "SELECT a FROM t");
+    let json: serde_json::Value = serde_json::from_str(&output.stdout).unwrap();
+
+    assert_eq!(output.code, 0);
+    assert_eq!(
+        json["Statement"]["Select"]["expr"]["Query"]["from"][0]["Named"]["name"],
+        serde_json::json!(["t"])
+    );
+}
+
+#[test]
+fn parse_as_debug() {
+    let output = run(&["parse", "--format", "debug"], "SELECT 1");
+
+    assert_eq!(output.code, 0);
+    assert!(output.stdout.starts_with("Statement(\n    Select(\n"));
+}
+
+#[test]
+fn complexity_as_text() {
+    let output = run(
+        &["complexity"],
+        "SELECT 1;\n  SELECT a FROM t JOIN u ON t.a = u.a",
+    );
+
+    assert_eq!(output.code, 0);
+    assert!(output
+        .stdout
+        .starts_with("<stdin>:1:1\n    subquery_depth: 0\n"));
+    assert!(output
+        .stdout
+        .contains("<stdin>:2:3\n    subquery_depth: 0\n    joins: inner 1, left 0,"));
+}
+
+#[test]
+fn complexity_as_json() {
+    let output = run(&["complexity", "--format", "json"], "SELECT a FROM t, u");
+    let json: serde_json::Value = serde_json::from_str(&output.stdout).unwrap();
+
+    assert_eq!(output.code, 0);
+    assert_eq!(json["tables"], serde_json::json!(2));
+    assert_eq!(json["joins"]["inner"], serde_json::json!(0));
+}
+
+#[test]
+fn tables_are_listed_once() {
+    let output = run(
+        &["tables"],
+        "SELECT a FROM s.t JOIN u ON t.a = u.a; DELETE FROM S.T",
+    );
+
+    assert_eq!(output.code, 0);
+    assert_eq!(output.stdout, "s.t\nu\n");
+}
+
+#[test]
+fn fmt_standard_input() {
+    let output = run(&["fmt"], "select a\nfrom t;select 1");
+
+    assert_eq!(output.code, 0);
+    assert_eq!(output.stdout, "SELECT a\nFROM t;\n\nSELECT 1\n");
+}
+
+#[test]
+fn fmt_check_and_rewrite_files() {
+    let path = script("fmt", "select a from t");
+    let path = path.to_str().unwrap();
+
+    let output = run(&["fmt", "--check", path], "");
+
+    assert_eq!(output.code, 1);
+    assert_eq!(output.stdout, format!("{}\n", path));
+
+    let output = run(&["fmt", path], "");
+
+    assert_eq!(output.code, 0);
+    assert_eq!(fs::read_to_string(path).unwrap(), "SELECT a\nFROM t\n");
+
+    let output = run(&["fmt", "--check", path], "");
+
+    assert_eq!(output.code, 0);
+    assert_eq!(output.stdout, "");
+}
+
+#[test]
+fn fmt_keeps_comments() {
+    let output = run(&["fmt"], "-- one\nselect 1 -- the answer\n");
+
+    assert_eq!(output.code, 0);
+    assert_eq!(output.stdout, "-- one\nSELECT 1 -- the answer\n");
+}
+
+#[test]
+fn fmt_with_config() {
+    let config = script("config", "keyword_case = \"lower\"\nindent_width = 2\n");
+    let config = config.to_str().unwrap();
+
+    let output = run(&["fmt", "--config", config], "SELECT a FROM t");
+
+    assert_eq!(output.code, 0);
+    assert_eq!(output.stdout, "select a\nfrom t\n");
+}
+
+#[test]
+fn fmt_with_invalid_config() {
+    let config = script("invalid_config", "keyword_case = \"title\"\n");
+    let config = config.to_str().unwrap();
+
+    let output = run(&["fmt", "--config", config], "SELECT 1");
+
+    assert_eq!(output.code, 2);
+    assert!(output.stderr.contains("Invalid format options"));
+}
+
+#[test]
+fn lint_type_errors() {
+    let output = run(&["lint"], "SELECT 1;\n  SELECT 1 + 'a' FROM t");
+
+    assert_eq!(output.code, 1);
+    assert_eq!(
+        output.stderr,
+        "<stdin>:2:3: cannot apply + to NUMERIC and VARCHAR\n"
+    );
+}
+
+#[test]
+fn lint_with_catalog() {
+    let catalog = script("catalog", CATALOG);
+    let catalog = catalog.to_str().unwrap();
+
+    let output = run(
+        &["lint", "--catalog", catalog],
+        "SELECT a, b FROM t;\nSELECT 1 FROM u",
+    );
+
+    assert_eq!(output.code, 1);
+    assert_eq!(
+        output.stderr,
+        "<stdin>:1:11: unknown column b\n<stdin>:2:15: unknown table u\n"
+    );
+}
+
+#[test]
+fn lint_rules() {
+    let output = run(
+        &["lint"],
+        "SELECT a FROM t;\nSELECT a FROM t WHERE b = NULL",
+    );
+
+    assert_eq!(output.code, 1);
+    assert_eq!(
+        output.stderr,
+        "<stdin>:2:23: error: comparing with = NULL is never true, use IS NULL [null-comparison]\n"
+    );
+
+    let output = run(
+        &["lint", "--disable", "null-comparison,select-star"],
+        "SELECT * FROM t WHERE b = NULL",
+    );
+
+    assert_eq!(output.code, 0);
+    assert_eq!(output.stderr, "");
+}
+
+#[test]
+fn lint_infos_are_not_problems() {
+    let output = run(&["lint"], "SELECT a FROM t UNION SELECT a FROM u");
+
+    assert_eq!(output.code, 0);
+    assert_eq!(
+        output.stderr,
+        "<stdin>:1:30: info: UNION removes the duplicate rows, use UNION ALL to keep them [union-distinct]\n"
+    );
+}
+
+#[test]
+fn invalid_arguments() {
+    assert_eq!(run(&[], "").code, 2);
+    assert_eq!(run(&["compile"], "").code, 2);
+    assert_eq!(run(&["parse", "--format", "yaml"], "").code, 2);
+    assert_eq!(run(&["complexity", "--format", "debug"], "").code, 2);
+    assert_eq!(run(&["fmt", "--catalog", "x"], "").code, 2);
+    assert_eq!(run(&["lint", "--disable", "x"], "").code, 2);
+    assert_eq!(run(&["validate", "missing.sql"], "").code, 2);
+}
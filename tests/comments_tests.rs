@@ -34,6 +34,7 @@ test_builder!(
     "#,
     SqlStatement::Statement(Statement::Select(SelectStatement {
         common: vec![],
+        recursive: false,
         expr: Box::new(SetExpression::Query(QuerySetExpression {
             mode: SelectMode::All,
             columns: ResultColumns::List(vec![ResultColumn::Expr(ExprResultColumn {
@@ -54,6 +55,7 @@ test_builder!(
     "select 1 -- comment",
     SqlStatement::Statement(Statement::Select(SelectStatement {
         common: vec![],
+        recursive: false,
         expr: Box::new(SetExpression::Query(QuerySetExpression {
             mode: SelectMode::All,
             columns: ResultColumns::List(vec![ResultColumn::Expr(ExprResultColumn {
@@ -74,6 +76,7 @@ test_builder!(
     "select '--'",
     SqlStatement::Statement(Statement::Select(SelectStatement {
         common: vec![],
+        recursive: false,
         expr: Box::new(SetExpression::Query(QuerySetExpression {
             mode: SelectMode::All,
             columns: ResultColumns::List(vec![ResultColumn::Expr(ExprResultColumn {
@@ -0,0 +1,18 @@
+from hephaestus import complexity
+
+
+def test_complexity():
+    assert complexity(
+        "WITH c AS (SELECT a FROM t) SELECT CASE WHEN a = 1 THEN 'x' ELSE 'y' END FROM c "
+        "LEFT JOIN u ON c.a = u.a JOIN c AS d ON c.a = d.a"
+    ) == {
+        "subquery_depth": 0,
+        "joins": {"inner": 1, "left": 1, "right": 0, "full": 0, "cross": 0, "natural": 0},
+        "ctes": 1,
+        "cte_reuses": 1,
+        "set_operation_branches": 0,
+        "case_branches": 2,
+        "tables": 2,
+        "functions": 0,
+        "nodes": 29,
+    }
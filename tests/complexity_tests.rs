@@ -0,0 +1,161 @@
+// MIT License
+//
+// Copyright (c) 2019 Daniele Esposti
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use hephaestus::complexity::{complexity, Complexity, JoinCounts};
+use hephaestus::parse;
+
+macro_rules! test_complexity_builder {
+    ($name:ident, $sql:expr, $expected:expr) => {
+        #[test]
+        fn $name() {
+            let result = complexity(&parse($sql).unwrap());
+            let expected = $expected;
+
+            assert_eq!(result, expected);
+        }
+    };
+}
+
+test_complexity_builder!(
+    simple_query,
+    "SELECT a, b FROM t",
+    Complexity {
+        tables: 1,
+        nodes: 8,
+        ..Complexity::default()
+    }
+);
+
+test_complexity_builder!(
+    nested_subqueries,
+    "SELECT a FROM (SELECT a FROM t WHERE b IN (SELECT b FROM u)) AS s \
+     WHERE c = (SELECT MAX(c) FROM v)",
+    Complexity {
+        subquery_depth: 2,
+        tables: 3,
+        functions: 1,
+        nodes: 28,
+        ..Complexity::default()
+    }
+);
+
+test_complexity_builder!(
+    joins,
+    "SELECT a FROM t JOIN u ON t.a = u.a LEFT JOIN v ON t.b = v.b \
+     NATURAL LEFT JOIN w ON 1 = 1 CROSS JOIN x ON 1 = 1 FULL JOIN t AS y USING (a)",
+    Complexity {
+        joins: JoinCounts {
+            inner: 1,
+            left: 2,
+            right: 0,
+            full: 1,
+            cross: 1,
+            natural: 1,
+        },
+        tables: 5,
+        nodes: 29,
+        ..Complexity::default()
+    }
+);
+
+test_complexity_builder!(
+    ctes,
+    "WITH c AS (SELECT a FROM t), d AS (SELECT a FROM c) \
+     SELECT a FROM c JOIN d ON c.a = d.a JOIN C AS e ON c.a = e.a",
+    Complexity {
+        joins: JoinCounts {
+            inner: 2,
+            ..JoinCounts::default()
+        },
+        ctes: 2,
+        cte_reuses: 2,
+        tables: 1,
+        nodes: 30,
+        ..Complexity::default()
+    }
+);
+
+test_complexity_builder!(
+    subquery_of_cte,
+    "WITH c AS (SELECT a FROM t WHERE a IN (SELECT a FROM u)) SELECT a FROM c",
+    Complexity {
+        subquery_depth: 1,
+        ctes: 1,
+        tables: 2,
+        nodes: 21,
+        ..Complexity::default()
+    }
+);
+
+test_complexity_builder!(
+    set_operations,
+    "SELECT a FROM t UNION SELECT a FROM u EXCEPT SELECT a FROM v INTERSECT SELECT a FROM t",
+    Complexity {
+        set_operation_branches: 4,
+        tables: 3,
+        nodes: 24,
+        ..Complexity::default()
+    }
+);
+
+test_complexity_builder!(
+    case_branches,
+    "SELECT CASE WHEN a = 1 THEN 'x' WHEN a = 2 THEN 'y' ELSE 'z' END, \
+     CASE a WHEN 1 THEN COALESCE(b, 0) END FROM t",
+    Complexity {
+        case_branches: 4,
+        tables: 1,
+        functions: 1,
+        nodes: 25,
+        ..Complexity::default()
+    }
+);
+
+test_complexity_builder!(
+    dml,
+    "UPDATE s.t SET a = (SELECT MAX(a) FROM S.T) WHERE b = 1",
+    Complexity {
+        subquery_depth: 1,
+        tables: 1,
+        functions: 1,
+        nodes: 12,
+        ..Complexity::default()
+    }
+);
+
+#[test]
+fn display() {
+    let result = complexity(&parse("SELECT a FROM t LEFT JOIN u ON t.a = u.a").unwrap());
+
+    assert_eq!(
+        result.to_string(),
+        "subquery_depth: 0\n\
+         joins: inner 0, left 1, right 0, full 0, cross 0, natural 0\n\
+         ctes: 0\n\
+         cte_reuses: 0\n\
+         set_operation_branches: 0\n\
+         case_branches: 0\n\
+         tables: 2\n\
+         functions: 0\n\
+         nodes: 11"
+    );
+}
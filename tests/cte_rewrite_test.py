@@ -0,0 +1,32 @@
+import pytest
+
+from hephaestus import extract_cte, inline_cte
+
+
+def test_inline_cte():
+    sql = "WITH c (x) AS (SELECT a FROM t) SELECT x FROM c JOIN c AS d USING (x)"
+
+    assert inline_cte(sql, "c") == (
+        "SELECT x FROM (SELECT a AS x FROM t) AS c JOIN (SELECT a AS x FROM t) AS d USING (x)"
+    )
+
+
+def test_inline_cte_missing():
+    with pytest.raises(ValueError, match="no common table expression named c"):
+        inline_cte("SELECT a FROM t", "c")
+
+
+def test_inline_cte_recursive():
+    sql = (
+        "WITH RECURSIVE n AS (SELECT 1 AS a UNION ALL SELECT a + 1 FROM n WHERE a < 3) "
+        "SELECT a FROM n"
+    )
+
+    with pytest.raises(ValueError, match="cannot inline the recursive common table expression n"):
+        inline_cte(sql, "n")
+
+
+def test_extract_cte():
+    assert extract_cte("SELECT a FROM (SELECT a FROM t) AS x, (SELECT a FROM t) AS y", "r") == (
+        "WITH r AS (SELECT a FROM t) SELECT a FROM r AS x, r AS y"
+    )
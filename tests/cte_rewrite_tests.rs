@@ -0,0 +1,187 @@
+// MIT License
+//
+// Copyright (c) 2019 Daniele Esposti
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use hephaestus::ast::{SqlStatement, Statement};
+use hephaestus::cte::{extract_cte, inline_cte, references_itself};
+use hephaestus::parse;
+
+macro_rules! test_inline_builder {
+    ($name:ident, $sql:expr, $cte:expr, $expected:expr) => {
+        #[test]
+        fn $name() {
+            let result = inline_cte(&parse($sql).unwrap(), $cte).map(|s| s.to_string());
+            let expected: Result<&str, &str> = $expected;
+
+            assert_eq!(result, expected.map(String::from).map_err(String::from));
+        }
+    };
+}
+
+macro_rules! test_extract_builder {
+    ($name:ident, $sql:expr, $cte:expr, $expected:expr) => {
+        #[test]
+        fn $name() {
+            let result = extract_cte(&parse($sql).unwrap(), $cte).map(|s| s.to_string());
+            let expected: Result<&str, &str> = $expected;
+
+            assert_eq!(result, expected.map(String::from).map_err(String::from));
+        }
+    };
+}
+
+test_inline_builder!(
+    inline_every_reference,
+    "WITH c AS (SELECT a, b FROM t) SELECT c.a FROM c JOIN c AS d ON c.a = d.b",
+    "c",
+    Ok("SELECT c.a FROM (SELECT a, b FROM t) AS c JOIN (SELECT a, b FROM t) AS d ON c.a = d.b")
+);
+
+test_inline_builder!(
+    inline_with_column_names,
+    "WITH c (x, y) AS (SELECT a, b AS z FROM t UNION SELECT a, b FROM u) SELECT x FROM c",
+    "c",
+    Ok("SELECT x FROM (SELECT a AS x, b AS y FROM t UNION SELECT a, b FROM u) AS c")
+);
+
+test_inline_builder!(
+    inline_into_later_ctes_and_subqueries,
+    "WITH a AS (SELECT 1 AS a), c AS (SELECT a FROM a), e AS (SELECT a FROM c) \
+     SELECT a FROM e WHERE a IN (SELECT a FROM C)",
+    "c",
+    Ok(
+        "WITH a AS (SELECT 1 AS a), e AS (SELECT a FROM (SELECT a FROM a) AS c) \
+        SELECT a FROM e WHERE a IN (SELECT a FROM (SELECT a FROM a) AS c)"
+    )
+);
+
+test_inline_builder!(
+    inline_shadowed,
+    "WITH c AS (SELECT a FROM t) \
+     SELECT a FROM (WITH c AS (SELECT 2 AS a) SELECT a FROM c) AS s, c",
+    "c",
+    Ok("SELECT a FROM (WITH c AS (SELECT 2 AS a) SELECT a FROM c) AS s, (SELECT a FROM t) AS c")
+);
+
+test_inline_builder!(
+    inline_captured,
+    "WITH c AS (SELECT a FROM a) \
+     SELECT a FROM (WITH a AS (SELECT 2 AS a) SELECT a FROM c) AS s",
+    "c",
+    Err("inlining c would bind a to another common table expression")
+);
+
+test_inline_builder!(
+    inline_star_with_column_names,
+    "WITH c (x) AS (SELECT * FROM t) SELECT x FROM c",
+    "c",
+    Err("cannot rename the columns of the common table expression c")
+);
+
+test_inline_builder!(
+    inline_missing,
+    "SELECT a FROM c",
+    "c",
+    Err("no common table expression named c")
+);
+
+test_inline_builder!(
+    inline_into_recursive_cte,
+    "WITH RECURSIVE c AS (SELECT 1 AS a), \
+     n AS (SELECT a FROM c UNION ALL SELECT a + 1 FROM n WHERE a < 3) SELECT a FROM n",
+    "c",
+    Ok("WITH RECURSIVE n AS (SELECT a FROM (SELECT 1 AS a) AS c \
+        UNION ALL SELECT a + 1 FROM n WHERE a < 3) SELECT a FROM n")
+);
+
+test_inline_builder!(
+    inline_recursive,
+    "WITH RECURSIVE n AS (SELECT 1 AS a UNION ALL SELECT a + 1 FROM n WHERE a < 3) \
+     SELECT a FROM n",
+    "n",
+    Err("cannot inline the recursive common table expression n")
+);
+
+test_extract_builder!(
+    extract_repeated_query,
+    "SELECT x.a FROM (SELECT a FROM t WHERE b = 1) AS x \
+     JOIN (SELECT a FROM t WHERE b = 1) AS y ON x.a = y.a",
+    "r",
+    Ok("WITH r AS (SELECT a FROM t WHERE b = 1) SELECT x.a FROM r AS x JOIN r AS y ON x.a = y.a")
+);
+
+test_extract_builder!(
+    extract_after_referenced_ctes,
+    "WITH c AS (SELECT a FROM t), d AS (SELECT a FROM (SELECT a FROM c) AS x) \
+     SELECT a FROM (SELECT a FROM c) AS y",
+    "r",
+    Ok(
+        "WITH c AS (SELECT a FROM t), r AS (SELECT a FROM c), d AS (SELECT a FROM r AS x) \
+        SELECT a FROM r AS y"
+    )
+);
+
+test_extract_builder!(
+    extract_name_in_use,
+    "SELECT a FROM (SELECT a FROM t) AS x, (SELECT a FROM t) AS y",
+    "t",
+    Err("t is already the name of a table")
+);
+
+test_extract_builder!(
+    extract_out_of_nested_with,
+    "SELECT a FROM (WITH q AS (SELECT 1 AS a) \
+     SELECT a FROM (SELECT a FROM q) AS x, (SELECT a FROM q) AS y) AS z",
+    "r",
+    Err("the nested query references the common table expression q of a nested query")
+);
+
+test_extract_builder!(
+    extract_without_repeated_query,
+    "SELECT a FROM (SELECT a FROM t) AS x",
+    "r",
+    Err("no nested query appears more than once")
+);
+
+#[test]
+fn round_trip() {
+    let sql = "WITH r AS (SELECT a FROM t) SELECT x.a FROM r AS x JOIN r AS y ON x.a = y.a";
+    let inlined = inline_cte(&parse(sql).unwrap(), "r").unwrap();
+    let extracted = extract_cte(&inlined, "r").unwrap();
+
+    assert_eq!(extracted.to_string(), sql);
+}
+
+#[test]
+fn self_references() {
+    let sql = "WITH RECURSIVE n AS (SELECT 1 AS a UNION ALL SELECT a + 1 FROM n WHERE a < 3), \
+               m AS (SELECT a FROM n), \
+               s AS (SELECT a FROM (WITH s AS (SELECT 1 AS a) SELECT a FROM s) AS x) \
+               SELECT a FROM m, s";
+
+    let ctes = match parse(sql).unwrap() {
+        SqlStatement::Statement(Statement::Select(select)) => select.common,
+        statement => panic!("unexpected statement {:?}", statement),
+    };
+    let result: Vec<bool> = ctes.iter().map(references_itself).collect();
+
+    assert_eq!(result, vec![true, false, false]);
+}
@@ -36,6 +36,7 @@ test_builder!(
             column_names: None,
             query: SelectStatement {
                 common: vec![],
+                recursive: false,
                 expr: Box::new(SetExpression::Query(QuerySetExpression {
                     mode: SelectMode::All,
                     columns: ResultColumns::List(vec![ResultColumn::Expr(ExprResultColumn {
@@ -50,6 +51,7 @@ test_builder!(
                 limit: None
             },
         }],
+        recursive: false,
         expr: Box::new(SetExpression::Query(QuerySetExpression {
             mode: SelectMode::All,
             columns: ResultColumns::List(vec![ResultColumn::Expr(ExprResultColumn {
@@ -74,6 +76,7 @@ test_builder!(
             column_names: None,
             query: SelectStatement {
                 common: vec![],
+                recursive: false,
                 expr: Box::new(SetExpression::Query(QuerySetExpression {
                     mode: SelectMode::All,
                     columns: ResultColumns::List(vec![ResultColumn::Expr(ExprResultColumn {
@@ -88,6 +91,7 @@ test_builder!(
                 limit: None
             },
         }],
+        recursive: false,
         expr: Box::new(SetExpression::Query(QuerySetExpression {
             mode: SelectMode::All,
             columns: ResultColumns::List(vec![ResultColumn::Expr(ExprResultColumn {
@@ -107,3 +111,45 @@ test_builder!(
         limit: None
     }))
 );
+
+#[test]
+fn select_recursive_cte() {
+    let sql = "WITH RECURSIVE n (x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM n WHERE x < 3), \
+               m AS (SELECT x FROM n) SELECT x FROM m";
+
+    match parse(sql).unwrap() {
+        SqlStatement::Statement(Statement::Select(select)) => {
+            assert!(select.recursive);
+            assert_eq!(select.common.len(), 2);
+        }
+        statement => panic!("unexpected statement {:?}", statement),
+    }
+}
+
+#[test]
+fn select_cte_named_recursive() {
+    match parse("WITH \"recursive\" AS (SELECT 1) SELECT * FROM \"recursive\"").unwrap() {
+        SqlStatement::Statement(Statement::Select(select)) => {
+            assert!(!select.recursive);
+            assert_eq!(
+                select.common[0].identifier,
+                symbols::Name::from("recursive")
+            );
+        }
+        statement => panic!("unexpected statement {:?}", statement),
+    }
+}
+
+#[test]
+fn select_invalid_recursive_cte() {
+    let invalid = [
+        "WITH RECURSIVE n AS (SELECT 1 UNION SELECT x FROM n) SELECT x FROM n",
+        "WITH RECURSIVE n AS (SELECT x FROM n UNION ALL SELECT 1) SELECT x FROM n",
+        "WITH RECURSIVE n AS (SELECT 1 UNION ALL SELECT x FROM n UNION ALL SELECT 2) SELECT x FROM n",
+        "WITH RECURSIVE n AS (SELECT x FROM n) SELECT x FROM n",
+    ];
+
+    for sql in invalid.iter() {
+        assert!(parse(sql).is_err(), "{}", sql);
+    }
+}
@@ -0,0 +1,36 @@
+import pytest
+
+from hephaestus import diff
+
+
+def test_diff():
+    result = diff("SELECT a FROM t JOIN u ON t.a = u.a", "select A from T join U using (a)")
+
+    assert result == [
+        {
+            "_type": "Edit",
+            "kind": "changed",
+            "path": "expr.from[0].constraint",
+            "old": "ON t.a = u.a",
+            "new": "USING (a)",
+        }
+    ]
+
+
+@pytest.mark.parametrize(
+    "sql_a, sql_b, expected",
+    [
+        ["SELECT a, c FROM t", "SELECT a, b, c FROM t", ("added", "expr.columns[1]", None, "b")],
+        ["SELECT a, b FROM t", "SELECT a FROM t", ("removed", "expr.columns[1]", "b", None)],
+        ["SELECT a FROM t", "SELECT b FROM t", ("changed", "expr.columns[0].expr", "a", "b")],
+    ],
+)
+def test_diff_edits(sql_a, sql_b, expected):
+    [edit] = diff(sql_a, sql_b)
+
+    assert (edit["kind"], edit["path"], edit["old"], edit["new"]) == expected
+
+
+def test_diff_invalid_sql():
+    with pytest.raises(ValueError):
+        diff("SELECT a", "SELECT")
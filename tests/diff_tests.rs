@@ -0,0 +1,169 @@
+// MIT License
+//
+// Copyright (c) 2019 Daniele Esposti
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use hephaestus::diff::{diff, Edit, EditKind};
+use hephaestus::parse;
+
+fn edit(kind: EditKind, path: &str, old: Option<&str>, new: Option<&str>) -> Edit {
+    Edit {
+        kind,
+        path: path.to_string(),
+        old: old.map(str::to_string),
+        new: new.map(str::to_string),
+    }
+}
+
+macro_rules! test_diff_builder {
+    ($name:ident, $sql_a:expr, $sql_b:expr, $expected:expr) => {
+        #[test]
+        fn $name() {
+            let a = parse($sql_a).unwrap();
+            let b = parse($sql_b).unwrap();
+
+            assert_eq!(diff(&a, &b), $expected);
+        }
+    };
+}
+
+test_diff_builder!(
+    diff_identical,
+    "SELECT a, b FROM t WHERE a = 1",
+    "SELECT a, b FROM t WHERE a = 1",
+    vec![]
+);
+
+test_diff_builder!(
+    diff_ignores_case,
+    "SELECT T.a AS x FROM t AS T",
+    "select t.A as X from T as t",
+    vec![]
+);
+
+test_diff_builder!(
+    diff_changed_literal,
+    "SELECT a FROM t WHERE a = 1",
+    "SELECT a FROM t WHERE a = 2",
+    vec![edit(
+        EditKind::Changed,
+        "expr.where_expr.right",
+        Some("1"),
+        Some("2")
+    )]
+);
+
+test_diff_builder!(
+    diff_added_column,
+    "SELECT a, c FROM t",
+    "SELECT a, b, c FROM t",
+    vec![edit(EditKind::Added, "expr.columns[1]", None, Some("b"))]
+);
+
+test_diff_builder!(
+    diff_removed_column,
+    "SELECT a, b, c FROM t",
+    "SELECT a, c FROM t",
+    vec![edit(EditKind::Removed, "expr.columns[1]", Some("b"), None)]
+);
+
+test_diff_builder!(
+    diff_changed_column,
+    "SELECT a, b AS x, c FROM t",
+    "SELECT a, b AS y, c FROM t",
+    vec![edit(
+        EditKind::Changed,
+        "expr.columns[1].rename",
+        Some("x"),
+        Some("y")
+    )]
+);
+
+test_diff_builder!(
+    diff_removed_where,
+    "SELECT a FROM t WHERE a = 1",
+    "SELECT a FROM t",
+    vec![edit(
+        EditKind::Removed,
+        "expr.where_expr",
+        Some("a = 1"),
+        None
+    )]
+);
+
+test_diff_builder!(
+    diff_changed_join_constraint,
+    "SELECT a FROM s, t JOIN u ON t.a = u.a",
+    "SELECT a FROM s, t JOIN u ON t.a = u.b",
+    vec![edit(
+        EditKind::Changed,
+        "expr.from[1].constraint.right.identifiers[1]",
+        Some("a"),
+        Some("b")
+    )]
+);
+
+test_diff_builder!(
+    diff_changed_join_constraint_kind,
+    "SELECT a FROM t JOIN u ON t.a = u.a",
+    "SELECT a FROM t JOIN u USING (a)",
+    vec![edit(
+        EditKind::Changed,
+        "expr.from[0].constraint",
+        Some("ON t.a = u.a"),
+        Some("USING (a)")
+    )]
+);
+
+test_diff_builder!(
+    diff_changed_expression_kind,
+    "SELECT a + 1 FROM t",
+    "SELECT a FROM t",
+    vec![edit(
+        EditKind::Changed,
+        "expr.columns[0].expr",
+        Some("a + 1"),
+        Some("a")
+    )]
+);
+
+test_diff_builder!(
+    diff_added_cte,
+    "SELECT a FROM t",
+    "WITH t AS (SELECT 1 AS a) SELECT a FROM t",
+    vec![edit(
+        EditKind::Added,
+        "common[0]",
+        None,
+        Some("t AS (SELECT 1 AS a)")
+    )]
+);
+
+test_diff_builder!(
+    diff_changed_statement_kind,
+    "SELECT a FROM t",
+    "DELETE FROM t",
+    vec![edit(
+        EditKind::Changed,
+        "",
+        Some("SELECT a FROM t"),
+        Some("DELETE FROM t")
+    )]
+);
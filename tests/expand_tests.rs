@@ -0,0 +1,161 @@
+// MIT License
+//
+// Copyright (c) 2019 Daniele Esposti
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use hephaestus::binder::BindErrorKind;
+use hephaestus::catalog::MemoryCatalog;
+use hephaestus::expand::expand_stars;
+use hephaestus::parse;
+
+const CATALOG: &str = r#"{
+    "schemas": [
+        {
+            "name": "s",
+            "tables": [
+                {
+                    "name": "orders",
+                    "columns": [
+                        {"name": "id", "data_type": "Numeric"},
+                        {"name": "customer_id", "data_type": "Numeric"},
+                        {"name": "placed_at", "data_type": "Timestamp"}
+                    ]
+                },
+                {
+                    "name": "customers",
+                    "columns": [
+                        {"name": "customer_id", "data_type": "Numeric"},
+                        {"name": "name", "data_type": "Varchar"}
+                    ]
+                }
+            ]
+        }
+    ]
+}"#;
+
+macro_rules! test_expand_builder {
+    ($name:ident, $sql:expr, $expected:expr) => {
+        #[test]
+        fn $name() {
+            let catalog = MemoryCatalog::from_json(CATALOG).unwrap();
+            let mut statement = parse($sql).unwrap();
+
+            let errors = expand_stars(&catalog, &mut statement);
+
+            assert_eq!(errors, vec![]);
+            assert_eq!(statement, parse($expected).unwrap());
+        }
+    };
+}
+
+test_expand_builder!(
+    expand_all,
+    "SELECT * FROM orders",
+    "SELECT orders.id, orders.customer_id, orders.placed_at FROM orders"
+);
+
+test_expand_builder!(
+    expand_all_from,
+    "SELECT c.name, o.* FROM s.orders o, customers c",
+    "SELECT c.name, o.id, o.customer_id, o.placed_at FROM s.orders o, customers c"
+);
+
+test_expand_builder!(
+    expand_all_over_join,
+    "SELECT * FROM customers c JOIN orders o ON c.customer_id = o.customer_id",
+    "SELECT c.customer_id, c.name, o.id, o.customer_id, o.placed_at \
+     FROM customers c JOIN orders o ON c.customer_id = o.customer_id"
+);
+
+test_expand_builder!(
+    expand_using_join,
+    "SELECT * FROM orders o JOIN customers c USING (customer_id)",
+    "SELECT customer_id, o.id, o.placed_at, c.name \
+     FROM orders o JOIN customers c USING (customer_id)"
+);
+
+test_expand_builder!(
+    expand_all_from_using_join,
+    "SELECT c.* FROM orders o JOIN customers c USING (customer_id)",
+    "SELECT c.customer_id, c.name FROM orders o JOIN customers c USING (customer_id)"
+);
+
+test_expand_builder!(
+    expand_natural_join,
+    "SELECT * FROM customers c NATURAL JOIN orders o ON 1 = 1",
+    "SELECT customer_id, c.name, o.id, o.placed_at FROM customers c NATURAL JOIN orders o ON 1 = 1"
+);
+
+test_expand_builder!(
+    expand_derived_table,
+    "SELECT * FROM (SELECT id, id + 1 AS next FROM orders) d",
+    "SELECT d.id, d.next FROM (SELECT id, id + 1 AS next FROM orders) d"
+);
+
+test_expand_builder!(
+    expand_cte_with_column_names,
+    "WITH c (k, n) AS (SELECT * FROM customers) SELECT * FROM c",
+    "WITH c (k, n) AS (SELECT customers.customer_id, customers.name FROM customers) \
+     SELECT c.k, c.n FROM c"
+);
+
+test_expand_builder!(
+    expand_nested_queries,
+    "SELECT c.*, (SELECT * FROM (SELECT MAX(id) AS m FROM orders) x) FROM customers c",
+    "SELECT c.customer_id, c.name, (SELECT x.m FROM (SELECT MAX(id) AS m FROM orders) x) \
+     FROM customers c"
+);
+
+test_expand_builder!(
+    expand_nested_queries_in_select_list_and_from,
+    "SELECT (SELECT c.* FROM (SELECT name FROM customers) c), d.* FROM (SELECT * FROM orders) d",
+    "SELECT (SELECT c.name FROM (SELECT name FROM customers) c), d.id, d.customer_id, d.placed_at \
+     FROM (SELECT orders.id, orders.customer_id, orders.placed_at FROM orders) d"
+);
+
+test_expand_builder!(
+    expand_keeps_unnamed_columns,
+    "SELECT * FROM (SELECT id + 1 FROM orders) d",
+    "SELECT * FROM (SELECT id + 1 FROM orders) d"
+);
+
+test_expand_builder!(
+    expand_keeps_count,
+    "SELECT COUNT(*) FROM orders",
+    "SELECT COUNT(*) FROM orders"
+);
+
+#[test]
+fn expand_keeps_unknown_tables() {
+    let catalog = MemoryCatalog::from_json(CATALOG).unwrap();
+    let sql = "SELECT m.*, o.* FROM missing m, orders o";
+    let mut statement = parse(sql).unwrap();
+
+    let errors = expand_stars(&catalog, &mut statement);
+
+    assert_eq!(
+        errors.iter().map(|error| error.kind).collect::<Vec<_>>(),
+        vec![BindErrorKind::UnknownTable]
+    );
+    assert_eq!(
+        statement,
+        parse("SELECT m.*, o.id, o.customer_id, o.placed_at FROM missing m, orders o").unwrap()
+    );
+}
@@ -0,0 +1,26 @@
+import pytest
+
+from hephaestus import fingerprint
+
+
+def test_fingerprint():
+    result = fingerprint("SELECT a FROM t WHERE y IN (1, 2, 3) AND x = 42")
+
+    assert result == ("7efa7e3950a2a060", "SELECT a FROM t WHERE x = ? AND y IN (?)")
+
+
+@pytest.mark.parametrize(
+    "sql_a, sql_b",
+    [
+        ["SELECT 1", "select 2"],
+        ["SELECT a FROM t WHERE x = 'a' OR y = 1", "SELECT a FROM t WHERE y = 2 OR x = 'b'"],
+        ["SELECT a FROM t WHERE x IN (1)", "SELECT a FROM t WHERE x IN (1, 2, 3)"],
+    ],
+)
+def test_fingerprint_same_query(sql_a, sql_b):
+    assert fingerprint(sql_a) == fingerprint(sql_b)
+
+
+def test_fingerprint_invalid_sql():
+    with pytest.raises(ValueError):
+        fingerprint("SELECT")
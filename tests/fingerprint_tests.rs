@@ -0,0 +1,86 @@
+// MIT License
+//
+// Copyright (c) 2019 Daniele Esposti
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use hephaestus::fingerprint::{fingerprint, normalize};
+use hephaestus::parse;
+
+macro_rules! test_normalize_builder {
+    ($name:ident, $sql:expr, $expected:expr) => {
+        #[test]
+        fn $name() {
+            let mut statement = parse($sql).unwrap();
+            normalize(&mut statement);
+
+            assert_eq!(statement.to_string(), $expected);
+        }
+    };
+}
+
+test_normalize_builder!(
+    normalize_literals,
+    "SELECT a, 'x' FROM t WHERE b = 1.5 AND c > DATE '2019-01-01' LIMIT 10",
+    "SELECT a, ? FROM t WHERE b = ? AND c > ? LIMIT ?"
+);
+
+test_normalize_builder!(
+    normalize_in_lists,
+    "SELECT 1 FROM t WHERE a IN (1, 2, 3) OR b NOT IN (c, 4, 'x', c)",
+    "SELECT ? FROM t WHERE a IN (?) OR b NOT IN (?, c)"
+);
+
+test_normalize_builder!(
+    normalize_and_operands,
+    "SELECT 1 FROM t WHERE c = 1 AND (b = 2 AND a = 3)",
+    "SELECT ? FROM t WHERE a = ? AND b = ? AND c = ?"
+);
+
+test_normalize_builder!(
+    normalize_nested_logical_operators,
+    "SELECT 1 FROM t WHERE (d OR c) AND (b = 1 OR a = 2)",
+    "SELECT ? FROM t WHERE (a = ? OR b = ?) AND (c OR d)"
+);
+
+test_normalize_builder!(
+    normalize_keeps_data_types,
+    "SELECT CAST(a AS VARCHAR(10)) FROM t",
+    "SELECT CAST(a AS VARCHAR(10)) FROM t"
+);
+
+#[test]
+fn fingerprint_groups_queries() {
+    let a = fingerprint(&parse("SELECT a FROM t WHERE x = 1 AND y IN (1, 2)").unwrap());
+    let b = fingerprint(&parse("select a from t where y in (3) and x = 42").unwrap());
+    let c = fingerprint(&parse("SELECT b FROM t WHERE x = 1 AND y IN (1, 2)").unwrap());
+
+    assert_eq!(a, b);
+    assert_ne!(a.hash, c.hash);
+    assert_eq!(a.sql, "SELECT a FROM t WHERE x = ? AND y IN (?)");
+}
+
+#[test]
+fn fingerprint_is_stable() {
+    let result = fingerprint(&parse("SELECT 1").unwrap());
+
+    assert_eq!(result.sql, "SELECT ?");
+    assert_eq!(result.hash, 0x199e_7dca_63ea_8858);
+    assert_eq!(result.to_string(), "199e7dca63ea8858");
+}
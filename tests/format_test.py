@@ -0,0 +1,29 @@
+import pytest
+
+from hephaestus import format_sql
+
+
+def test_format_sql():
+    sql = "select a, b -- the columns\nfrom t join u on t.a = u.a where a = 1"
+
+    assert format_sql(sql) == (
+        "SELECT a, b -- the columns\nFROM t\nJOIN u ON t.a = u.a\nWHERE a = 1"
+    )
+
+
+def test_format_sql_with_options():
+    sql = "SELECT a FROM t JOIN u ON t.a = u.a"
+    expected = "select a\nfrom t\n  join u on t.a = u.a"
+
+    assert format_sql(sql, keyword_case="lower", indent_width=2, indent_joins=True) == expected
+
+
+@pytest.mark.parametrize("options", [{"tabs": True}, {"keyword_case": "title"}])
+def test_format_sql_invalid_options(options):
+    with pytest.raises(ValueError, match="Invalid format options"):
+        format_sql("SELECT 1", **options)
+
+
+def test_format_sql_invalid_sql():
+    with pytest.raises(ValueError):
+        format_sql("SELECT FROM")
@@ -0,0 +1,175 @@
+// MIT License
+//
+// Copyright (c) 2019 Daniele Esposti
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use hephaestus::format::{format, format_sql, CommaStyle, FormatOptions, KeywordCase};
+use hephaestus::parse;
+
+macro_rules! test_format_builder {
+    ($name:ident, $sql:expr, $options:expr, $expected:expr) => {
+        #[test]
+        fn $name() {
+            let options = FormatOptions::from_toml($options).unwrap();
+            let formatted = format_sql($sql, &options).unwrap();
+
+            assert_eq!(formatted, $expected);
+
+            // formatting again must not change anything
+            assert_eq!(format_sql(&formatted, &options).unwrap(), formatted);
+        }
+    };
+}
+
+test_format_builder!(
+    clauses_on_their_own_line,
+    "select a, b from t where a = 1 and b = 2",
+    "",
+    "SELECT a, b\nFROM t\nWHERE a = 1 AND b = 2"
+);
+
+test_format_builder!(
+    long_list_one_item_per_line,
+    "SELECT aaaaaaaaaa, bbbbbbbbbbbbbb, cccccccccccccc, dddddddddddddd, eeeeeeeeeeeeeeee, ffffffffffff FROM t",
+    "",
+    "SELECT\n    aaaaaaaaaa,\n    bbbbbbbbbbbbbb,\n    cccccccccccccc,\n    dddddddddddddd,\n    eeeeeeeeeeeeeeee,\n    ffffffffffff\nFROM t"
+);
+
+test_format_builder!(
+    non_reserved_keywords_as_identifiers,
+    "select plan, count(count) from t left join query on t.max = query.max",
+    "keyword_case = \"lower\"",
+    "select plan, count(count)\nfrom t\nleft join query on t.max = query.max"
+);
+
+test_format_builder!(
+    non_reserved_keywords_keep_their_case,
+    "select plan, Count(*), \"date\" from t",
+    "",
+    "SELECT plan, COUNT(*), \"date\"\nFROM t"
+);
+
+test_format_builder!(
+    non_reserved_keywords_by_position,
+    "SELECT COUNT, count FROM t WHERE COUNT = COUNT(*)",
+    "keyword_case = \"lower\"",
+    "select COUNT, count\nfrom t\nwhere COUNT = count(*)"
+);
+
+test_format_builder!(
+    leading_commas,
+    "SELECT aaaaaaaaaa, bbbbbbbbbbbbbb, cccccccccccccc FROM t",
+    "comma_style = \"leading\"\nmax_width = 20",
+    "SELECT\n    aaaaaaaaaa\n    , bbbbbbbbbbbbbb\n    , cccccccccccccc\nFROM t"
+);
+
+test_format_builder!(
+    long_condition_broken_on_and,
+    "SELECT a FROM t WHERE (a = 1 OR b = 2) AND c = 3",
+    "max_width = 30",
+    "SELECT a\nFROM t\nWHERE (a = 1 OR b = 2)\n    AND c = 3"
+);
+
+test_format_builder!(
+    joins_aligned_with_from,
+    "SELECT a FROM t JOIN u ON t.a = u.a LEFT JOIN v ON u.b = v.b",
+    "",
+    "SELECT a\nFROM t\nJOIN u ON t.a = u.a\nLEFT JOIN v ON u.b = v.b"
+);
+
+test_format_builder!(
+    joins_indented,
+    "SELECT a FROM t JOIN u ON t.a = u.a LEFT JOIN v ON u.b = v.b",
+    "indent_joins = true",
+    "SELECT a\nFROM t\n    JOIN u ON t.a = u.a\n    LEFT JOIN v ON u.b = v.b"
+);
+
+test_format_builder!(
+    case_expanded,
+    "SELECT CASE WHEN a = 1 THEN 'x' ELSE 'y' END AS c FROM t",
+    "expand_case = true",
+    "SELECT\n    CASE\n        WHEN a = 1 THEN 'x'\n        ELSE 'y'\n    END AS c\nFROM t"
+);
+
+test_format_builder!(
+    common_table_expressions_indented,
+    "WITH x AS (SELECT a FROM t) SELECT a FROM x ORDER BY a LIMIT 10",
+    "indent_width = 2",
+    "WITH x AS (\n  SELECT a\n  FROM t\n)\nSELECT a\nFROM x\nORDER BY a\nLIMIT 10"
+);
+
+test_format_builder!(
+    insert_statement,
+    "INSERT INTO t (a, b) SELECT a, b FROM u",
+    "",
+    "INSERT INTO t (a, b)\nSELECT a, b\nFROM u"
+);
+
+test_format_builder!(
+    lowercase_keywords,
+    "UPDATE t SET a = 1 WHERE b = 2",
+    "keyword_case = \"lower\"",
+    "update t\nset a = 1\nwhere b = 2"
+);
+
+test_format_builder!(
+    comments_kept,
+    "-- header\nselect a, -- first\n  b\nfrom t -- the table\n;\n-- trailing",
+    "",
+    "-- header\nSELECT a, b -- first\nFROM t; -- the table\n-- trailing"
+);
+
+test_format_builder!(
+    many_statements,
+    "select 1;select a from t;",
+    "",
+    "SELECT 1;\n\nSELECT a\nFROM t;"
+);
+
+#[test]
+fn format_statement() {
+    let statement = parse("select a from t").unwrap();
+    let options = FormatOptions {
+        keyword_case: KeywordCase::Lower,
+        ..FormatOptions::default()
+    };
+
+    assert_eq!(format(&statement, &options), "select a\nfrom t");
+}
+
+#[test]
+fn options_from_toml() {
+    let options = FormatOptions::from_toml("comma_style = \"leading\"\nindent_width = 2").unwrap();
+
+    assert_eq!(options.comma_style, CommaStyle::Leading);
+    assert_eq!(options.indent_width, 2);
+    assert_eq!(options.max_width, FormatOptions::default().max_width);
+}
+
+#[test]
+fn invalid_options_from_toml() {
+    assert!(FormatOptions::from_toml("keyword_case = \"title\"").is_err());
+    assert!(FormatOptions::from_toml("tabs = true").is_err());
+}
+
+#[test]
+fn invalid_sql() {
+    assert!(format_sql("SELECT FROM", &FormatOptions::default()).is_err());
+}
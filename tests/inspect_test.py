@@ -0,0 +1,34 @@
+import pytest
+
+from hephaestus import columns, ctes, functions, literals, tables
+
+
+def test_tables():
+    sql = "WITH x AS (SELECT a FROM s.t) SELECT a FROM x JOIN u ON x.a = u.a"
+
+    assert tables(sql) == ["s.t", "x", "u"]
+
+
+def test_ctes():
+    assert ctes("WITH x AS (SELECT 1 FROM t), y AS (SELECT 2 FROM x) SELECT 1 FROM y") == [
+        "x",
+        "y",
+    ]
+
+
+def test_columns():
+    assert columns("SELECT t.a, b FROM t JOIN u USING (c) WHERE b > 1") == ["t.a", "b", "c"]
+
+
+def test_functions():
+    assert functions("SELECT COUNT(*), sum(a), s.f(b) FROM t") == ["COUNT", "SUM", "s.f"]
+
+
+def test_literals():
+    assert literals("SELECT 1, 'x' FROM t WHERE a = 1 AND b IS NULL") == ["1", "'x'"]
+
+
+@pytest.mark.parametrize("function", [tables, ctes, columns, functions, literals])
+def test_invalid_sql(function):
+    with pytest.raises(ValueError):
+        function("SELECT")
@@ -0,0 +1,101 @@
+// MIT License
+//
+// Copyright (c) 2019 Daniele Esposti
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use hephaestus::inspect::{columns, ctes, functions, literals, tables};
+use hephaestus::parse;
+
+macro_rules! test_inspect_builder {
+    ($name:ident, $function:ident, $sql:expr, $expected:expr) => {
+        #[test]
+        fn $name() {
+            let statement = parse($sql).unwrap();
+            let expected: Vec<&str> = $expected;
+
+            assert_eq!($function(&statement), expected);
+        }
+    };
+}
+
+test_inspect_builder!(
+    tables_in_joins_and_subqueries,
+    tables,
+    "SELECT a FROM s.t JOIN u ON t.a = u.a WHERE b IN (SELECT b FROM v) AND c = (SELECT 1 FROM S.T)",
+    vec!["s.t", "u", "v"]
+);
+
+test_inspect_builder!(
+    tables_in_ctes,
+    tables,
+    "WITH x AS (SELECT a FROM t) SELECT a FROM x",
+    vec!["t", "x"]
+);
+
+test_inspect_builder!(
+    tables_in_insert_statement,
+    tables,
+    "INSERT INTO s.t (a, b) SELECT a, b FROM u",
+    vec!["s.t", "u"]
+);
+
+test_inspect_builder!(
+    tables_in_update_statement,
+    tables,
+    "UPDATE t SET a = 1 WHERE b IN (SELECT b FROM u)",
+    vec!["t", "u"]
+);
+
+test_inspect_builder!(
+    ctes_nested,
+    ctes,
+    "WITH x AS (WITH y AS (SELECT 1 FROM t) SELECT 1 FROM y), z AS (SELECT 2 FROM x) SELECT 1 FROM z",
+    vec!["x", "y", "z"]
+);
+
+test_inspect_builder!(ctes_none, ctes, "SELECT 1 FROM t", vec![]);
+
+test_inspect_builder!(
+    columns_qualified_and_using,
+    columns,
+    "SELECT t.a, b FROM t JOIN u USING (c) WHERE T.A > 1 ORDER BY d",
+    vec!["t.a", "b", "c", "d"]
+);
+
+test_inspect_builder!(
+    columns_in_update_statement,
+    columns,
+    "UPDATE t SET a = b + 1, c = 2",
+    vec!["a", "b", "c"]
+);
+
+test_inspect_builder!(
+    functions_builtin_and_unknown,
+    functions,
+    "SELECT COUNT(*), sum(a), coalesce(b, 1), s.f(c), COUNT(d) FROM t",
+    vec!["COUNT", "SUM", "COALESCE", "s.f"]
+);
+
+test_inspect_builder!(
+    literals_distinct,
+    literals,
+    "SELECT 1, 'x', NULL FROM t WHERE a = 1 AND b > DATE '2019-01-01'",
+    vec!["1", "'x'", "NULL", "DATE '2019-01-01'"]
+);
@@ -0,0 +1,32 @@
+from hephaestus import join_graphs, join_graphs_dot
+
+
+def test_join_graphs():
+    (graph,) = join_graphs("SELECT a FROM t AS x LEFT JOIN s.u ON x.a = u.b")
+
+    assert graph["nodes"] == [{"name": ["t"], "alias": "x"}, {"name": ["s", "u"], "alias": None}]
+
+    (edge,) = graph["edges"]
+
+    assert edge["left"] == 0
+    assert edge["right"] == 1
+    assert edge["op"]["_type"] == "RegularJoin"
+    assert edge["keys"] == [{"left": ["x", "a"], "right": ["u", "b"]}]
+
+
+def test_join_graphs_where():
+    (graph,) = join_graphs("SELECT a FROM t, u WHERE t.a = u.a")
+    (edge,) = graph["edges"]
+
+    assert edge["op"] is None
+    assert edge["keys"] == [{"left": ["t", "a"], "right": ["u", "a"]}]
+
+
+def test_join_graphs_dot():
+    assert join_graphs_dot("SELECT a FROM t JOIN u USING (a)") == (
+        "digraph joins {\n"
+        '    t0 [label="t"];\n'
+        '    t1 [label="u"];\n'
+        '    t0 -> t1 [label="JOIN\\nt.a = u.a"];\n'
+        "}\n"
+    )
@@ -0,0 +1,149 @@
+// MIT License
+//
+// Copyright (c) 2019 Daniele Esposti
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use hephaestus::ast::{JoinOperator, JoinType};
+use hephaestus::join_graph::{join_graphs, to_dot, JoinEdge, JoinKey, TableNode};
+use hephaestus::parse;
+use hephaestus::symbols::Name;
+
+macro_rules! test_join_graph_builder {
+    ($name:ident, $sql:expr, $expected:expr) => {
+        #[test]
+        fn $name() {
+            let result = to_dot(&join_graphs(&parse($sql).unwrap()));
+            let expected = $expected;
+
+            assert_eq!(result, expected);
+        }
+    };
+}
+
+test_join_graph_builder!(
+    single_table,
+    "SELECT a FROM s.t",
+    "digraph joins {\n    t0 [label=\"s.t\"];\n}\n"
+);
+
+test_join_graph_builder!(no_tables, "SELECT 1", "digraph joins {\n}\n");
+
+test_join_graph_builder!(
+    join_on,
+    "SELECT a FROM t AS x LEFT JOIN u AS y ON x.a = y.b AND y.c = x.d AND y.e > 1",
+    "digraph joins {
+    t0 [label=\"t AS x\"];
+    t1 [label=\"u AS y\"];
+    t0 -> t1 [label=\"LEFT JOIN\\nx.a = y.b\\nx.d = y.c\"];
+}
+"
+);
+
+test_join_graph_builder!(
+    join_using,
+    "SELECT a FROM t JOIN u AS y USING (a, b)",
+    "digraph joins {
+    t0 [label=\"t\"];
+    t1 [label=\"u AS y\"];
+    t0 -> t1 [label=\"JOIN\\nt.a = y.a\\nt.b = y.b\"];
+}
+"
+);
+
+test_join_graph_builder!(
+    chained_joins,
+    "SELECT a FROM t JOIN u ON t.a = u.a JOIN v ON t.b = v.b AND u.c = v.c LEFT JOIN w ON w.d > 1",
+    "digraph joins {
+    t0 [label=\"t\"];
+    t1 [label=\"u\"];
+    t2 [label=\"v\"];
+    t3 [label=\"w\"];
+    t0 -> t1 [label=\"JOIN\\nt.a = u.a\"];
+    t0 -> t2 [label=\"JOIN\\nt.b = v.b\"];
+    t1 -> t2 [label=\"JOIN\\nu.c = v.c\"];
+    t2 -> t3 [label=\"LEFT JOIN\"];
+}
+"
+);
+
+test_join_graph_builder!(
+    comma_separated,
+    "SELECT a FROM t, u AS y, v WHERE t.a = y.a AND y.b = v.b AND y.c = y.d AND t.e = 1",
+    "digraph joins {
+    t0 [label=\"t\"];
+    t1 [label=\"u AS y\"];
+    t2 [label=\"v\"];
+    t0 -> t1 [label=\"WHERE\\nt.a = y.a\", style=dashed];
+    t1 -> t2 [label=\"WHERE\\ny.b = v.b\", style=dashed];
+}
+"
+);
+
+test_join_graph_builder!(
+    nested_queries,
+    "SELECT a FROM t JOIN (SELECT b FROM u, v WHERE u.b = v.b) AS s ON t.a = s.b",
+    "digraph joins {
+    subgraph cluster_0 {
+        label=\"query 1\";
+        q0_t0 [label=\"t\"];
+        q0_t1 [label=\"(subquery) AS s\"];
+        q0_t0 -> q0_t1 [label=\"JOIN\\nt.a = s.b\"];
+    }
+    subgraph cluster_1 {
+        label=\"query 2\";
+        q1_t0 [label=\"u\"];
+        q1_t1 [label=\"v\"];
+        q1_t0 -> q1_t1 [label=\"WHERE\\nu.b = v.b\", style=dashed];
+    }
+}
+"
+);
+
+#[test]
+fn join_graph() {
+    let graphs = join_graphs(&parse("SELECT a FROM t RIGHT JOIN u AS y ON t.a = y.a").unwrap());
+
+    assert_eq!(graphs.len(), 1);
+    assert_eq!(
+        graphs[0].nodes,
+        vec![
+            TableNode {
+                name: vec![Name::from("t")],
+                alias: None,
+            },
+            TableNode {
+                name: vec![Name::from("u")],
+                alias: Some(Name::from("y")),
+            },
+        ]
+    );
+    assert_eq!(
+        graphs[0].edges,
+        vec![JoinEdge {
+            left: 0,
+            right: 1,
+            op: Some(JoinOperator::Join(JoinType::Right)),
+            keys: vec![JoinKey {
+                left: vec![Name::from("t"), Name::from("a")],
+                right: vec![Name::from("y"), Name::from("a")],
+            }],
+        }]
+    );
+}
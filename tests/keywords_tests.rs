@@ -0,0 +1,133 @@
+// MIT License
+//
+// Copyright (c) 2019 Daniele Esposti
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use hephaestus::ast::{
+    ExprResultColumn, Expression, QualifiedIdentifierExpression, QuerySetExpression, ResultColumn,
+    ResultColumns, SetExpression, SqlStatement, Statement,
+};
+use hephaestus::parse;
+use hephaestus::recovery::parse_strict;
+use hephaestus::symbols::Name;
+
+macro_rules! test_keyword_builder {
+    ($name:ident, $sql:expr, $expected_sql:expr) => {
+        #[test]
+        fn $name() {
+            let statement = parse_strict($sql).unwrap();
+
+            assert_eq!(statement.to_string(), $expected_sql);
+        }
+    };
+}
+
+test_keyword_builder!(
+    column_names,
+    "SELECT plan, query, count, sum, offset, precision, value FROM t",
+    "SELECT plan, query, count, sum, offset, precision, value FROM t"
+);
+
+test_keyword_builder!(
+    qualified_column_names,
+    "SELECT t.count, t.plan FROM s.query AS t",
+    "SELECT t.count, t.plan FROM s.query AS t"
+);
+
+test_keyword_builder!(
+    aliases_with_as,
+    "SELECT a AS count FROM t AS plan",
+    "SELECT a AS count FROM t AS plan"
+);
+
+test_keyword_builder!(
+    table_names,
+    "SELECT a FROM query LEFT JOIN plan ON query.a = plan.a",
+    "SELECT a FROM query LEFT JOIN plan ON query.a = plan.a"
+);
+
+test_keyword_builder!(
+    function_arguments,
+    "SELECT count(count), max(max), sum(sum), min(min) FROM t",
+    "SELECT COUNT(count), MAX(max), SUM(sum), MIN(min) FROM t"
+);
+
+test_keyword_builder!(
+    keywords_still_keywords,
+    "SELECT DATE '2020-01-01', CAST(plan AS DATE), count(query) FROM t WHERE plan ISNULL",
+    "SELECT DATE '2020-01-01', CAST(plan AS DATE), COUNT(query) FROM t WHERE plan IS NULL"
+);
+
+test_keyword_builder!(
+    cte_and_columns,
+    "WITH plan (count, max) AS (SELECT 1, 2) SELECT count FROM plan",
+    "WITH plan (count, max) AS (SELECT 1, 2) SELECT count FROM plan"
+);
+
+test_keyword_builder!(
+    quoted_reserved_keywords,
+    "SELECT \"date\", \"left\".\"file\" FROM \"left\" WHERE \"zone\" = 1",
+    "SELECT \"date\", \"left\".\"file\" FROM \"left\" WHERE \"zone\" = 1"
+);
+
+test_keyword_builder!(
+    quoted_names,
+    "SELECT \"a b\", \"a\"\"b\", \"count\", \"c\" FROM t AS \"select\"",
+    "SELECT \"a b\", \"a\"\"b\", count, c FROM t AS \"select\""
+);
+
+#[test]
+fn keyword_name_keeps_its_case() {
+    let statement = parse("SELECT Count FROM t").unwrap();
+
+    let columns = match statement {
+        SqlStatement::Statement(Statement::Select(select)) => match *select.expr {
+            SetExpression::Query(QuerySetExpression { columns, .. }) => columns,
+            _ => panic!("Not a query"),
+        },
+        _ => panic!("Not a select statement"),
+    };
+
+    assert_eq!(
+        columns,
+        ResultColumns::List(vec![ResultColumn::Expr(ExprResultColumn {
+            expr: Expression::QualifiedIdentifier(QualifiedIdentifierExpression {
+                identifiers: vec![Name::new("Count".to_string())]
+            }),
+            rename: None
+        })])
+    );
+}
+
+#[test]
+fn reserved_keywords_are_not_identifiers() {
+    assert!(parse_strict("SELECT select FROM t").is_err());
+    assert!(parse_strict("SELECT a FROM order").is_err());
+    assert!(parse_strict("SELECT group FROM t").is_err());
+    assert!(parse_strict("SELECT date FROM t").is_err());
+    assert!(parse_strict("SELECT a FROM left").is_err());
+    assert!(parse_strict("SELECT a FROM t WHERE zone = 1").is_err());
+}
+
+#[test]
+fn alias_without_as_is_not_a_keyword() {
+    assert!(parse_strict("SELECT a count FROM t").is_err());
+    assert!(parse_strict("SELECT a x FROM t").is_ok());
+}
@@ -0,0 +1,207 @@
+// MIT License
+//
+// Copyright (c) 2019 Daniele Esposti
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::BTreeSet;
+
+use hephaestus::lineage::{lineage, ColumnLineage, ColumnReference, LineageAnalyzer};
+use hephaestus::parse;
+use hephaestus::symbols;
+
+fn names(names: &[&str]) -> Vec<symbols::Name> {
+    names
+        .iter()
+        .map(|name| symbols::Name::from(*name))
+        .collect()
+}
+
+fn column(name: Option<&str>, sources: &[(&[&str], &str)]) -> ColumnLineage {
+    ColumnLineage {
+        name: name.map(symbols::Name::from),
+        sources: sources
+            .iter()
+            .map(|(table, column)| ColumnReference {
+                table: names(table),
+                column: symbols::Name::from(*column),
+            })
+            .collect(),
+    }
+}
+
+fn tables(tables: &[&[&str]]) -> BTreeSet<Vec<symbols::Name>> {
+    tables.iter().map(|table| names(table)).collect()
+}
+
+macro_rules! test_lineage_builder {
+    ($name:ident, $sql:expr, $columns:expr, $reads:expr, $writes:expr) => {
+        #[test]
+        fn $name() {
+            let result = lineage(&parse($sql).unwrap());
+
+            assert_eq!(result.columns, $columns);
+            assert_eq!(result.reads, tables($reads));
+            assert_eq!(result.writes, tables($writes));
+        }
+    };
+}
+
+test_lineage_builder!(
+    lineage_simple_select,
+    "SELECT a, b AS c FROM s.t",
+    vec![
+        column(Some("a"), &[(&["s", "t"], "a")]),
+        column(Some("c"), &[(&["s", "t"], "b")]),
+    ],
+    &[&["s", "t"]],
+    &[]
+);
+
+test_lineage_builder!(
+    lineage_join_with_aliases,
+    "SELECT x.a, y.b + x.c AS d FROM t1 x JOIN t2 y ON x.id = y.id",
+    vec![
+        column(Some("a"), &[(&["t1"], "a")]),
+        column(Some("d"), &[(&["t1"], "c"), (&["t2"], "b")]),
+    ],
+    &[&["t1"], &["t2"]],
+    &[]
+);
+
+test_lineage_builder!(
+    lineage_cte,
+    "WITH c AS (SELECT a FROM t) SELECT a FROM c",
+    vec![column(Some("a"), &[(&["t"], "a")])],
+    &[&["t"]],
+    &[]
+);
+
+test_lineage_builder!(
+    lineage_cte_column_names,
+    "WITH c (x) AS (SELECT a FROM t) SELECT c.x FROM c",
+    vec![column(Some("x"), &[(&["t"], "a")])],
+    &[&["t"]],
+    &[]
+);
+
+test_lineage_builder!(
+    lineage_recursive_cte,
+    "WITH RECURSIVE n (k) AS (SELECT 1 FROM dual UNION ALL SELECT k + 1 FROM n WHERE k < 10) \
+     SELECT k FROM n",
+    vec![column(Some("k"), &[])],
+    &[&["dual"]],
+    &[]
+);
+
+test_lineage_builder!(
+    lineage_recursive_cte_anchor_columns,
+    "WITH RECURSIVE n (k) AS (SELECT a FROM t UNION ALL SELECT n.k + u.b FROM n, u) \
+     SELECT k FROM n",
+    vec![column(Some("k"), &[(&["t"], "a"), (&["u"], "b")])],
+    &[&["t"], &["u"]],
+    &[]
+);
+
+test_lineage_builder!(
+    lineage_derived_table,
+    "SELECT s.total FROM (SELECT a + b AS total FROM t) s",
+    vec![column(Some("total"), &[(&["t"], "a"), (&["t"], "b")])],
+    &[&["t"]],
+    &[]
+);
+
+test_lineage_builder!(
+    lineage_ambiguous_column,
+    "SELECT a FROM t1, t2",
+    vec![column(Some("a"), &[(&[], "a")])],
+    &[&["t1"], &["t2"]],
+    &[]
+);
+
+test_lineage_builder!(
+    lineage_join_using,
+    "SELECT id FROM t1 JOIN t2 USING (id)",
+    vec![column(Some("id"), &[(&["t1"], "id"), (&["t2"], "id")])],
+    &[&["t1"], &["t2"]],
+    &[]
+);
+
+test_lineage_builder!(
+    lineage_select_all,
+    "SELECT * FROM t1, (SELECT a FROM t2) x",
+    vec![
+        column(Some("*"), &[(&["t1"], "*")]),
+        column(Some("a"), &[(&["t2"], "a")]),
+    ],
+    &[&["t1"], &["t2"]],
+    &[]
+);
+
+test_lineage_builder!(
+    lineage_union,
+    "SELECT a FROM t1 UNION ALL SELECT b FROM t2",
+    vec![column(Some("a"), &[(&["t1"], "a"), (&["t2"], "b")])],
+    &[&["t1"], &["t2"]],
+    &[]
+);
+
+test_lineage_builder!(
+    lineage_correlated_subquery,
+    "SELECT t.a, (SELECT max(u.b) FROM u WHERE u.id = t.id) AS m FROM t",
+    vec![
+        column(Some("a"), &[(&["t"], "a")]),
+        column(Some("m"), &[(&["u"], "b")]),
+    ],
+    &[&["t"], &["u"]],
+    &[]
+);
+
+test_lineage_builder!(
+    lineage_insert,
+    "INSERT INTO s.target (x, y) SELECT a, b FROM src",
+    vec![
+        column(Some("x"), &[(&["src"], "a")]),
+        column(Some("y"), &[(&["src"], "b")]),
+    ],
+    &[&["src"]],
+    &[&["s", "target"]]
+);
+
+test_lineage_builder!(
+    lineage_update,
+    "UPDATE t SET a = b + 1 WHERE c IN (SELECT c FROM u)",
+    vec![column(Some("a"), &[(&["t"], "b")])],
+    &[&["u"]],
+    &[&["t"]]
+);
+
+#[test]
+fn lineage_default_schema() {
+    let statement = parse("WITH c AS (SELECT a FROM t) SELECT a FROM c").unwrap();
+    let result = LineageAnalyzer::new()
+        .with_default_schema(symbols::Name::from("public"))
+        .analyze(&statement);
+
+    assert_eq!(
+        result.columns,
+        vec![column(Some("a"), &[(&["public", "t"], "a")])]
+    );
+    assert_eq!(result.reads, tables(&[&["public", "t"]]));
+}
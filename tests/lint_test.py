@@ -0,0 +1,30 @@
+import pytest
+
+from hephaestus import lint
+
+
+def test_lint():
+    assert lint("SELECT a FROM t WHERE b = NULL") == [
+        {
+            "rule": "null-comparison",
+            "severity": "error",
+            "message": "comparing with = NULL is never true, use IS NULL",
+            "span": (22, 23),
+        }
+    ]
+
+
+def test_lint_clean_statement():
+    assert lint("SELECT a FROM t ORDER BY a LIMIT 1") == []
+
+
+def test_lint_disable():
+    assert [d["rule"] for d in lint("SELECT * FROM t, u")] == ["select-star", "implicit-cross-join"]
+    assert [d["rule"] for d in lint("SELECT * FROM t, u", disable=["select-star"])] == [
+        "implicit-cross-join"
+    ]
+
+
+def test_lint_unknown_rule():
+    with pytest.raises(ValueError, match="unknown rule `no-such-rule`"):
+        lint("SELECT 1", disable=["no-such-rule"])
@@ -0,0 +1,193 @@
+// MIT License
+//
+// Copyright (c) 2019 Daniele Esposti
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use hephaestus::ast::{Expression, Literal, SqlStatement};
+use hephaestus::lint::{lint, Linter, Rule, Severity, Violation};
+use hephaestus::parse;
+use hephaestus::symbols::Span;
+use hephaestus::visit::{self, Visitor};
+
+macro_rules! test_lint_builder {
+    ($name:ident, $sql:expr, $expected:expr) => {
+        #[test]
+        fn $name() {
+            let statement = parse($sql).unwrap();
+            let expected: Vec<&str> = $expected;
+
+            let rules: Vec<String> = lint(&statement)
+                .into_iter()
+                .map(|diagnostic| diagnostic.rule)
+                .collect();
+
+            assert_eq!(rules, expected);
+        }
+    };
+}
+
+test_lint_builder!(
+    clean_statement,
+    "SELECT a, b FROM t JOIN u ON t.a = u.a WHERE a IS NULL ORDER BY a LIMIT 10",
+    vec![]
+);
+
+test_lint_builder!(select_star, "SELECT * FROM t", vec!["select-star"]);
+
+test_lint_builder!(
+    select_star_from_table,
+    "SELECT a, t.* FROM t",
+    vec!["select-star"]
+);
+
+test_lint_builder!(count_star, "SELECT COUNT(*) FROM t", vec![]);
+
+test_lint_builder!(
+    implicit_cross_join,
+    "SELECT a FROM t, u WHERE t.a = u.a",
+    vec!["implicit-cross-join"]
+);
+
+test_lint_builder!(
+    not_in_subquery,
+    "SELECT a FROM t WHERE a NOT IN (SELECT a FROM u)",
+    vec!["not-in-subquery"]
+);
+
+test_lint_builder!(
+    not_in_list,
+    "SELECT a FROM t WHERE a NOT IN (1, 2) AND b IN (SELECT b FROM u)",
+    vec![]
+);
+
+test_lint_builder!(
+    union_distinct,
+    "SELECT a FROM t UNION SELECT a FROM u UNION ALL SELECT a FROM v",
+    vec!["union-distinct"]
+);
+
+test_lint_builder!(
+    unaliased_subquery,
+    "SELECT a FROM (SELECT a FROM t) JOIN (SELECT a FROM u) v ON 1 = 1",
+    vec!["unaliased-subquery"]
+);
+
+test_lint_builder!(
+    limit_without_order_by,
+    "SELECT a FROM t WHERE b IN (SELECT b FROM u LIMIT 1) ORDER BY a LIMIT 10",
+    vec!["limit-without-order-by"]
+);
+
+test_lint_builder!(
+    null_comparison,
+    "UPDATE t SET a = NULL WHERE b = NULL OR NULL != c",
+    vec!["null-comparison", "null-comparison"]
+);
+
+test_lint_builder!(
+    diagnostics_in_source_order,
+    "SELECT * FROM t WHERE a = NULL AND b NOT IN (SELECT b FROM u LIMIT 1)",
+    vec![
+        "select-star",
+        "null-comparison",
+        "not-in-subquery",
+        "limit-without-order-by"
+    ]
+);
+
+#[test]
+fn diagnostic_location_and_severity() {
+    let statement = parse("SELECT a FROM t WHERE b = NULL").unwrap();
+    let diagnostics = lint(&statement);
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].severity, Severity::Error);
+    assert_eq!(diagnostics[0].span, Some(Span::new(22, 23)));
+    assert_eq!(
+        diagnostics[0].to_string(),
+        "error: comparing with = NULL is never true, use IS NULL [null-comparison]"
+    );
+}
+
+#[test]
+fn without_rule() {
+    let statement = parse("SELECT * FROM t, u").unwrap();
+    let linter = Linter::new().without_rule("select-star").unwrap();
+
+    let rules: Vec<String> = linter
+        .lint(&statement)
+        .into_iter()
+        .map(|diagnostic| diagnostic.rule)
+        .collect();
+
+    assert_eq!(rules, vec!["implicit-cross-join"]);
+    assert!(Linter::new().without_rule("no-such-rule").is_err());
+}
+
+/// A custom rule reporting the string literals
+struct StringLiterals;
+
+struct StringLiteralCollector {
+    violations: Vec<Violation>,
+}
+
+impl<'ast> Visitor<'ast> for StringLiteralCollector {
+    fn visit_expression(&mut self, node: &'ast Expression) {
+        if let Expression::Literal(Literal::String(string)) = node {
+            let message = format!("string literal '{}'", string);
+            self.violations.push(Violation::new(&message, None));
+        }
+
+        visit::walk_expression(self, node);
+    }
+}
+
+impl Rule for StringLiterals {
+    fn name(&self) -> &'static str {
+        "string-literals"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Info
+    }
+
+    fn check(&self, statement: &SqlStatement) -> Vec<Violation> {
+        let mut collector = StringLiteralCollector {
+            violations: Vec::new(),
+        };
+        collector.visit_sql_statement(statement);
+        collector.violations
+    }
+}
+
+#[test]
+fn custom_rule() {
+    let statement = parse("SELECT 'x' FROM t LIMIT 1").unwrap();
+    let linter = Linter::empty().with_rule(StringLiterals);
+
+    let diagnostics = linter.lint(&statement);
+
+    assert_eq!(linter.rule_names(), vec!["string-literals"]);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].rule, "string-literals");
+    assert_eq!(diagnostics[0].severity, Severity::Info);
+    assert_eq!(diagnostics[0].message, "string literal 'x'");
+    assert_eq!(diagnostics[0].span, None);
+}
@@ -0,0 +1,36 @@
+import pytest
+
+from hephaestus import parameters, substitute
+
+
+def test_parameters():
+    assert parameters("SELECT a FROM t WHERE b LIKE :pattern AND c = ${c} LIMIT ?") == [
+        {"parameter": {"_type": "NamedParameter", "name": "pattern"}, "data_type": "VARCHAR"},
+        {"parameter": {"_type": "VariableParameter", "name": "c"}, "data_type": "GENERIC"},
+        {"parameter": {"_type": "PositionalParameter", "index": 1}, "data_type": "NUMERIC"},
+    ]
+
+
+def test_substitute():
+    assert (
+        substitute("SELECT a FROM t WHERE b = ? AND c = :c AND d = ?", 1.5, None, c="it's")
+        == "SELECT a FROM t WHERE b = 1.5 AND c = 'it''s' AND d = NULL"
+    )
+
+
+def test_substitute_literal():
+    assert (
+        substitute("SELECT a FROM t WHERE b = ?", {"_type": "DateLiteral", "date": "2020-01-01"})
+        == "SELECT a FROM t WHERE b = DATE '2020-01-01'"
+    )
+
+
+def test_substitute_missing_value():
+    with pytest.raises(ValueError, match="no value for the parameter :c"):
+        substitute("SELECT a FROM t WHERE c = :c")
+
+
+@pytest.mark.parametrize("value", [True, float("nan")])
+def test_substitute_invalid_value(value):
+    with pytest.raises(ValueError, match="can't be bound to a parameter"):
+        substitute("SELECT a FROM t WHERE b = ?", value)
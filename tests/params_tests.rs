@@ -0,0 +1,306 @@
+// MIT License
+//
+// Copyright (c) 2019 Daniele Esposti
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::HashMap;
+
+use hephaestus::ast::{Literal, Parameter};
+use hephaestus::params::{parameters, parameters_with_column_types, substitute};
+use hephaestus::parse;
+use hephaestus::symbols::{Name, Span};
+use hephaestus::types::DataType;
+use hephaestus::visit::Visitor;
+
+macro_rules! test_parameters_builder {
+    ($name:ident, $sql:expr, $expected:expr) => {
+        #[test]
+        fn $name() {
+            let statement = parse($sql).unwrap();
+            let expected: Vec<(Parameter, DataType)> = $expected;
+
+            let parameters: Vec<(Parameter, DataType)> = parameters(&statement)
+                .into_iter()
+                .map(|parameter| (parameter.parameter, parameter.data_type))
+                .collect();
+
+            assert_eq!(parameters, expected);
+        }
+    };
+}
+
+macro_rules! test_substitute_builder {
+    ($name:ident, $sql:expr, $positional:expr, $named:expr, $expected:expr) => {
+        #[test]
+        fn $name() {
+            let statement = parse($sql).unwrap();
+            let positional: Vec<Literal> = $positional;
+            let named: HashMap<String, Literal> = $named
+                .into_iter()
+                .map(|(name, value): (&str, Literal)| (name.to_string(), value))
+                .collect();
+
+            let result = substitute(&statement, &positional, &named).map(|s| s.to_string());
+
+            assert_eq!(result, $expected);
+        }
+    };
+}
+
+fn positional(index: usize) -> Parameter {
+    Parameter::Positional(index)
+}
+
+fn named(name: &str) -> Parameter {
+    Parameter::Named(Name::new(name.to_string()))
+}
+
+fn variable(name: &str) -> Parameter {
+    Parameter::Variable(Name::new(name.to_string()))
+}
+
+fn number(value: &str) -> Literal {
+    Literal::Numeric(value.to_string())
+}
+
+fn string(value: &str) -> Literal {
+    Literal::String(value.to_string())
+}
+
+#[derive(Default)]
+struct Parameters {
+    parameters: Vec<Parameter>,
+}
+
+impl<'ast> Visitor<'ast> for Parameters {
+    fn visit_parameter(&mut self, node: &'ast Parameter) {
+        self.parameters.push(node.clone());
+    }
+}
+
+#[test]
+fn number_positional_parameters() {
+    let statement = parse("SELECT ? FROM t WHERE a = ? AND b = :b LIMIT ?").unwrap();
+    let mut collector = Parameters::default();
+
+    collector.visit_sql_statement(&statement);
+
+    assert_eq!(
+        collector.parameters,
+        vec![positional(1), positional(2), named("b"), positional(3)]
+    );
+}
+
+#[test]
+fn parameter_spans() {
+    let statement = parse("SELECT a FROM t WHERE a = :abc OR b = ${xyz}").unwrap();
+    let mut collector = Parameters::default();
+
+    collector.visit_sql_statement(&statement);
+
+    let spans: Vec<Option<Span>> = collector
+        .parameters
+        .iter()
+        .map(|parameter| match parameter {
+            Parameter::Named(name) | Parameter::Variable(name) => name.span(),
+            Parameter::Positional(_) => None,
+        })
+        .collect();
+
+    assert_eq!(
+        spans,
+        vec![
+            Some(Span { start: 27, end: 30 }),
+            Some(Span { start: 40, end: 43 })
+        ]
+    );
+}
+
+#[test]
+fn print_parameters() {
+    let statement = parse("select a from t where b = :b and c = ${c} and d in (?, ?)").unwrap();
+
+    assert_eq!(
+        statement.to_string(),
+        "SELECT a FROM t WHERE b = :b AND c = ${c} AND d IN (?, ?)"
+    );
+}
+
+#[test]
+fn parameter_keyword_name() {
+    assert!(parse("SELECT a FROM t WHERE b = :date").is_ok());
+}
+
+#[test]
+fn invalid_parameters() {
+    assert!(parse("SELECT a FROM t WHERE b = :1").is_err());
+    assert!(parse("SELECT a FROM t WHERE b = ${}").is_err());
+}
+
+test_substitute_builder!(
+    substitute_positional,
+    "SELECT a FROM t WHERE b = ? AND c = ?",
+    vec![number("1"), string("x")],
+    vec![],
+    Ok("SELECT a FROM t WHERE b = 1 AND c = 'x'".to_string())
+);
+
+test_substitute_builder!(
+    substitute_named,
+    "SELECT a FROM t WHERE b = :Value OR c = :value OR d = ${table_name}",
+    vec![],
+    vec![("value", number("2")), ("TABLE_NAME", string("t"))],
+    Ok("SELECT a FROM t WHERE b = 2 OR c = 2 OR d = 't'".to_string())
+);
+
+test_substitute_builder!(
+    substitute_null,
+    "UPDATE t SET a = ? WHERE b = 1",
+    vec![Literal::Null],
+    vec![],
+    Ok("UPDATE t SET a = NULL WHERE b = 1".to_string())
+);
+
+test_substitute_builder!(
+    substitute_escapes_strings,
+    "SELECT a FROM t WHERE b = :b",
+    vec![],
+    vec![("b", string("it's"))],
+    Ok("SELECT a FROM t WHERE b = 'it''s'".to_string())
+);
+
+test_substitute_builder!(
+    substitute_missing_positional,
+    "SELECT a FROM t WHERE b = ? AND c = ?",
+    vec![number("1")],
+    vec![],
+    Err("no value for the positional parameter 2".to_string())
+);
+
+test_substitute_builder!(
+    substitute_missing_named,
+    "SELECT a FROM t WHERE b = :b",
+    vec![],
+    vec![("c", number("1"))],
+    Err("no value for the parameter :b".to_string())
+);
+
+test_parameters_builder!(no_parameters, "SELECT a FROM t WHERE b = 1", vec![]);
+
+test_parameters_builder!(
+    comparison_with_literal,
+    "SELECT a FROM t WHERE ? = 'x' AND :n > 1",
+    vec![
+        (positional(1), DataType::Varchar),
+        (named("n"), DataType::Numeric)
+    ]
+);
+
+test_parameters_builder!(
+    comparison_with_column,
+    "SELECT a FROM t WHERE b = ?",
+    vec![(positional(1), DataType::Generic)]
+);
+
+test_parameters_builder!(
+    repeated_parameter,
+    "SELECT a FROM t WHERE b = :x OR c LIKE :x",
+    vec![(named("x"), DataType::Varchar)]
+);
+
+test_parameters_builder!(
+    arithmetic,
+    "SELECT a * ? FROM t WHERE b || ${suffix} = 'x'",
+    vec![
+        (positional(1), DataType::Numeric),
+        (variable("suffix"), DataType::Varchar)
+    ]
+);
+
+test_parameters_builder!(
+    between_and_in_list,
+    "SELECT a FROM t WHERE a BETWEEN ? AND DATE '2020-01-01' AND b IN (1, :b)",
+    vec![
+        (positional(1), DataType::Date),
+        (named("b"), DataType::Numeric)
+    ]
+);
+
+test_parameters_builder!(
+    limit_and_offset,
+    "SELECT a FROM t ORDER BY a LIMIT ? OFFSET ?",
+    vec![
+        (positional(1), DataType::Numeric),
+        (positional(2), DataType::Numeric)
+    ]
+);
+
+test_parameters_builder!(
+    boolean_operands,
+    "SELECT a FROM t WHERE :flag AND b = 1",
+    vec![(named("flag"), DataType::Logical)]
+);
+
+#[test]
+fn parameters_with_columns() {
+    let statement =
+        parse("SELECT a FROM t WHERE b = ? AND c > :c AND d = 1 UNION SELECT 1").unwrap();
+
+    let parameters: Vec<(Parameter, DataType)> =
+        parameters_with_column_types(&statement, |name| match name.last()?.as_str() {
+            "b" => Some(DataType::Timestamp),
+            "c" => Some(DataType::Varchar),
+            _ => None,
+        })
+        .into_iter()
+        .map(|parameter| (parameter.parameter, parameter.data_type))
+        .collect();
+
+    assert_eq!(
+        parameters,
+        vec![
+            (positional(1), DataType::Timestamp),
+            (named("c"), DataType::Varchar)
+        ]
+    );
+}
+
+#[test]
+fn parameters_of_insert() {
+    let statement = parse("INSERT INTO t (a, b) VALUES (?, :b)").unwrap();
+
+    let parameters: Vec<(Parameter, DataType)> =
+        parameters_with_column_types(&statement, |name| match name.last()?.as_str() {
+            "a" => Some(DataType::Numeric),
+            "b" => Some(DataType::Date),
+            _ => None,
+        })
+        .into_iter()
+        .map(|parameter| (parameter.parameter, parameter.data_type))
+        .collect();
+
+    assert_eq!(
+        parameters,
+        vec![
+            (positional(1), DataType::Numeric),
+            (named("b"), DataType::Date)
+        ]
+    );
+}
@@ -0,0 +1,37 @@
+from hephaestus import predicates
+
+
+def test_predicates():
+    assert predicates("SELECT a FROM t WHERE 1 < a AND (b = 'x' OR b = 'y') AND c IS NULL") == [
+        {
+            "column": ["a"],
+            "operator": ">",
+            "value": {"_type": "SingleValue", "value": {"_type": "NumericLiteral", "num": "1"}},
+            "clause": "where",
+        },
+        {
+            "column": ["b"],
+            "operator": "IN",
+            "value": {
+                "_type": "ListValue",
+                "values": [
+                    {"_type": "StringLiteral", "str": "x"},
+                    {"_type": "StringLiteral", "str": "y"},
+                ],
+            },
+            "clause": "where",
+        },
+        {"column": ["c"], "operator": "IS NULL", "value": None, "clause": "where"},
+    ]
+
+
+def test_predicates_range():
+    (predicate,) = predicates("SELECT a FROM t JOIN u ON t.b = u.c AND u.d BETWEEN 1 AND 2")
+
+    assert predicate["column"] == ["u", "d"]
+    assert predicate["value"] == {
+        "_type": "RangeValue",
+        "lower": {"_type": "NumericLiteral", "num": "1"},
+        "upper": {"_type": "NumericLiteral", "num": "2"},
+    }
+    assert predicate["clause"] == "join"
@@ -0,0 +1,157 @@
+// MIT License
+//
+// Copyright (c) 2019 Daniele Esposti
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use hephaestus::parse;
+use hephaestus::predicates::{conjuncts, predicates, Predicate, Value};
+use hephaestus::sql::ExpressionParser;
+
+macro_rules! test_predicates_builder {
+    ($name:ident, $sql:expr, $expected:expr) => {
+        #[test]
+        fn $name() {
+            let result: Vec<String> = predicates(&parse($sql).unwrap())
+                .iter()
+                .map(render)
+                .collect();
+            let expected: Vec<&str> = $expected;
+
+            assert_eq!(result, expected);
+        }
+    };
+}
+
+fn render(predicate: &Predicate) -> String {
+    let column: Vec<String> = predicate
+        .column
+        .iter()
+        .map(|name| name.to_string())
+        .collect();
+    let value = match &predicate.value {
+        Some(Value::Single(value)) => format!(" {}", value),
+        Some(Value::List(values)) => {
+            let values: Vec<String> = values.iter().map(|value| value.to_string()).collect();
+            format!(" ({})", values.join(", "))
+        }
+        Some(Value::Range { lower, upper }) => format!(" {} AND {}", lower, upper),
+        None => "".to_string(),
+    };
+
+    format!(
+        "{}: {} {}{}",
+        predicate.clause,
+        column.join("."),
+        predicate.operator,
+        value
+    )
+}
+
+test_predicates_builder!(
+    comparisons,
+    "SELECT a FROM t WHERE a = 1 AND t.b >= DATE '2020-01-01' AND c LIKE 'x%' AND d != :d",
+    vec![
+        "where: a = 1",
+        "where: t.b >= DATE '2020-01-01'",
+        "where: c LIKE 'x%'",
+        "where: d != :d"
+    ]
+);
+
+test_predicates_builder!(
+    either_operand_order,
+    "SELECT a FROM t WHERE 1 < a AND CURRENT_DATE - 7 <= b AND 'x' = c",
+    vec![
+        "where: a > 1",
+        "where: b >= CURRENT_DATE - 7",
+        "where: c = 'x'"
+    ]
+);
+
+test_predicates_builder!(
+    ranges_lists_and_nulls,
+    "SELECT a FROM t WHERE a BETWEEN 1 AND 10 AND b IN (1, 2) AND c IS NULL AND d NOT IN ('x')",
+    vec![
+        "where: a BETWEEN 1 AND 10",
+        "where: b IN (1, 2)",
+        "where: c IS NULL",
+        "where: d NOT IN ('x')"
+    ]
+);
+
+test_predicates_builder!(
+    negations,
+    "SELECT a FROM t WHERE NOT (a > 1 OR b IS NULL) AND NOT c BETWEEN 1 AND 2 AND NOT d LIKE 'x'",
+    vec![
+        "where: a <= 1",
+        "where: b IS NOT NULL",
+        "where: c NOT BETWEEN 1 AND 2",
+        "where: d NOT LIKE 'x'"
+    ]
+);
+
+test_predicates_builder!(
+    disjunction_of_equalities,
+    "SELECT a FROM t WHERE (a = 1 OR a = 2 OR a IN (3, 4)) AND (b = 1 OR c = 2)",
+    vec!["where: a IN (1, 2, 3, 4)"]
+);
+
+test_predicates_builder!(
+    distributed_disjunction,
+    "SELECT a FROM t WHERE a = 1 OR (b = 2 AND a = 3)",
+    vec!["where: a IN (1, 3)"]
+);
+
+test_predicates_builder!(
+    not_filters,
+    "SELECT a FROM t WHERE a = b AND a IN (SELECT c FROM u) AND f(a) = 1 AND a + 1 = 2",
+    vec![]
+);
+
+test_predicates_builder!(
+    join_having_and_nested_queries,
+    "SELECT a, SUM(b) FROM t JOIN u ON t.id = u.id AND u.kind = 'x' WHERE a IN (SELECT c FROM v WHERE d > 0) GROUP BY a HAVING a != 0",
+    vec!["join: u.kind = 'x'", "where: d > 0", "having: a != 0"]
+);
+
+test_predicates_builder!(
+    delete_statement,
+    "DELETE FROM t WHERE a = 1",
+    vec!["where: a = 1"]
+);
+
+test_predicates_builder!(
+    update_statement,
+    "UPDATE t SET a = 1 WHERE b IS NOT NULL",
+    vec!["where: b IS NOT NULL"]
+);
+
+#[test]
+fn conjunctive_normal_form() {
+    let expr = ExpressionParser::new()
+        .parse("NOT (a = 1 AND b < 2) OR (c = 3 AND d = 4)")
+        .unwrap();
+    let result: Vec<String> = conjuncts(&expr).iter().map(|e| e.to_string()).collect();
+
+    assert_eq!(
+        result,
+        vec!["a != 1 OR b >= 2 OR c = 3", "a != 1 OR b >= 2 OR d = 4"]
+    );
+}
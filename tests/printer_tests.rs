@@ -0,0 +1,146 @@
+// MIT License
+//
+// Copyright (c) 2019 Daniele Esposti
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use hephaestus::parse;
+
+macro_rules! test_print_builder {
+    ($name:ident, $sql:expr, $expected:expr) => {
+        #[test]
+        fn $name() {
+            let statement = parse($sql).unwrap();
+            let printed = statement.to_string();
+
+            assert_eq!(printed, $expected);
+            assert_eq!(parse(&printed).unwrap(), statement);
+        }
+    };
+}
+
+test_print_builder!(
+    print_select,
+    "select distinct a, b as c, t.* from s.t x where a>1 order by b desc, a limit 10, 5",
+    "SELECT DISTINCT a, b AS c, t.* FROM s.t AS x WHERE a > 1 ORDER BY b DESC, a LIMIT 10 OFFSET 5"
+);
+
+test_print_builder!(
+    print_ctes_and_set_operations,
+    "WITH a (x) AS (SELECT 1) SELECT x FROM a UNION ALL SELECT 2 EXCEPT VALUES (3)",
+    "WITH a (x) AS (SELECT 1) SELECT x FROM a UNION ALL SELECT 2 EXCEPT VALUES (3)"
+);
+
+test_print_builder!(
+    print_recursive_ctes,
+    "with recursive n(x) as (select 1 union all select x+1 from n where x<3) select x from n",
+    "WITH RECURSIVE n (x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM n WHERE x < 3) SELECT x FROM n"
+);
+
+test_print_builder!(
+    print_joins,
+    "SELECT * FROM a LEFT OUTER JOIN (b NATURAL JOIN c ON 1 = 1) ON a.x = b.x \
+     CROSS JOIN (SELECT 1) d ON TRUE_ = 1 JOIN e USING (y, z)",
+    "SELECT * FROM a LEFT JOIN (b NATURAL JOIN c ON 1 = 1) ON a.x = b.x \
+     CROSS JOIN (SELECT 1) AS d ON TRUE_ = 1 JOIN e USING (y, z)"
+);
+
+test_print_builder!(
+    print_precedence,
+    "SELECT (a + b) * c, a - (b - c), -(-a), (a OR b) AND NOT (c AND d), NOT a = b",
+    "SELECT (a + b) * c, a - (b - c), -(-a), (a OR b) AND NOT (c AND d), NOT a = b"
+);
+
+test_print_builder!(
+    print_predicates,
+    "SELECT 1 WHERE a NOT IN (1, 2) AND b IS NOT NULL AND c NOTNULL AND d ISNULL \
+     AND e NOT BETWEEN 1 AND 2 + 3 AND f IN (SELECT 1) AND g LIKE 'x%'",
+    "SELECT 1 WHERE a NOT IN (1, 2) AND b IS NOT NULL AND c IS NOT NULL AND d IS NULL \
+     AND e NOT BETWEEN 1 AND 2 + 3 AND f IN (SELECT 1) AND g LIKE 'x%'"
+);
+
+test_print_builder!(
+    print_functions,
+    "SELECT COUNT(DISTINCT *), SUM(a) * 2, MAX(b), MIN(DISTINCT c), COALESCE(a, b), \
+     REPLACE(a, 'b'), SUBSTR(a, 1, 2), TO_DATE(a, 'YYYY'), POWER(a, 2), CONCAT(a, b), \
+     CAST(a AS DECIMAL(10, 2)), RIGHT(a, 1), s.f(a, 1) FROM t WHERE 1 = COUNT(*)",
+    "SELECT COUNT(DISTINCT *), SUM(a) * 2, MAX(b), MIN(DISTINCT c), COALESCE(a, b), \
+     REPLACE(a, 'b'), SUBSTRING(a FROM 1 FOR 2), TO_DATE(a, 'YYYY'), POWER(a, 2), CONCAT(a, b), \
+     CAST(a AS DECIMAL(10, 2)), RIGHT(a, 1), s.f(a, 1) FROM t WHERE 1 = COUNT(*)"
+);
+
+test_print_builder!(
+    print_function_operands,
+    "SELECT (COUNT(*)) IS NULL, a < (MAX(b)), -(SUM(c))",
+    "SELECT (COUNT(*)) IS NULL, a < (MAX(b)), -(SUM(c))"
+);
+
+test_print_builder!(
+    print_case,
+    "SELECT CASE a WHEN 1 THEN 'one' ELSE 'many' END, a = CASE WHEN b THEN 1 END, \
+     (CASE WHEN b THEN 1 END) + 1",
+    "SELECT CASE a WHEN 1 THEN 'one' ELSE 'many' END, a = CASE WHEN b THEN 1 END, \
+     (CASE WHEN b THEN 1 END) + 1"
+);
+
+test_print_builder!(
+    print_literals,
+    "SELECT NULL, 1.5, .5E3, 0xFF, CURRENT_DATE, CURRENT_TIME, CURRENT_TIMESTAMP, \
+     DATE '2019-01-01', TIME '10:00:00', TIMESTAMP '2019-01-01 10:00:00', (1, 'a')",
+    "SELECT NULL, 1.5, .5E3, 0xFF, CURRENT_DATE, CURRENT_TIME, CURRENT_TIMESTAMP, \
+     DATE '2019-01-01', TIME '10:00:00', TIMESTAMP '2019-01-01 10:00:00', (1, 'a')"
+);
+
+test_print_builder!(
+    print_group_by,
+    "SELECT a, SUM(b) FROM t GROUP BY a HAVING (SUM(b)) > 1 ORDER BY a COLLATE utf8",
+    "SELECT a, SUM(b) FROM t GROUP BY a HAVING (SUM(b)) > 1 ORDER BY a COLLATE utf8"
+);
+
+test_print_builder!(
+    print_insert,
+    "INSERT INTO s.t (a, b) SELECT 1, 2",
+    "INSERT INTO s.t (a, b) SELECT 1, 2"
+);
+
+test_print_builder!(
+    print_update,
+    "UPDATE t SET a = 1, b = b + 1 WHERE c = 'x'",
+    "UPDATE t SET a = 1, b = b + 1 WHERE c = 'x'"
+);
+
+test_print_builder!(
+    print_delete,
+    "DELETE FROM s.t WHERE a IN s.u",
+    "DELETE FROM s.t WHERE a IN s.u"
+);
+
+test_print_builder!(
+    print_explain,
+    "EXPLAIN SELECT CAST(a AS TIMESTAMP WITH LOCAL TIME ZONE)",
+    "EXPLAIN QUERY PLAN SELECT CAST(a AS TIMESTAMP WITH LOCAL TIME ZONE)"
+);
+
+test_print_builder!(
+    print_attach,
+    "ATTACH FILE 'data.csv' AS s.t",
+    "ATTACH FILE 'data.csv' AS s.t"
+);
+
+test_print_builder!(print_describe, "DESCRIBE s.t", "DESCRIBE s.t");
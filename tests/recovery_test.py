@@ -0,0 +1,27 @@
+from hephaestus import parse, parse_lenient, to_sql
+
+
+def test_parse_lenient():
+    result = parse_lenient("SELECT a, + FROM t WHERE a = 1 AND b = = 2")
+
+    assert to_sql(result["statement"]) == "SELECT a, + FROM t WHERE a = 1 AND b = = 2"
+    assert [
+        (error["token"], error["location"], error["text"], error["span"])
+        for error in result["errors"]
+    ] == [("FROM", 12, "+", (10, 11)), ("=", 39, "b = = 2", (35, 42))]
+    assert result["errors"][0]["message"].startswith("Unrecognized token `FROM` found at 12:16")
+
+
+def test_parse_lenient_valid_statement():
+    assert parse_lenient("SELECT a FROM t") == {
+        "_type": "LenientParse",
+        "statement": parse("SELECT a FROM t"),
+        "errors": [],
+    }
+
+
+def test_parse_lenient_unrecoverable_error():
+    result = parse_lenient("SELECT a FROM t WHERE a = ~")
+
+    assert result["statement"] is None
+    assert result["errors"][0]["message"] == "Invalid token at 26"
@@ -0,0 +1,183 @@
+// MIT License
+//
+// Copyright (c) 2019 Daniele Esposti
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use hephaestus::ast::{
+    ExprResultColumn, Expression, QuerySetExpression, ResultColumn, ResultColumns, SelectStatement,
+    SetExpression, SqlStatement, Statement, TableExpression,
+};
+use hephaestus::recovery::{parse_lenient, parse_strict};
+use hephaestus::symbols::Span;
+
+macro_rules! test_recovery_builder {
+    ($name:ident, $sql:expr, $expected_sql:expr, $expected_errors:expr) => {
+        #[test]
+        fn $name() {
+            let result = parse_lenient($sql);
+            let expected_errors: Vec<&str> = $expected_errors;
+
+            let errors: Vec<&str> = result
+                .errors
+                .iter()
+                .map(|error| error.text.as_str())
+                .collect();
+
+            assert_eq!(result.statement.unwrap().to_string(), $expected_sql);
+            assert_eq!(errors, expected_errors);
+            assert!(parse_strict($sql).is_err());
+        }
+    };
+}
+
+test_recovery_builder!(
+    where_conjunct,
+    "SELECT a FROM t WHERE a = 1 AND b = = 2 AND c = 3",
+    "SELECT a FROM t WHERE a = 1 AND b = = 2 AND c = 3",
+    vec!["b = = 2"]
+);
+
+test_recovery_builder!(
+    where_disjunct,
+    "SELECT a FROM t WHERE a = 1 AND (b = 2 OR c = ) AND d = 4",
+    "SELECT a FROM t WHERE a = 1 AND (b = 2 OR c =) AND d = 4",
+    vec!["c ="]
+);
+
+test_recovery_builder!(
+    select_list_item,
+    "SELECT a, +, b FROM t",
+    "SELECT a, +, b FROM t",
+    vec!["+"]
+);
+
+test_recovery_builder!(
+    from_entry,
+    "SELECT a FROM t, 1, u WHERE a = 1",
+    "SELECT a FROM t, 1, u WHERE a = 1",
+    vec!["1"]
+);
+
+test_recovery_builder!(
+    many_errors,
+    "SELECT a, * FROM t, 1 WHERE a = = 1",
+    "SELECT a, * FROM t, 1 WHERE a = = 1",
+    vec!["*", "1", "a = = 1"]
+);
+
+test_recovery_builder!(
+    truncated_statement,
+    "SELECT a FROM t WHERE a = 1 AND",
+    "SELECT a FROM t WHERE a = 1 AND ",
+    vec![""]
+);
+
+test_recovery_builder!(
+    statement,
+    "SELEC a FROM t",
+    "SELEC a FROM t",
+    vec!["SELEC a FROM t"]
+);
+
+#[test]
+fn valid_statement() {
+    let sql = "SELECT a, b FROM t JOIN u ON t.a = u.a WHERE a = 1";
+    let result = parse_lenient(sql);
+
+    assert!(result.errors.is_empty());
+    assert_eq!(result.statement, Some(parse_strict(sql).unwrap()));
+}
+
+#[test]
+fn error_node() {
+    let result = parse_lenient("SELECT a FROM t WHERE a = 1 AND b = = 2");
+    let error = &result.errors[0];
+
+    assert_eq!(result.errors.len(), 1);
+    assert!(error
+        .message
+        .starts_with("Unrecognized token `=` found at 36:37\nExpected one of"));
+    assert_eq!(error.token, Some("=".to_string()));
+    assert_eq!(error.location, 36);
+    assert_eq!(error.text, "b = = 2");
+    assert_eq!(error.span, Span { start: 32, end: 39 });
+}
+
+fn query(sql: &str) -> QuerySetExpression {
+    match parse_lenient(sql).statement {
+        Some(SqlStatement::Statement(Statement::Select(SelectStatement { expr, .. }))) => {
+            match *expr {
+                SetExpression::Query(query) => query,
+                expr => panic!("unexpected expression {:?}", expr),
+            }
+        }
+        statement => panic!("unexpected statement {:?}", statement),
+    }
+}
+
+#[test]
+fn error_placeholders() {
+    match query("SELECT a, * FROM t").columns {
+        ResultColumns::List(columns) => match &columns[1] {
+            ResultColumn::Expr(ExprResultColumn {
+                expr: Expression::Error(error),
+                rename: None,
+            }) => assert_eq!(error.text, "*"),
+            column => panic!("unexpected column {:?}", column),
+        },
+        columns => panic!("unexpected columns {:?}", columns),
+    }
+
+    match &query("SELECT 1 FROM").from[0] {
+        TableExpression::Error(error) => assert_eq!(error.span, Span { start: 13, end: 13 }),
+        table => panic!("unexpected table {:?}", table),
+    }
+
+    match query("SELECT a FROM t WHERE").where_expr {
+        Some(Expression::Error(error)) => assert_eq!(error.token, None),
+        expr => panic!("unexpected expression {:?}", expr),
+    }
+
+    match parse_lenient("DROP TABLE t").statement {
+        Some(SqlStatement::Error(error)) => assert_eq!(error.text, "DROP TABLE t"),
+        statement => panic!("unexpected statement {:?}", statement),
+    }
+}
+
+#[test]
+fn unrecoverable_error() {
+    let result = parse_lenient("SELECT a FROM t WHERE a = 'é' ~ 1");
+
+    assert_eq!(result.statement, None);
+    assert_eq!(result.errors.len(), 1);
+    assert_eq!(result.errors[0].message, "Invalid token at 31");
+    assert_eq!(result.errors[0].token, None);
+    assert_eq!(result.errors[0].text, "~ 1");
+}
+
+#[test]
+fn strict_error_message() {
+    assert!(parse_strict("SELECT a FROM t WHERE a = = 1")
+        .unwrap_err()
+        .starts_with("Unrecognized token `=` found at 26:27"));
+    assert!(parse_strict("SELECT a FROM t WHERE")
+        .unwrap_err()
+        .starts_with("Unrecognized EOF found at 21"));
+}
@@ -32,6 +32,7 @@ test_builder!(
     "select 1",
     SqlStatement::Statement(Statement::Select(SelectStatement {
         common: vec![],
+        recursive: false,
         expr: Box::new(SetExpression::Query(QuerySetExpression {
             mode: SelectMode::All,
             columns: ResultColumns::List(vec![ResultColumn::Expr(ExprResultColumn {
@@ -52,6 +53,7 @@ test_builder!(
     "select 1 from dual",
     SqlStatement::Statement(Statement::Select(SelectStatement {
         common: vec![],
+        recursive: false,
         expr: Box::new(SetExpression::Query(QuerySetExpression {
             mode: SelectMode::All,
             columns: ResultColumns::List(vec![ResultColumn::Expr(ExprResultColumn {
@@ -75,6 +77,7 @@ test_builder!(
     "select *",
     SqlStatement::Statement(Statement::Select(SelectStatement {
         common: vec![],
+        recursive: false,
         expr: Box::new(SetExpression::Query(QuerySetExpression {
             mode: SelectMode::All,
             columns: ResultColumns::All,
@@ -92,6 +95,7 @@ test_builder!(
     "select a, b from dual",
     SqlStatement::Statement(Statement::Select(SelectStatement {
         common: vec![],
+        recursive: false,
         expr: Box::new(SetExpression::Query(QuerySetExpression {
             mode: SelectMode::All,
             columns: ResultColumns::List(vec![
@@ -125,6 +129,7 @@ test_builder!(
     "select a.b",
     SqlStatement::Statement(Statement::Select(SelectStatement {
         common: vec![],
+        recursive: false,
         expr: Box::new(SetExpression::Query(QuerySetExpression {
             mode: SelectMode::All,
             columns: ResultColumns::List(vec![ResultColumn::Expr(ExprResultColumn {
@@ -150,6 +155,7 @@ test_builder!(
     "select a as b",
     SqlStatement::Statement(Statement::Select(SelectStatement {
         common: vec![],
+        recursive: false,
         expr: Box::new(SetExpression::Query(QuerySetExpression {
             mode: SelectMode::All,
             columns: ResultColumns::List(vec![ResultColumn::Expr(ExprResultColumn {
@@ -172,6 +178,7 @@ test_builder!(
     "select a.b as c",
     SqlStatement::Statement(Statement::Select(SelectStatement {
         common: vec![],
+        recursive: false,
         expr: Box::new(SetExpression::Query(QuerySetExpression {
             mode: SelectMode::All,
             columns: ResultColumns::List(vec![ResultColumn::Expr(ExprResultColumn {
@@ -197,6 +204,7 @@ test_builder!(
     "SELECT a || b",
     SqlStatement::Statement(Statement::Select(SelectStatement {
         common: vec![],
+        recursive: false,
         expr: Box::new(SetExpression::Query(QuerySetExpression {
             mode: SelectMode::All,
             columns: ResultColumns::List(vec![ResultColumn::Expr(ExprResultColumn {
@@ -229,6 +237,7 @@ test_builder!(
     "SELECT COALESCE(1) || b",
     SqlStatement::Statement(Statement::Select(SelectStatement {
         common: vec![],
+        recursive: false,
         expr: Box::new(SetExpression::Query(QuerySetExpression {
             mode: SelectMode::All,
             columns: ResultColumns::List(vec![ResultColumn::Expr(ExprResultColumn {
@@ -259,6 +268,7 @@ test_builder!(
     "SELECT b || COALESCE(1)",
     SqlStatement::Statement(Statement::Select(SelectStatement {
         common: vec![],
+        recursive: false,
         expr: Box::new(SetExpression::Query(QuerySetExpression {
             mode: SelectMode::All,
             columns: ResultColumns::List(vec![ResultColumn::Expr(ExprResultColumn {
@@ -289,6 +299,7 @@ test_builder!(
     "SELECT COALESCE(1) || COALESCE(2)",
     SqlStatement::Statement(Statement::Select(SelectStatement {
         common: vec![],
+        recursive: false,
         expr: Box::new(SetExpression::Query(QuerySetExpression {
             mode: SelectMode::All,
             columns: ResultColumns::List(vec![ResultColumn::Expr(ExprResultColumn {
@@ -317,6 +328,7 @@ test_builder!(
     "SELECT COALESCE(1) || 'a' || COALESCE(2)",
     SqlStatement::Statement(Statement::Select(SelectStatement {
         common: vec![],
+        recursive: false,
         expr: Box::new(SetExpression::Query(QuerySetExpression {
             mode: SelectMode::All,
             columns: ResultColumns::List(vec![ResultColumn::Expr(ExprResultColumn {
@@ -349,6 +361,7 @@ test_builder!(
     "SELECT COALESCE(POWER(1, 2), 3)",
     SqlStatement::Statement(Statement::Select(SelectStatement {
         common: vec![],
+        recursive: false,
         expr: Box::new(SetExpression::Query(QuerySetExpression {
             mode: SelectMode::All,
             columns: ResultColumns::List(vec![ResultColumn::Expr(ExprResultColumn {
@@ -379,6 +392,7 @@ test_builder!(
     "SELECT COALESCE(POWER(1, 2), 2) as a",
     SqlStatement::Statement(Statement::Select(SelectStatement {
         common: vec![],
+        recursive: false,
         expr: Box::new(SetExpression::Query(QuerySetExpression {
             mode: SelectMode::All,
             columns: ResultColumns::List(vec![ResultColumn::Expr(ExprResultColumn {
@@ -32,6 +32,7 @@ test_builder!(
     "select * from a.b",
     SqlStatement::Statement(Statement::Select(SelectStatement {
         common: vec![],
+        recursive: false,
         expr: Box::new(SetExpression::Query(QuerySetExpression {
             mode: SelectMode::All,
             columns: ResultColumns::All,
@@ -55,6 +56,7 @@ test_builder!(
     "select * from a as b",
     SqlStatement::Statement(Statement::Select(SelectStatement {
         common: vec![],
+        recursive: false,
         expr: Box::new(SetExpression::Query(QuerySetExpression {
             mode: SelectMode::All,
             columns: ResultColumns::All,
@@ -75,6 +77,7 @@ test_builder!(
     "select * from a.b as c",
     SqlStatement::Statement(Statement::Select(SelectStatement {
         common: vec![],
+        recursive: false,
         expr: Box::new(SetExpression::Query(QuerySetExpression {
             mode: SelectMode::All,
             columns: ResultColumns::All,
@@ -98,6 +101,7 @@ test_builder!(
     "select * from a.b c",
     SqlStatement::Statement(Statement::Select(SelectStatement {
         common: vec![],
+        recursive: false,
         expr: Box::new(SetExpression::Query(QuerySetExpression {
             mode: SelectMode::All,
             columns: ResultColumns::All,
@@ -32,6 +32,7 @@ test_builder!(
     "select * from a join b on a.id = b.id",
     SqlStatement::Statement(Statement::Select(SelectStatement {
         common: vec![],
+        recursive: false,
         expr: Box::new(SetExpression::Query(QuerySetExpression {
             mode: SelectMode::All,
             columns: ResultColumns::All,
@@ -78,6 +79,7 @@ test_builder!(
     "select * from a t1 join b t2 on t1.id = t2.id",
     SqlStatement::Statement(Statement::Select(SelectStatement {
         common: vec![],
+        recursive: false,
         expr: Box::new(SetExpression::Query(QuerySetExpression {
             mode: SelectMode::All,
             columns: ResultColumns::All,
@@ -124,6 +126,7 @@ test_builder!(
     "select * from a left join b on a.id = b.id",
     SqlStatement::Statement(Statement::Select(SelectStatement {
         common: vec![],
+        recursive: false,
         expr: Box::new(SetExpression::Query(QuerySetExpression {
             mode: SelectMode::All,
             columns: ResultColumns::All,
@@ -170,6 +173,7 @@ test_builder!(
     "select * from a join b on a.f1 = b.f1 and a.f2 = b.f2",
     SqlStatement::Statement(Statement::Select(SelectStatement {
         common: vec![],
+        recursive: false,
         expr: Box::new(SetExpression::Query(QuerySetExpression {
             mode: SelectMode::All,
             columns: ResultColumns::All,
@@ -0,0 +1,15 @@
+import pytest
+
+from hephaestus import simplify
+
+
+def test_simplify():
+    assert (
+        simplify("SELECT 0.1 + 0.2, COALESCE(a) FROM t WHERE a = 1 AND TRUE")
+        == "SELECT 0.3, a FROM t WHERE a = 1"
+    )
+
+
+def test_simplify_invalid_sql():
+    with pytest.raises(ValueError):
+        simplify("SELECT")
@@ -0,0 +1,140 @@
+// MIT License
+//
+// Copyright (c) 2019 Daniele Esposti
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use hephaestus::parse;
+use hephaestus::simplify::{simplify, simplify_expression};
+use hephaestus::sql::ExpressionParser;
+
+macro_rules! test_simplify_builder {
+    ($name:ident, $sql:expr, $expected_sql:expr) => {
+        #[test]
+        fn $name() {
+            let mut statement = parse($sql).unwrap();
+            simplify(&mut statement);
+
+            assert_eq!(statement.to_string(), $expected_sql);
+
+            // simplifying again must not change anything
+            let mut again = parse(&statement.to_string()).unwrap();
+            simplify(&mut again);
+
+            assert_eq!(again, statement);
+        }
+    };
+}
+
+test_simplify_builder!(
+    exact_arithmetic,
+    "SELECT 1 + 2 * 3, 0.1 + 0.2, 10 / 4, 1.50 - 2, 0.5 * 0.25, -(-3)",
+    "SELECT 7, 0.3, 2.5, -0.50, 0.125, 3"
+);
+
+test_simplify_builder!(
+    inexact_arithmetic,
+    "SELECT 1 / 3, 5 / 0, 1E3 + 1, 0x10 + 1, 999999999999999999999999999999999999 + 1",
+    "SELECT 1 / 3, 5 / 0, 1E3 + 1, 0x10 + 1, 999999999999999999999999999999999999 + 1"
+);
+
+test_simplify_builder!(
+    null_arithmetic,
+    "SELECT 1 + NULL, a + NULL",
+    "SELECT NULL, a + NULL"
+);
+
+test_simplify_builder!(
+    concatenation,
+    "SELECT 'a' || 'b' || c, CONCAT('a', 'b', 'c'), 'a' || NULL, CONCAT(a, 'b')",
+    "SELECT 'ab' || c, 'abc', 'a' || NULL, CONCAT(a, 'b')"
+);
+
+test_simplify_builder!(
+    double_negation,
+    "SELECT a FROM t WHERE NOT (NOT a = 1)",
+    "SELECT a FROM t WHERE a = 1"
+);
+
+test_simplify_builder!(
+    boolean_constants,
+    "SELECT a FROM t WHERE a = 1 AND TRUE OR FALSE",
+    "SELECT a FROM t WHERE a = 1"
+);
+
+test_simplify_builder!(
+    false_conjunct,
+    "SELECT a FROM t WHERE a = 1 AND 1 = 2",
+    "SELECT a FROM t WHERE FALSE"
+);
+
+test_simplify_builder!(
+    null_conjunct,
+    "SELECT a FROM t WHERE a = 1 AND NULL",
+    "SELECT a FROM t WHERE a = 1 AND NULL"
+);
+
+test_simplify_builder!(
+    comparisons,
+    "SELECT 1 < 2, 'a' = 'b', 1.0 = 1, 1 = '1', NULL = NULL, NOT TRUE",
+    "SELECT TRUE, FALSE, TRUE, 1 = '1', NULL, FALSE"
+);
+
+test_simplify_builder!(
+    empty_string_is_null,
+    "SELECT '' IS NULL, 'a' IS NULL, '' = ''",
+    "SELECT TRUE, FALSE, NULL"
+);
+
+test_simplify_builder!(
+    coalesce,
+    "SELECT COALESCE(a), COALESCE(NULL, a, 1, b), COALESCE(NULL, '')",
+    "SELECT a, COALESCE(a, 1), NULL"
+);
+
+test_simplify_builder!(
+    case_constant_guards,
+    "SELECT CASE WHEN 1 = 2 THEN 'a' WHEN b THEN 'b' WHEN TRUE THEN 'c' ELSE 'd' END FROM t",
+    "SELECT CASE WHEN b THEN 'b' ELSE 'c' END FROM t"
+);
+
+test_simplify_builder!(
+    case_constant_operand,
+    "SELECT CASE 1 WHEN 2 THEN 'a' WHEN 1 THEN 'b' END, CASE WHEN NULL THEN 1 END, CASE a WHEN 1 THEN 2 END",
+    "SELECT 'b', NULL, CASE a WHEN 1 THEN 2 END"
+);
+
+test_simplify_builder!(
+    between,
+    "SELECT 2 BETWEEN 1 AND 3, 5 NOT BETWEEN 1 AND 3, NULL BETWEEN 1 AND 2, a BETWEEN 5 AND 5, a BETWEEN 1 AND 3",
+    "SELECT TRUE, TRUE, NULL, a = 5, a BETWEEN 1 AND 3"
+);
+
+test_simplify_builder!(
+    nested_queries,
+    "SELECT a FROM (SELECT 1 + 1 AS a) AS t WHERE a > 1 + 1",
+    "SELECT a FROM (SELECT 2 AS a) AS t WHERE a > 2"
+);
+
+#[test]
+fn expression() {
+    let expr = ExpressionParser::new().parse("(1 + 1) * a").unwrap();
+
+    assert_eq!(simplify_expression(expr).to_string(), "2 * a");
+}
@@ -0,0 +1,54 @@
+// MIT License
+//
+// Copyright (c) 2019 Daniele Esposti
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use hephaestus::split_statements;
+
+macro_rules! test_split_builder {
+    ($name:ident, $sql:expr, $expected:expr) => {
+        #[test]
+        fn $name() {
+            let expected: Vec<(usize, &str)> = $expected;
+
+            assert_eq!(split_statements($sql), expected);
+        }
+    };
+}
+
+test_split_builder!(split_single_statement, "SELECT 1", vec![(0, "SELECT 1")]);
+
+test_split_builder!(
+    split_many_statements,
+    "SELECT 1;\nSELECT 2;",
+    vec![(0, "SELECT 1"), (9, "\nSELECT 2")]
+);
+
+test_split_builder!(
+    split_skips_semicolons_in_strings_and_comments,
+    "SELECT ';' -- a; b\n; SELECT 2",
+    vec![(0, "SELECT ';' -- a; b\n"), (20, " SELECT 2")]
+);
+
+test_split_builder!(
+    split_skips_blank_statements,
+    ";\n  ;SELECT 1;\n-- the end\n",
+    vec![(5, "SELECT 1")]
+);
@@ -0,0 +1,57 @@
+import pytest
+
+from hephaestus import parse, to_sql
+
+
+@pytest.mark.parametrize(
+    "sql",
+    [
+        "SELECT 1",
+        "SELECT DISTINCT a, t.* FROM t JOIN u USING (a) WHERE b IN (1, 2) ORDER BY a DESC",
+        "SELECT CAST(a AS VARCHAR(10)) FROM t WHERE a LIKE 'x%' LIMIT 10 OFFSET 5",
+        "WITH c AS (SELECT 1 AS a) SELECT a FROM c UNION ALL SELECT 2",
+        "INSERT INTO t (a, b) SELECT a, b FROM u",
+        "DELETE FROM t WHERE a IS NULL",
+    ],
+)
+def test_to_sql_round_trip(sql):
+    assert to_sql(parse(sql)) == sql
+
+
+def test_to_sql_rewrite():
+    statement = parse("SELECT a FROM t")
+    statement["expr"]["from"][0]["name"] = ["s", "u"]
+
+    assert to_sql(statement) == "SELECT a FROM s.u"
+
+
+@pytest.mark.parametrize(
+    "path, value, exception, message",
+    [
+        [("expr", "from", 0, "name"), 1, TypeError, "`expr.from[0].name`: expected a list, got int"],
+        [("expr", "columns"), None, ValueError, "`expr.columns`: missing key"],
+        [
+            ("expr", "where_expr"),
+            {"_type": "Unknown"},
+            ValueError,
+            "`expr.where_expr`: unexpected _type Unknown for Expression",
+        ],
+        [("expr",), [], ValueError, "`expr`: unexpected value [] for SetExpression"],
+    ],
+)
+def test_to_sql_invalid_dict(path, value, exception, message):
+    statement = parse("SELECT a FROM t")
+
+    parent = statement
+    for key in path[:-1]:
+        parent = parent[key]
+
+    if value is None:
+        del parent[path[-1]]
+    else:
+        parent[path[-1]] = value
+
+    with pytest.raises(exception) as e:
+        to_sql(statement)
+
+    assert str(e.value) == message
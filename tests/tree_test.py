@@ -0,0 +1,60 @@
+import pytest
+
+from hephaestus import (
+    ExprResultColumn,
+    NamedTableExpression,
+    NumericLiteral,
+    QuerySetExpression,
+    SelectStatement,
+    parse_tree,
+)
+
+
+def test_parse_tree():
+    result = parse_tree("SELECT a FROM t")
+
+    assert isinstance(result, SelectStatement)
+    assert isinstance(result.expr, QuerySetExpression)
+    assert result.common == []
+    assert result.limit is None
+
+    [table] = result.expr.from_
+
+    assert isinstance(table, NamedTableExpression)
+    assert table.name == ["t"]
+    assert table.alias is None
+
+
+def test_parse_tree_repr():
+    result = parse_tree("SELECT 1")
+
+    assert repr(result.expr.columns.values[0]) == (
+        "ExprResultColumn(expr=NumericLiteral(num='1'), rename=None)"
+    )
+
+
+@pytest.mark.parametrize(
+    "sql_a, sql_b, expected",
+    [
+        ["SELECT a FROM t", "select A from T", True],
+        ["SELECT a FROM t", "SELECT b FROM t", False],
+        ["SELECT 1", "SELECT 1 LIMIT 1", False],
+    ],
+)
+def test_parse_tree_eq(sql_a, sql_b, expected):
+    assert (parse_tree(sql_a) == parse_tree(sql_b)) is expected
+
+
+def test_parse_tree_children():
+    result = parse_tree("SELECT a, 1 FROM t")
+    query = result.expr
+
+    assert result.children() == [query]
+    assert [type(n) for n in query.columns.values[1].children()] == [NumericLiteral]
+    assert [type(n) for n in query.columns.children()] == [ExprResultColumn, ExprResultColumn]
+    assert query.from_[0].children() == []
+
+
+def test_parse_tree_invalid_sql():
+    with pytest.raises(ValueError):
+        parse_tree("SELECT")
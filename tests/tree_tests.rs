@@ -0,0 +1,55 @@
+// MIT License
+//
+// Copyright (c) 2019 Daniele Esposti
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::env;
+use std::fs;
+
+use hephaestus::tree::stub;
+
+const STUB_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/hephaestus/hephaestus.pyi");
+
+#[test]
+fn stub_is_up_to_date() {
+    let expected = stub();
+
+    if env::var_os("UPDATE_STUB").is_some() {
+        fs::write(STUB_PATH, &expected).unwrap();
+    }
+
+    let actual = fs::read_to_string(STUB_PATH).unwrap();
+
+    assert!(
+        actual == expected,
+        "the stub file is outdated, regenerate it with `UPDATE_STUB=1 make test_rust`"
+    );
+}
+
+#[test]
+fn stub_declares_classes() {
+    let stub = stub();
+
+    assert!(stub.contains("\nclass SelectStatement:\n    common: List[CommonTableExpression]\n"));
+    assert!(stub.contains("\nclass QuerySetExpression:\n    mode: SelectMode\n"));
+    assert!(stub.contains("    from_: List[TableExpression]\n"));
+    assert!(stub.contains("\nclass NullLiteral:\n    def children(self) -> List[Node]: ...\n"));
+    assert!(stub.contains("def parse_tree(sql_str: str) -> Statement: ..."));
+}
@@ -0,0 +1,166 @@
+// MIT License
+//
+// Copyright (c) 2019 Daniele Esposti
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use hephaestus::parse;
+use hephaestus::sql::ExpressionParser;
+use hephaestus::typecheck::{check, infer, TypeChecker, TypeError};
+use hephaestus::types::DataType;
+
+macro_rules! test_infer_builder {
+    ($name:ident, $sql:expr, $expected:expr) => {
+        #[test]
+        fn $name() {
+            let expr = ExpressionParser::new().parse($sql).unwrap();
+
+            assert_eq!(infer(&expr), $expected);
+        }
+    };
+}
+
+macro_rules! test_check_builder {
+    ($name:ident, $sql:expr, $expected:expr) => {
+        #[test]
+        fn $name() {
+            let result = parse($sql).unwrap();
+            let errors: Vec<String> = check(&result).into_iter().map(|e| e.message).collect();
+
+            assert_eq!(errors, $expected as Vec<&str>);
+        }
+    };
+}
+
+test_infer_builder!(infer_numeric_literal, "1", Ok(DataType::Numeric));
+test_infer_builder!(infer_string_literal, "'a'", Ok(DataType::Varchar));
+test_infer_builder!(infer_null_literal, "NULL", Ok(DataType::Generic));
+test_infer_builder!(infer_column, "a.b", Ok(DataType::Generic));
+test_infer_builder!(infer_arithmetic, "1 + 2 * 3", Ok(DataType::Numeric));
+test_infer_builder!(
+    infer_date_plus_days,
+    "DATE '2020-01-01' + 1",
+    Ok(DataType::Date)
+);
+test_infer_builder!(
+    infer_date_difference,
+    "CURRENT_DATE - DATE '2020-01-01'",
+    Ok(DataType::Numeric)
+);
+test_infer_builder!(infer_concat, "'a' || 1", Ok(DataType::Varchar));
+test_infer_builder!(infer_comparison, "a = 1", Ok(DataType::Logical));
+test_infer_builder!(
+    infer_cast,
+    "CAST(a AS DECIMAL(10, 2))",
+    Ok(DataType::Numeric)
+);
+test_infer_builder!(infer_catalogue_function, "upper(a)", Ok(DataType::Varchar));
+test_infer_builder!(
+    infer_catalogue_first_argument,
+    "add_days(CURRENT_DATE, 1)",
+    Ok(DataType::Date)
+);
+test_infer_builder!(infer_unknown_function, "my_udf(a)", Ok(DataType::Generic));
+test_infer_builder!(
+    infer_coalesce,
+    "COALESCE(a, NULL, 1)",
+    Ok(DataType::Numeric)
+);
+test_infer_builder!(
+    infer_case,
+    "CASE WHEN a = 1 THEN DATE '2020-01-01' ELSE CURRENT_TIMESTAMP END",
+    Ok(DataType::Timestamp)
+);
+
+test_infer_builder!(
+    infer_add_date_to_string,
+    "DATE '2020-01-01' + 'a'",
+    Err(vec![TypeError {
+        message: "cannot apply + to DATE and VARCHAR".to_string()
+    }])
+);
+
+test_infer_builder!(
+    infer_compare_unordered,
+    "(a = 1) < (b = 2)",
+    Err(vec![TypeError {
+        message: "cannot use < on LOGICAL values, they are not ordered".to_string()
+    }])
+);
+
+test_infer_builder!(
+    infer_compare_incompatible,
+    "'a' = 1",
+    Err(vec![TypeError {
+        message: "cannot compare VARCHAR with NUMERIC".to_string()
+    }])
+);
+
+test_infer_builder!(
+    infer_case_incompatible_branches,
+    "CASE WHEN a THEN 1 ELSE 'b' END",
+    Err(vec![TypeError {
+        message: "CASE mixes incompatible types NUMERIC and VARCHAR".to_string()
+    }])
+);
+
+test_check_builder!(
+    check_valid_query,
+    "SELECT a + 1 FROM t WHERE b = 'c'",
+    vec![]
+);
+
+test_check_builder!(
+    check_where_not_logical,
+    "SELECT a FROM t WHERE 1 + 1",
+    vec!["WHERE condition must be of type LOGICAL, found NUMERIC"]
+);
+
+test_check_builder!(
+    check_nested_select,
+    "SELECT a FROM t WHERE a IN (SELECT b FROM u WHERE 'x' + 1 = 2)",
+    vec!["cannot apply + to VARCHAR and NUMERIC"]
+);
+
+test_check_builder!(
+    check_join_constraint,
+    "SELECT * FROM a JOIN b ON a.id - 'x'",
+    vec!["cannot apply - to GENERIC and VARCHAR"]
+);
+
+#[test]
+fn check_with_column_types() {
+    let expr = ExpressionParser::new().parse("t.created_at + 'a'").unwrap();
+    let mut checker = TypeChecker::new().with_column_types(|name| match name.last() {
+        Some(column) if column == "created_at" => Some(DataType::Timestamp),
+        _ => None,
+    });
+
+    let typed = checker.annotate(&expr);
+
+    assert_eq!(typed.data_type, DataType::Generic);
+    assert_eq!(typed.children[0].data_type, DataType::Timestamp);
+    assert_eq!(typed.children[1].data_type, DataType::Varchar);
+    assert_eq!(
+        checker.into_errors(),
+        vec![TypeError {
+            message: "cannot apply + to TIMESTAMP and VARCHAR".to_string()
+        }]
+    );
+}
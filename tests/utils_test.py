@@ -26,13 +26,16 @@ def test_iter_node_attributes():
         ["SELECT 1", []],
         ["SELECT 1 FROM dual", ["dual"]],
         ["SELECT 1 FROM dual d", ["dual"]],
+        ["SELECT 1 FROM s.t", ["s.t"]],
         ["SELECT * FROM a JOIN b USING (c)", ["a", "b"]],
         ["WITH a AS (SELECT 1) SELECT * FROM a", ["a"]],
     ],
 )
 def test_iter_tables(sql, expected):
     sql_ast = parse(sql)
-    results = list(iter_tables(sql_ast))
+
+    with pytest.deprecated_call():
+        results = list(iter_tables(sql_ast))
 
     assert results == expected
 
@@ -47,6 +50,8 @@ def test_iter_tables(sql, expected):
 )
 def test_iter_ctes(sql, expected):
     sql_ast = parse(sql)
-    results = list(iter_ctes(sql_ast))
+
+    with pytest.deprecated_call():
+        results = list(iter_ctes(sql_ast))
 
     assert results == expected
@@ -1,3 +1,4 @@
+from hephaestus import parse
 from hephaestus.walk import Visitor
 
 
@@ -25,3 +26,16 @@ def test_traverse_node_in_list():
     visitor.walk()
 
     assert visitor.has_dual_table is True
+
+
+def test_traverse_parsed_ast():
+    class MyVisitor(Visitor):
+        tables = []
+
+        def visit_NamedTableExpression(self, attr, node):
+            self.tables.append(node["name"])
+
+    visitor = MyVisitor(parse("SELECT 1 FROM t JOIN u ON t.a = u.a"))
+    visitor.walk()
+
+    assert visitor.tables == [["t"], ["u"]]